@@ -0,0 +1,78 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use json_toolkit::{ChildCache, CompiledPointer, Pointer};
+
+fn compile_shallow_pointer(c: &mut Criterion) {
+    let pointer = Pointer::new("/foo/0/bar/zoo").unwrap();
+
+    c.bench_function("compile shallow pointer", |b| {
+        b.iter(|| CompiledPointer::new(black_box(&pointer)))
+    });
+}
+
+fn build_array_pointers(c: &mut Criterion) {
+    let pointer = Pointer::new("/foo/items").unwrap();
+
+    c.bench_function("build array pointers", |b| {
+        b.iter(|| {
+            for i in 0..1_000 {
+                black_box(pointer.with_index(black_box(i)));
+            }
+        })
+    });
+}
+
+fn parse_strict_pointers(c: &mut Criterion) {
+    c.bench_function("strictly parse ASCII pointer", |b| {
+        b.iter(|| Pointer::new_strict(black_box("/foo/0/bar~1baz/zoo~0/qux")))
+    });
+    c.bench_function("strictly parse non-ASCII pointer", |b| {
+        b.iter(|| Pointer::new_strict(black_box("/föo/0/bär~1baz/zoo~0/qux")))
+    });
+}
+
+fn build_child_pointers(c: &mut Criterion) {
+    let pointer = Pointer::new("/foo/items").unwrap();
+    let tokens = ["id", "name", "a/b", "~tilde", "created_at"];
+
+    c.bench_function("join child pointers", |b| {
+        b.iter(|| {
+            for _ in 0..200 {
+                for token in tokens {
+                    black_box(pointer.join(black_box(token)));
+                }
+            }
+        })
+    });
+    c.bench_function("cache child pointers", |b| {
+        let mut cache = ChildCache::with_tokens(&pointer, tokens);
+
+        b.iter(|| {
+            for _ in 0..200 {
+                for token in tokens {
+                    black_box(cache.child(black_box(token)));
+                }
+            }
+        })
+    });
+}
+
+fn decode_pointer_tokens(c: &mut Criterion) {
+    let pointer = Pointer::new("/foo/0/bar/zoo/items/42/name").unwrap();
+
+    c.bench_function("tokenize pointer", |b| {
+        b.iter(|| black_box(&pointer).tokenize().for_each(|token| drop(black_box(token))))
+    });
+    c.bench_function("borrow pointer tokens", |b| {
+        b.iter(|| black_box(&pointer).tokens().for_each(|token| drop(black_box(token))))
+    });
+}
+
+criterion_group!(
+    benches,
+    compile_shallow_pointer,
+    build_array_pointers,
+    parse_strict_pointers,
+    build_child_pointers,
+    decode_pointer_tokens
+);
+criterion_main!(benches);
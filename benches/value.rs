@@ -0,0 +1,37 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use json_toolkit::{Pointer, ValueExt};
+use serde_json::{json, Value};
+
+fn check_many_pointers(c: &mut Criterion) {
+    let users = (0..100)
+        .map(|_| json!({ "profile": { "name": "foo", "address": { "city": "bar", "zip": 42 } }, "roles": ["admin"] }))
+        .collect::<Vec<_>>();
+    let value = json!({ "data": { "users": users } });
+    let pointers = (0..200)
+        .flat_map(|i| {
+            [
+                "/profile/name",
+                "/profile/address/city",
+                "/profile/address/country",
+                "/roles/0",
+            ]
+            .into_iter()
+            .map(move |suffix| Pointer::new(format!("/data/users/{}{}", i, suffix)).unwrap())
+        })
+        .collect::<Vec<_>>();
+
+    c.bench_function("check existence of each pointer", |b| {
+        b.iter(|| {
+            black_box(&pointers)
+                .iter()
+                .map(|pointer| black_box(&value).exists(pointer))
+                .collect::<Vec<_>>()
+        })
+    });
+    c.bench_function("check existence of many pointers at once", |b| {
+        b.iter(|| Value::contains_all_mask(black_box(&value), black_box(&pointers)))
+    });
+}
+
+criterion_group!(benches, check_many_pointers);
+criterion_main!(benches);
@@ -0,0 +1,90 @@
+//! Derive macros for the [`json-toolkit`](https://docs.rs/json-toolkit/latest/json_toolkit/) crate.
+//!
+//! This crate is not meant to be used directly, enable the `macros` feature of `json-toolkit` instead.
+//!
+//! Unlike `json-toolkit`, this crate requires Rust 1.71 or later, as [`syn`](https://docs.rs/syn/latest/syn/) does.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitStr};
+
+/// Generates a `<FIELD>_POINTER` JSON pointer constant for each named field of a struct.
+///
+/// The JSON key of a field is its name, unless it is renamed with a `#[serde(rename = "...")]` attribute.
+#[proc_macro_derive(PointerConstants, attributes(serde))]
+pub fn derive_pointer_constants(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_pointer_constants(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_pointer_constants(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input,
+                    "PointerConstants only supports structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input,
+                "PointerConstants only supports structs with named fields",
+            ))
+        }
+    };
+
+    let mut constants = Vec::with_capacity(fields.len());
+
+    for field in fields {
+        // named fields always have an identifier.
+        let ident = field.ident.as_ref().unwrap();
+        let key = serde_rename(field)?.unwrap_or_else(|| ident.to_string().trim_start_matches("r#").to_string());
+        let pointer = format!("/{}", key.replace('~', "~0").replace('/', "~1"));
+        let constant = Ident::new(
+            &format!("{}_POINTER", ident.to_string().trim_start_matches("r#").to_uppercase()),
+            Span::call_site(),
+        );
+        let doc = format!("JSON pointer to the `{}` field.", ident);
+
+        constants.push(quote! {
+            #[doc = #doc]
+            pub const #constant: ::json_toolkit::Pointer<'static> = ::json_toolkit::Pointer::from_static(#pointer);
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#constants)*
+        }
+    })
+}
+
+/// Returns the JSON key given by the `#[serde(rename = "...")]` attribute of the field, if any.
+fn serde_rename(field: &syn::Field) -> Result<Option<String>, Error> {
+    let mut rename = None;
+
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.input.peek(syn::Token![=]) {
+                // skip the value of any other `key = value` serde attribute.
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(rename)
+}
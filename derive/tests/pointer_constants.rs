@@ -0,0 +1,34 @@
+use json_toolkit::{Pointer, PointerConstants};
+
+#[allow(dead_code)]
+#[derive(PointerConstants)]
+struct Config {
+    name: String,
+    max_connections: u32,
+    #[serde(rename = "a/b~c")]
+    renamed: bool,
+    r#type: String,
+}
+
+#[test]
+fn it_generates_pointer_constants() -> Result<(), json_toolkit::Error> {
+    let tests = [
+        (Config::NAME_POINTER, "/name"),
+        (Config::MAX_CONNECTIONS_POINTER, "/max_connections"),
+        (Config::RENAMED_POINTER, "/a~1b~0c"),
+        (Config::TYPE_POINTER, "/type"),
+    ];
+
+    for (pointer, expected_pointer) in tests {
+        assert_eq!(
+            pointer,
+            Pointer::new(expected_pointer)?,
+            "'{}' JSON pointer constant",
+            pointer
+        );
+    }
+
+    assert_eq!(Config::RENAMED_POINTER.key(), Some("a/b~c".to_string()));
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use crate::Pointer;
+
+/// Fluent builder of an owned JSON pointer, interleaving JSON object keys and JSON array indexes.
+///
+/// A `PointerBuilder` is created by [`Pointer::builder`] and starts from the root JSON pointer.
+///
+/// # Examples
+/// ```
+/// # use json_toolkit::Pointer;
+///
+/// let pointer = Pointer::builder().key("a").index(0).key("b/c").build();
+///
+/// assert_eq!(pointer.as_str(), "/a/0/b~1c");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PointerBuilder {
+    pointer: Pointer<'static>,
+}
+
+impl PointerBuilder {
+    /// Creates a `PointerBuilder` starting from the root JSON pointer.
+    pub fn new() -> Self {
+        Self {
+            pointer: Pointer::root(),
+        }
+    }
+
+    /// Appends the given raw JSON key, which is encoded as a reference token, see [`Pointer::push_token`].
+    pub fn key(mut self, key: &str) -> Self {
+        self.pointer.push_token(key);
+        self
+    }
+
+    /// Appends the given JSON array index, see [`Pointer::push_index`].
+    pub fn index(mut self, index: usize) -> Self {
+        self.pointer.push_index(index);
+        self
+    }
+
+    /// Builds the JSON pointer.
+    pub fn build(self) -> Pointer<'static> {
+        self.pointer
+    }
+}
+
+impl Default for PointerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn it_builds_json_pointer() -> Result<(), Error> {
+        let tests = [
+            (PointerBuilder::new(), ""),
+            (Pointer::builder().key("a").index(0).key("b"), "/a/0/b"),
+            (Pointer::builder().index(1).index(2), "/1/2"),
+            (Pointer::builder().key("a/b").key("~c").key(""), "/a~1b/~0c/"),
+            (Pointer::builder().key("0").index(0), "/0/0"),
+        ];
+
+        for (builder, expected_pointer) in tests {
+            assert_eq!(builder.build(), Pointer::new(expected_pointer)?);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use crate::Pointer;
+
+/// Memoized child JSON pointers of a fixed base JSON pointer.
+///
+/// Each child JSON pointer is encoded once, the first time it is requested, and then borrowed from the cache, which is
+/// cheaper than calling [`Pointer::join`] over and over with the same set of raw reference tokens.
+///
+/// # Examples
+/// ```
+/// # use json_toolkit::{ChildCache, Pointer};
+///
+/// let mut cache = ChildCache::new(&Pointer::new("/users").unwrap());
+///
+/// for _ in 0..3 {
+///     assert_eq!(cache.child("a/b").as_str(), "/users/a~1b");
+/// }
+///
+/// assert_eq!(cache.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChildCache {
+    base: Pointer<'static>,
+    children: HashMap<String, Pointer<'static>>,
+}
+
+impl ChildCache {
+    /// Creates an empty `ChildCache` for the given base JSON pointer.
+    pub fn new(base: &Pointer<'_>) -> Self {
+        Self {
+            base: base.clone().into_owned(),
+            children: HashMap::new(),
+        }
+    }
+
+    /// Creates a `ChildCache` for the given base JSON pointer, eagerly encoding the child JSON pointers of the given
+    /// raw reference tokens.
+    pub fn with_tokens<I, S>(base: &Pointer<'_>, tokens: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut cache = Self::new(base);
+
+        for token in tokens {
+            cache.child(token.as_ref());
+        }
+
+        cache
+    }
+
+    /// Returns the base JSON pointer.
+    pub fn base(&self) -> &Pointer<'static> {
+        &self.base
+    }
+
+    /// Returns the number of cached child JSON pointers.
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Indicates if the cache contains no child JSON pointers.
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Returns the cached child JSON pointer of the given raw reference token, if any.
+    pub fn get(&self, token: &str) -> Option<&Pointer<'static>> {
+        self.children.get(token)
+    }
+
+    /// Returns the child JSON pointer of the given raw reference token, encoding and caching it if needed.
+    pub fn child(&mut self, token: &str) -> &Pointer<'static> {
+        if !self.children.contains_key(token) {
+            let child = self.base.join(token);
+
+            self.children.insert(token.to_string(), child);
+        }
+
+        // the child JSON pointer has been cached just above if it was missing.
+        &self.children[token]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn it_caches_child_json_pointers() -> Result<(), Error> {
+        let tokens = ["foo", "", "a/b", "~0", "é"];
+
+        for base in [Pointer::root(), Pointer::new("/")?, Pointer::new("/foo/a~1b")?] {
+            let mut cache = ChildCache::new(&base);
+
+            for token in tokens.iter().chain(&tokens) {
+                assert_eq!(
+                    cache.child(token),
+                    &base.join(token),
+                    "Child '{}' of '{}' JSON pointer",
+                    token,
+                    base
+                );
+            }
+
+            assert_eq!(cache.len(), tokens.len());
+            assert_eq!(cache.base(), &base);
+            assert_eq!(cache, ChildCache::with_tokens(&base, tokens));
+            assert_eq!(cache.get("missing"), None);
+            assert_eq!(cache.get("a/b"), Some(&base.join("a/b")));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use crate::Pointer;
+
+#[cfg(feature = "smallvec")]
+type Tokens = smallvec::SmallVec<[String; 8]>;
+#[cfg(not(feature = "smallvec"))]
+type Tokens = Vec<String>;
+
+/// Pre-evaluated JSON pointer representation.
+///
+/// A `CompiledPointer` stores the decoded reference tokens of a [`Pointer`] so that looking up the same JSON pointer
+/// many times does not decode its reference tokens again, see
+/// [`ValueExt::pointer_compiled`](crate::ValueExt::pointer_compiled).
+///
+/// When the `smallvec` feature is enabled, the vector of reference tokens of JSON pointers with up to 8 reference
+/// tokens is stored inline, saving one heap allocation. Note that each reference token is still a heap-allocated
+/// [`String`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompiledPointer {
+    tokens: Tokens,
+}
+
+impl CompiledPointer {
+    /// Creates a `CompiledPointer` by evaluating the given JSON pointer into tokens.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::{CompiledPointer, Pointer};
+    ///
+    /// let pointer = CompiledPointer::new(&Pointer::new("/~1foo/bar").unwrap());
+    ///
+    /// assert_eq!(pointer.tokens(), ["/foo".to_string(), "bar".to_string()]);
+    /// ```
+    pub fn new(pointer: &Pointer<'_>) -> Self {
+        Self {
+            tokens: pointer.tokenize().collect(),
+        }
+    }
+
+    /// Returns the decoded reference tokens.
+    pub fn tokens(&self) -> &[String] {
+        &self.tokens
+    }
+
+    /// Indicates if the compiled JSON pointer points to root value.
+    pub fn is_root(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Indicates the number of reference tokens in the compiled JSON pointer.
+    pub fn depth(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Converts the compiled JSON pointer back into a [`Pointer`].
+    pub fn to_pointer(&self) -> Pointer<'static> {
+        self.tokens
+            .iter()
+            .fold(Pointer::root(), |pointer, token| pointer.join(token))
+    }
+}
+
+impl From<&Pointer<'_>> for CompiledPointer {
+    fn from(pointer: &Pointer<'_>) -> Self {
+        Self::new(pointer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn it_compiles_json_pointer_into_tokens() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), vec![]),
+            (Pointer::new("/")?, vec![""]),
+            (Pointer::new("/~1a/~0b/c")?, vec!["/a", "~b", "c"]),
+            (
+                Pointer::new("/a/b/c/d/e/f/g/h/i/j")?,
+                vec!["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"],
+            ),
+        ];
+
+        for (pointer, expected_tokens) in tests {
+            let compiled_pointer = CompiledPointer::new(&pointer);
+
+            assert_eq!(
+                compiled_pointer.tokens(),
+                expected_tokens,
+                "Tokens of '{}' JSON pointer",
+                pointer
+            );
+            assert_eq!(compiled_pointer.depth(), pointer.depth());
+            assert_eq!(compiled_pointer.is_root(), pointer.is_root());
+            assert_eq!(compiled_pointer.to_pointer(), pointer);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{Error, ValueExt};
+
+/// A view into a single JSON value located by a JSON pointer, which may either be occupied or vacant.
+///
+/// This type is returned by [`ValueExt::entry_at`].
+#[derive(Debug)]
+pub enum PointerEntry<'v, V> {
+    /// The pointee JSON value exists.
+    Occupied(OccupiedEntry<'v, V>),
+    /// The pointee JSON value does not exist but its parent JSON value does.
+    Vacant(VacantEntry<'v, V>),
+}
+
+/// A view into an existing pointee JSON value, see [`PointerEntry`].
+#[derive(Debug)]
+pub struct OccupiedEntry<'v, V> {
+    parent: &'v mut V,
+    key: String,
+}
+
+/// A view into a missing pointee JSON value whose parent JSON value exists, see [`PointerEntry`].
+#[derive(Debug)]
+pub struct VacantEntry<'v, V> {
+    parent: &'v mut V,
+    key: String,
+}
+
+impl<'v, V: ValueExt> PointerEntry<'v, V> {
+    pub(crate) fn new(parent: &'v mut V, key: String) -> Self {
+        if parent.lookup_token(&key).is_some() {
+            Self::Occupied(OccupiedEntry { parent, key })
+        } else {
+            Self::Vacant(VacantEntry { parent, key })
+        }
+    }
+
+    /// Returns the decoded last reference token of the entry's JSON pointer.
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Occupied(entry) => entry.key(),
+            Self::Vacant(entry) => entry.key(),
+        }
+    }
+}
+
+impl<'v, V: ValueExt> OccupiedEntry<'v, V> {
+    /// Returns the decoded last reference token of the entry's JSON pointer.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the pointee JSON value.
+    pub fn get(&self) -> &V {
+        // an occupied entry is only created if its pointee JSON value exists.
+        self.parent.lookup_token(&self.key).unwrap()
+    }
+
+    /// Returns the mutable pointee JSON value.
+    pub fn get_mut(&mut self) -> &mut V {
+        // an occupied entry is only created if its pointee JSON value exists.
+        self.parent.lookup_token_mut(&self.key).unwrap()
+    }
+
+    /// Converts the entry into the mutable pointee JSON value, bound to the lifetime of the entry's JSON value.
+    pub fn into_mut(self) -> &'v mut V {
+        // an occupied entry is only created if its pointee JSON value exists.
+        self.parent.lookup_token_mut(&self.key).unwrap()
+    }
+
+    /// Removes the pointee JSON value from its parent JSON value, and returns it.
+    pub fn remove(self) -> V {
+        // an occupied entry is only created if its pointee JSON value exists.
+        self.parent.remove_token(&self.key).unwrap()
+    }
+}
+
+impl<'v, V: ValueExt> VacantEntry<'v, V> {
+    /// Returns the decoded last reference token of the entry's JSON pointer.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Inserts the given JSON value into the parent JSON value, and returns the mutable inserted JSON value.
+    ///
+    /// # Errors
+    /// This method fails if the parent JSON value does not support the insertion, see [`ValueExt::insert`].
+    pub fn insert(self, value: impl Into<V>) -> Result<&'v mut V, Error> {
+        let mut key = self.key;
+
+        self.parent.insert(key.clone(), value)?;
+
+        // the `-` token appends the JSON value, which is then the last element of the JSON array.
+        if key == "-" {
+            if let Some(array) = self.parent.as_array() {
+                key = (array.len() - 1).to_string();
+            }
+        }
+
+        self.parent.lookup_token_mut(&key).ok_or(Error::UnsupportedInsertion)
+    }
+}
@@ -1,12 +1,80 @@
-use thiserror::Error;
+use alloc::boxed::Box;
+use alloc::string::String;
+
+use derive_more::Display;
 
 /// Any error that may occur when using this crate.
-#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
 pub enum Error {
-    #[error("JSON pointer must start with a leading '/' if not empty")]
+    #[display(fmt = "JSON pointer must start with a leading '/' if not empty")]
     MissingLeadingBackslash,
-    #[error("unsupported JSON value insertion")]
+    #[display(fmt = "unsupported JSON value insertion")]
     UnsupportedInsertion,
-    #[error("JSON key not found")]
+    #[display(fmt = "JSON key not found")]
     KeyNotFound,
+    #[display(fmt = "JSON key already exists")]
+    KeyAlreadyExists,
+    #[display(fmt = "JSON pointer is not a valid UTF-8 string")]
+    InvalidUtf8,
+    #[display(fmt = "JSON pointer must not be a root JSON pointer")]
+    UnexpectedRootPointer,
+    #[display(fmt = "JSON value is neither a JSON object nor a JSON array")]
+    NotAContainer,
+    #[display(fmt = "invalid JSON document: {_0}")]
+    InvalidJson(String),
+    #[display(fmt = "I/O error: {_0}")]
+    Io(String),
+    #[display(fmt = "unresolved placeholder '{_0}'")]
+    UnresolvedPlaceholder(String),
+    #[display(fmt = "JSON pointer exceeds the maximum number of traversal steps")]
+    StepLimitExceeded,
+    #[display(fmt = "unknown JSON key '{_0}'")]
+    UnknownKey(String),
+    #[display(fmt = "invalid JSON pointer at position {position}: {source}")]
+    InvalidListEntry { position: usize, source: Box<Error> },
+    #[display(fmt = "unsupported JSONPath expression '{_0}'")]
+    UnsupportedJsonPath(String),
+    #[display(fmt = "JSON document exceeds the maximum number of nodes")]
+    DocumentTooLarge,
+    #[display(fmt = "JSON patch test operation failed")]
+    PatchTestFailed,
+    #[display(fmt = "JSON pointer contains an escape sequence other than '~0' and '~1'")]
+    InvalidEscapeSequence,
+    #[display(fmt = "URI fragment must start with a leading '#' and contain well-formed percent-encoded bytes")]
+    InvalidUriFragment,
+    #[display(fmt = "JSON value is not a JSON array")]
+    NotAnArray,
+    #[display(fmt = "expected a JSON {expected}, found a JSON {found}")]
+    TypeMismatch {
+        expected: &'static str,
+        found: &'static str,
+    },
+    #[display(fmt = "JSON array index {index} is out of bounds for a JSON array of length {len}")]
+    IndexOutOfBounds { index: usize, len: usize },
+    #[display(fmt = "invalid JSON array index '{_0}'")]
+    InvalidIndex(String),
+    #[display(fmt = "JSON pointer '{_0}' does not belong to the schema")]
+    NotInSchema(String),
+    #[display(fmt = "JSON value cannot be moved into one of its descendants")]
+    MoveIntoDescendant,
+    #[display(fmt = "cannot resolve '{pointer}' JSON pointer: {source}")]
+    UnresolvedToken { pointer: String, source: Box<Error> },
+    #[display(fmt = "cannot deserialize JSON value: {_0}")]
+    Deserialization(String),
+    #[display(fmt = "unsupported conversion of '{_0}'")]
+    UnsupportedConversion(String),
+    #[display(fmt = "JSON pointers must not overlap")]
+    OverlappingPointers,
+    #[display(fmt = "relative JSON pointer must start with a non-negative integer followed by '#' or a JSON pointer")]
+    InvalidRelativePointer,
+}
+
+#[cfg(any(feature = "std", test))]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidListEntry { source, .. } | Self::UnresolvedToken { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
 }
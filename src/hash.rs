@@ -0,0 +1,54 @@
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+const FNV_128_OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+const FNV_128_PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+/// 64-bit [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/index.html) hasher.
+///
+/// Unlike [`std::hash::Hasher`] implementations, the computed hashes are guaranteed to be stable across processes,
+/// platforms and crate versions.
+pub(crate) struct Fnv64(u64);
+
+impl Fnv64 {
+    pub(crate) const fn new() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Writes a length-prefixed byte slice, so that consecutive writes cannot be confused with each other.
+    pub(crate) fn write_prefixed(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u64).to_le_bytes());
+        self.write(bytes);
+    }
+
+    pub(crate) const fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// 128-bit [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/index.html) hasher.
+///
+/// Like [`Fnv64`], the computed hashes are guaranteed to be stable across processes, platforms and crate versions.
+pub(crate) struct Fnv128(u128);
+
+impl Fnv128 {
+    pub(crate) const fn new() -> Self {
+        Self(FNV_128_OFFSET_BASIS)
+    }
+
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u128::from(*byte)).wrapping_mul(FNV_128_PRIME);
+        }
+    }
+
+    pub(crate) const fn finish(&self) -> u128 {
+        self.0
+    }
+}
@@ -0,0 +1,75 @@
+pub use serde_json::Value;
+
+use crate::Error;
+
+/// Parses a [JSON5](https://json5.org/) document, supporting comments, trailing commas and the other JSON5 syntax
+/// extensions.
+///
+/// The returned [`Value`] implements [`ValueExt`](crate::ValueExt) so it can be manipulated by JSON pointers.
+///
+/// # Errors
+/// This function fails if the input is not a valid JSON5 document.
+///
+/// # Examples
+/// ```
+/// # use json_toolkit::{json5, Pointer, ValueExt};
+///
+/// let value = json5::parse("{ // comment\n foo: [1, 2,], }").unwrap();
+///
+/// assert_eq!(ValueExt::pointer(&value, &Pointer::new("/foo/1").unwrap()), Some(&2.into()));
+/// ```
+pub fn parse(s: &str) -> Result<Value, Error> {
+    ::json5::from_str(s).map_err(|e| Error::InvalidJson(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Pointer, ValueExt};
+
+    use serde_json::json;
+
+    #[test]
+    fn it_parses_json5_document() -> Result<(), Box<dyn std::error::Error>> {
+        let s = r#"
+            // line comment
+            {
+                unquoted: 'single quoted',
+                /* block comment */
+                "trailing": [1, 2, 3,],
+                hex: 0x10,
+            }
+        "#;
+
+        let value = parse(s)?;
+
+        assert_eq!(
+            value,
+            json!({"unquoted": "single quoted", "trailing": [1, 2, 3], "hex": 16})
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_manipulates_json5_document_by_json_pointer() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = parse("{ server: { port: 8080, }, // comment\n }")?;
+
+        value.insert_at(&Pointer::new("/server/host")?, "localhost")?;
+
+        assert_eq!(
+            ValueExt::pointer(&value, &Pointer::new("/server/port")?),
+            Some(&8080.into())
+        );
+        assert_eq!(value, json!({"server": {"port": 8080, "host": "localhost"}}));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_fails_to_parse_invalid_json5_document() {
+        let result = parse("{ foo: }");
+
+        assert!(matches!(result, Err(Error::InvalidJson(_))));
+    }
+}
@@ -0,0 +1,30 @@
+/// Kind of a JSON value, as defined by [RFC8259](https://datatracker.ietf.org/doc/html/rfc8259#section-3).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum JsonType {
+    /// A JSON null value.
+    Null,
+    /// A JSON boolean value.
+    Boolean,
+    /// A JSON number value.
+    Number,
+    /// A JSON string value.
+    String,
+    /// A JSON array value.
+    Array,
+    /// A JSON object value.
+    Object,
+}
+
+impl JsonType {
+    /// Returns the lowercase name of the JSON type, e.g. `"string"`.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Boolean => "boolean",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Object => "object",
+        }
+    }
+}
@@ -6,6 +6,8 @@
 //! - Manipulate any JSON value by a JSON pointer.
 //!
 //! ```
+//! # #[cfg(feature = "serde")]
+//! # {
 //! use json_toolkit::{ValueExt, Pointer};
 //! use serde_json::{Value, json};
 //!
@@ -20,39 +22,525 @@
 //!
 //! let id = ValueExt::pointer(&json, &Pointer::new("/zoo/id").unwrap());
 //! assert_eq!(id, Some(&1.into()));
+//! # }
 //! ```
 //!
 //! ## Features
 //!
 //! `json-toolkit` supports several JSON value representation, and has features that may be enabled or disabled :
 //! - `serde`: Enable [`serde`](https://docs.rs/serde/latest/serde/) {de}serialization on [`Pointer`] type
-//! and implement [`ValueExt`]on [`serde_json::Value`](https://docs.rs/serde_json/latest/serde_json/enum.Value.html) type.
+//!   and implement [`ValueExt`] on [`serde_json::Value`](https://docs.rs/serde_json/latest/serde_json/enum.Value.html) type.
 //! - `json`: Implement [`ValueExt`] on [`json::JsonValue`](https://docs.rs/json/latest/json/enum.JsonValue.html) type.
+//! - `json5`: Parse [JSON5](https://json5.org/) documents into [`serde_json::Value`] type, enabling the `serde`
+//!   feature.
+//! - `simd-json`: Implement [`ValueExt`] on
+//!   [`simd_json::OwnedValue`](https://docs.rs/simd-json/latest/simd_json/value/owned/enum.Value.html) type. Note that
+//!   this feature requires Rust 1.88 or later, as [`simd-json`](https://docs.rs/simd-json/latest/simd_json/) does.
+//! - `smallvec`: Store [`CompiledPointer`] reference tokens in a [`SmallVec`](https://docs.rs/smallvec/latest/smallvec/)
+//!   to avoid allocating the vector of reference tokens for JSON pointers with up to 8 reference tokens.
+//! - `fast-fmt`: Format JSON array indexes with [`itoa`](https://docs.rs/itoa/latest/itoa/) instead of the
+//!   [`std::fmt`] machinery.
+//! - `macros`: Enable the `#[derive(PointerConstants)]` macro generating JSON pointer constants from struct fields.
+//!   Note that this feature requires Rust 1.71 or later, as [`syn`](https://docs.rs/syn/latest/syn/) does.
+//! - `unicode`: Sort [`Pointer`] type regardless of the case and of the diacritics of Latin letters.
+//! - `std` (enabled by default): Implement [`std::error::Error`] on [`Error`] type and provide [`ValueExt`] trait
+//!   alongside its related types. Without it, only [`Pointer`] type, [`PointerBuilder`] type and [`Error`] type are
+//!   available in a `#![no_std]` environment with an allocator.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+mod builder;
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "std")]
+mod compiled;
+#[cfg(feature = "std")]
+mod entry;
 mod error;
+mod hash;
 #[cfg(feature = "json")]
 /// [`ValueExt`] implementation for [`json::Value`][::json::JsonValue] type.
 pub mod json;
+#[cfg(feature = "json5")]
+/// [JSON5](https://json5.org/) parsing into [`ValueExt`]-compliant [`serde_json::Value`] type.
+pub mod json5;
+#[cfg(feature = "std")]
+mod json_type;
+#[cfg(feature = "std")]
+mod map;
+#[cfg(feature = "std")]
+mod options;
+#[cfg(feature = "std")]
+mod patch;
+#[cfg(feature = "std")]
+mod pattern;
 mod pointer;
+#[cfg(feature = "std")]
+mod policy;
+mod relative;
+#[cfg(feature = "std")]
+mod schema;
 #[cfg(feature = "serde")]
 /// [`ValueExt`] implementation for [`serde_json::Value`] type.
 pub mod serde;
+#[cfg(feature = "simd-json")]
+/// [`ValueExt`] implementation for [`simd_json::OwnedValue`] type.
+///
+/// Note that JSON objects are [`halfbrown`](https://docs.rs/halfbrown/latest/halfbrown/) hash maps iterated in hash
+/// order, hence the order of the entries yielded by [`ValueExt::children`] and the methods built on it, like
+/// [`ValueExt::diff`], [`ValueExt::locate`] or [`ValueExt::flatten_to_depth`], is not deterministic.
+pub mod simd;
+#[cfg(feature = "serde")]
+/// [`serde`](https://docs.rs/serde/latest/serde/) adapter {de}serializing [`Pointer`] type in its URI fragment
+/// representation, to be used with `#[serde(with = "json_toolkit::uri_fragment")]`.
+pub mod uri_fragment;
+#[cfg(feature = "std")]
+mod walk;
 
+pub use builder::PointerBuilder;
+#[cfg(feature = "std")]
+pub use cache::ChildCache;
+#[cfg(feature = "std")]
+pub use compiled::CompiledPointer;
+#[cfg(feature = "std")]
+pub use entry::{OccupiedEntry, PointerEntry, VacantEntry};
 pub use error::Error;
-pub use pointer::Pointer;
+#[cfg(feature = "macros")]
+pub use json_toolkit_derive::PointerConstants;
+#[cfg(feature = "std")]
+pub use json_type::JsonType;
+#[cfg(feature = "std")]
+pub use map::PointerMap;
+#[cfg(feature = "std")]
+pub use options::InsertOptions;
+#[cfg(feature = "std")]
+pub use patch::{Patch, PatchOperation};
+#[cfg(feature = "std")]
+pub use pattern::PointerPattern;
+pub use pointer::{binary_search_prefix, encode_token, Component, DisplayFragment, LexicalPointer, Pointer};
+#[cfg(feature = "std")]
+pub use policy::PolicyViolation;
+pub use relative::{RelativePointer, RelativeTarget};
+#[cfg(feature = "std")]
+pub use schema::{Schema, SchemaPointer};
+#[cfg(feature = "std")]
+pub use walk::Entries;
+
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+#[cfg(feature = "std")]
+use std::cmp::Ordering;
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::fmt::Write;
+
+#[cfg(feature = "std")]
+use hash::Fnv64;
+#[cfg(feature = "std")]
+use pointer::parse_index;
+
+#[cfg(feature = "std")]
+/// Looks up a value by a JSON pointer, creating missing JSON values as empty JSON objects along the way.
+fn pointer_mut_or_create<'v, V: ValueExt>(value: &'v mut V, pointer: &Pointer<'_>) -> Result<&'v mut V, Error> {
+    pointer.tokenize().try_fold(value, |value, token| {
+        let child_pointer = Pointer::root().join(&token);
+
+        if value.pointer(&child_pointer).is_none() {
+            value.insert(token, V::new_object())?;
+        }
+
+        value.pointer_mut(&child_pointer).ok_or(Error::KeyNotFound)
+    })
+}
+
+#[cfg(feature = "std")]
+/// Substitutes the `${<JSON pointer>}` placeholders of the template by their resolved value in the given document.
+fn render_template<V: ValueExt>(template: &str, document: &V) -> Result<String, Error> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut remaining = template;
+
+    while let Some((before, after)) = remaining.split_once("${") {
+        let (s, after) = match after.split_once('}') {
+            Some(placeholder) => placeholder,
+            None => break,
+        };
+
+        let pointer = Pointer::new(s)?;
+        let value = document.pointer(&pointer);
+        let value = value
+            .and_then(|value| value.as_str().map(ToString::to_string))
+            .or_else(|| value.and_then(ValueExt::as_bool).map(|b| b.to_string()))
+            .or_else(|| value.and_then(ValueExt::as_i64).map(|i| i.to_string()))
+            .or_else(|| value.and_then(ValueExt::as_f64).map(|f| f.to_string()))
+            .ok_or_else(|| Error::UnresolvedPlaceholder(s.to_string()))?;
+
+        rendered.push_str(before);
+        rendered.push_str(&value);
+        remaining = after;
+    }
+
+    rendered.push_str(remaining);
+
+    Ok(rendered)
+}
+
+#[cfg(feature = "std")]
+/// Renders a JSON value without children as JSON text.
+fn render_leaf<V: ValueExt>(value: &V) -> String {
+    if value.is_object() {
+        "{}".to_string()
+    } else if value.as_array().is_some() {
+        "[]".to_string()
+    } else if let Some(s) = value.as_str() {
+        let mut rendered = String::with_capacity(s.len() + 2);
+
+        rendered.push('"');
+
+        for c in s.chars() {
+            match c {
+                '"' => rendered.push_str("\\\""),
+                '\\' => rendered.push_str("\\\\"),
+                '\n' => rendered.push_str("\\n"),
+                '\r' => rendered.push_str("\\r"),
+                '\t' => rendered.push_str("\\t"),
+                // writing into a `String` never fails.
+                c if c.is_control() => {
+                    let _ = write!(rendered, "\\u{:04x}", c as u32);
+                }
+                c => rendered.push(c),
+            }
+        }
+
+        rendered.push('"');
+
+        rendered
+    } else if let Some(b) = value.as_bool() {
+        b.to_string()
+    } else if let Some(i) = value.as_i64() {
+        i.to_string()
+    } else if let Some(f) = value.as_f64() {
+        f.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+#[cfg(feature = "std")]
+/// Counts the JSON values of the given JSON value, including itself.
+fn count_nodes<V: ValueExt>(value: &V) -> usize {
+    1 + value
+        .children()
+        .into_iter()
+        .map(|(_, value)| count_nodes(value))
+        .sum::<usize>()
+}
+
+#[cfg(feature = "std")]
+/// Collects the pointers of the leaf JSON values which differ between both JSON values, see
+/// [`ValueExt::changed_pointers`].
+fn collect_changed_pointers<V: ValueExt + PartialEq>(
+    value: Option<&V>,
+    other: Option<&V>,
+    pointer: &Pointer<'static>,
+    changed_pointers: &mut Vec<Pointer<'static>>,
+) {
+    if let (Some(value), Some(other)) = (value, other) {
+        let children = value.children();
+        let other_children = other.children();
+        let is_same_container =
+            value.is_object() == other.is_object() && value.as_array().is_some() == other.as_array().is_some();
+
+        if is_same_container && !children.is_empty() && !other_children.is_empty() {
+            for (token, child) in &children {
+                let other_child = other_children.iter().find(|(other_token, _)| other_token == token);
+
+                collect_changed_pointers(
+                    Some(*child),
+                    other_child.map(|(_, other_child)| *other_child),
+                    &pointer.join(token),
+                    changed_pointers,
+                );
+            }
+
+            for (other_token, other_child) in &other_children {
+                if !children.iter().any(|(token, _)| token == other_token) {
+                    collect_changed_pointers(None, Some(*other_child), &pointer.join(other_token), changed_pointers);
+                }
+            }
+
+            return;
+        }
+
+        if value == other {
+            return;
+        }
+    }
+
+    for value in value.into_iter().chain(other) {
+        for (leaf_pointer, _) in value.flatten_to_depth(usize::MAX) {
+            changed_pointers.push(pointer.concat(&leaf_pointer));
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+/// Collects the JSON patch operations turning a JSON value into the other one, both located at the given pointer.
+///
+/// Elements removed from the end of a JSON array are removed from the last one so that the following operations'
+/// locations remain valid.
+fn collect_patch_operations<V: ValueExt + Clone + PartialEq>(
+    value: Option<&V>,
+    other: Option<&V>,
+    pointer: &Pointer<'static>,
+    operations: &mut Vec<PatchOperation<V>>,
+) {
+    let (value, other) = match (value, other) {
+        (Some(value), Some(other)) if value != other => (value, other),
+        (Some(_), None) => {
+            operations.push(PatchOperation::Remove { path: pointer.clone() });
+
+            return;
+        }
+        (None, Some(other)) => {
+            operations.push(PatchOperation::Add {
+                path: pointer.clone(),
+                value: other.clone(),
+            });
+
+            return;
+        }
+        _ => return,
+    };
+
+    if value.is_object() && other.is_object() {
+        for (token, child) in value.children() {
+            collect_patch_operations(
+                Some(child),
+                other.lookup_token(&token),
+                &pointer.join(&token),
+                operations,
+            );
+        }
+
+        for (token, other_child) in other.children() {
+            if value.lookup_token(&token).is_none() {
+                operations.push(PatchOperation::Add {
+                    path: pointer.join(&token),
+                    value: other_child.clone(),
+                });
+            }
+        }
+    } else if let (Some(array), Some(other_array)) = (value.as_array(), other.as_array()) {
+        for (i, (child, other_child)) in array.iter().zip(other_array).enumerate() {
+            collect_patch_operations(Some(child), Some(other_child), &pointer.with_index(i), operations);
+        }
+
+        for (i, other_child) in other_array.iter().enumerate().skip(array.len()) {
+            operations.push(PatchOperation::Add {
+                path: pointer.with_index(i),
+                value: other_child.clone(),
+            });
+        }
+
+        for i in (other_array.len()..array.len()).rev() {
+            operations.push(PatchOperation::Remove {
+                path: pointer.with_index(i),
+            });
+        }
+    } else {
+        operations.push(PatchOperation::Replace {
+            path: pointer.clone(),
+            value: other.clone(),
+        });
+    }
+}
+
+#[cfg(feature = "std")]
+/// Returns the reason why the reference token does not resolve in the given JSON value.
+fn lookup_error<V: ValueExt>(value: &V, token: &str) -> Error {
+    if value.is_object() {
+        return Error::KeyNotFound;
+    }
+
+    match (value.as_array(), parse_index(token)) {
+        (Some(array), _) if token == "-" => Error::IndexOutOfBounds {
+            index: array.len(),
+            len: array.len(),
+        },
+        (Some(array), Some(index)) if index >= array.len() => Error::IndexOutOfBounds {
+            index,
+            len: array.len(),
+        },
+        (Some(_), _) => Error::InvalidIndex(token.to_string()),
+        (None, _) => Error::NotAContainer,
+    }
+}
+
+#[cfg(feature = "std")]
+/// Rebuilds the JSON value whose leaf JSON values are given alongside their reference tokens, the first `depth` ones
+/// being skipped.
+fn build_from_flat_entries<V: ValueExt>(mut entries: Vec<(Vec<String>, V)>, depth: usize) -> Result<V, Error> {
+    match entries.len() {
+        0 => return Err(Error::KeyNotFound),
+        1 if entries[0].0.len() == depth => return Ok(entries.remove(0).1),
+        _ if entries.iter().any(|(tokens, _)| tokens.len() == depth) => return Err(Error::KeyAlreadyExists),
+        _ => {}
+    }
 
+    let mut children: BTreeMap<String, Vec<(Vec<String>, V)>> = BTreeMap::new();
+
+    for (tokens, value) in entries {
+        children.entry(tokens[depth].clone()).or_default().push((tokens, value));
+    }
+
+    let is_array = (0..children.len()).all(|i| children.contains_key(&i.to_string()));
+
+    if is_array {
+        let mut value = V::new_array();
+
+        for i in 0..children.len() {
+            // the JSON array index is guaranteed to be a key of the map above.
+            let child_entries = children.remove(&i.to_string()).unwrap();
+
+            value.insert(i.to_string(), build_from_flat_entries(child_entries, depth + 1)?)?;
+        }
+
+        Ok(value)
+    } else {
+        let mut value = V::new_object();
+
+        for (token, child_entries) in children {
+            value.insert(token, build_from_flat_entries(child_entries, depth + 1)?)?;
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(feature = "std")]
+/// Compares two JSON values, JSON values of distinct kinds being ordered as JSON null, JSON boolean, JSON number,
+/// JSON string, JSON array then JSON object. JSON arrays, and respectively JSON objects, are considered equal.
+fn compare_values<V: ValueExt>(value: &V, other: &V) -> Ordering {
+    if let (Some(i), Some(other_i)) = (value.as_i64(), other.as_i64()) {
+        return i.cmp(&other_i);
+    }
+
+    if let (Some(i), Some(other_f)) = (value.as_i64(), other.as_f64()) {
+        return compare_i64_with_f64(i, other_f);
+    }
+
+    if let (Some(f), Some(other_i)) = (value.as_f64(), other.as_i64()) {
+        return compare_i64_with_f64(other_i, f).reverse();
+    }
+
+    if let (Some(f), Some(other_f)) = (value.as_f64(), other.as_f64()) {
+        return f.partial_cmp(&other_f).unwrap_or(Ordering::Equal);
+    }
+
+    if let (Some(s), Some(other_s)) = (value.as_str(), other.as_str()) {
+        return s.cmp(other_s);
+    }
+
+    if let (Some(b), Some(other_b)) = (value.as_bool(), other.as_bool()) {
+        return b.cmp(&other_b);
+    }
+
+    value.json_type().cmp(&other.json_type())
+}
+
+#[cfg(feature = "std")]
+/// Compares an integer with a float exactly, without converting the integer into a possibly rounded float.
+fn compare_i64_with_f64(i: i64, f: f64) -> Ordering {
+    // unlike `i64::MAX`, 2^63 is exactly representable as a float.
+    const TWO_POW_63: f64 = 9_223_372_036_854_775_808.0;
+
+    if f.is_nan() {
+        Ordering::Equal
+    } else if f >= TWO_POW_63 {
+        Ordering::Less
+    } else if f < -TWO_POW_63 {
+        Ordering::Greater
+    } else {
+        // the truncated float is within the `i64` range, hence it is converted without loss.
+        let truncated_f = f.trunc();
+
+        i.cmp(&(truncated_f as i64))
+            .then_with(|| truncated_f.partial_cmp(&f).unwrap_or(Ordering::Equal))
+    }
+}
+
+#[cfg(feature = "std")]
+/// Feeds the canonical form of the JSON value into the hasher, skipping the excluded JSON pointers.
+fn hash_canonical<V: ValueExt>(value: &V, pointer: &Pointer<'_>, exclude: &[Pointer<'_>], hasher: &mut Fnv64) {
+    if value.is_object() {
+        let mut members = value
+            .children()
+            .into_iter()
+            .map(|(key, value)| (pointer.join(&key), key, value))
+            .filter(|(pointer, ..)| !exclude.contains(pointer))
+            .collect::<Vec<_>>();
+
+        members.sort_by(|(_, key_a, _), (_, key_b, _)| key_a.cmp(key_b));
+
+        hasher.write(b"o");
+        hasher.write(&(members.len() as u64).to_le_bytes());
+
+        for (pointer, key, value) in members {
+            hasher.write_prefixed(key.as_bytes());
+            hash_canonical(value, &pointer, exclude, hasher);
+        }
+    } else if let Some(array) = value.as_array() {
+        hasher.write(b"a");
+        hasher.write(&(array.len() as u64).to_le_bytes());
+
+        for (i, value) in array.iter().enumerate() {
+            let pointer = pointer.join(&i.to_string());
+
+            if exclude.contains(&pointer) {
+                hasher.write(b"x");
+            } else {
+                hash_canonical(value, &pointer, exclude, hasher);
+            }
+        }
+    } else if let Some(s) = value.as_str() {
+        hasher.write(b"s");
+        hasher.write_prefixed(s.as_bytes());
+    } else if let Some(b) = value.as_bool() {
+        hasher.write(if b { b"t" } else { b"f" });
+    } else if let Some(i) = value.as_i64() {
+        hasher.write(b"i");
+        hasher.write(&i.to_le_bytes());
+    } else if let Some(f) = value.as_f64() {
+        hasher.write(b"d");
+        hasher.write(&f.to_bits().to_le_bytes());
+    } else {
+        hasher.write(b"n");
+    }
+}
+
+#[cfg(feature = "std")]
 /// An extension trait for any JSON value representation that provides a variety of manipulation methods.
 pub trait ValueExt: Sized {
     /// Inserts any data at the given pointee JSON value.
     ///
     /// If the JSON pointer's key already exists in the JSON pointee value, it will be overrided.
     ///
+    /// When the parent JSON value is a JSON array, an existing element is replaced and returned, while the `-` token or
+    /// an index equal to the JSON array length appends the data and returns `None`.
+    ///
     /// # Arguments
     /// * `pointer`: A JSON pointer.
     /// * `value`: A data to insert at the pointee JSON value.
     ///
     /// # Errors
-    /// This method may fail if the pointee JSON value is not a JSON object or if it does not exist.
+    /// This method may fail if:
+    /// - the pointee JSON value is neither a JSON object nor a JSON array, returning [`Error::UnsupportedInsertion`].
+    /// - the pointee JSON value does not exist, returning [`Error::KeyNotFound`].
+    /// - the JSON pointer's key is an index beyond the JSON array length, returning [`Error::IndexOutOfBounds`].
+    /// - the JSON pointer's key is neither an index nor `-`, returning [`Error::InvalidIndex`].
     fn insert_at(&mut self, pointer: &Pointer<'_>, value: impl Into<Self>) -> Result<Option<Self>, Error> {
         let mut value = value.into();
 
@@ -72,13 +560,2399 @@ pub trait ValueExt: Sized {
         }
     }
 
-    /// Insert any data in the current JSON value.
+    /// Inserts any data at the given pointee JSON value like [`ValueExt::insert_at`], reporting precisely where the
+    /// insertion failed.
+    ///
+    /// # Arguments
+    /// * `pointer`: A JSON pointer.
+    /// * `value`: A data to insert at the pointee JSON value.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Error, Pointer, ValueExt};
+    /// use serde_json::json;
     ///
-    /// If the JSON value already contains the given key, it will be overrided.
+    /// let mut json = json!({ "a": { "b": {} } });
+    ///
+    /// assert_eq!(
+    ///     json.insert_at_verbose(&Pointer::new("/a/c/d/e").unwrap(), 42),
+    ///     Err(Error::UnresolvedToken {
+    ///         pointer: "/a/c".to_string(),
+    ///         source: Box::new(Error::KeyNotFound),
+    ///     })
+    /// );
+    /// # }
+    /// ```
     ///
     /// # Errors
-    /// This method may fail if the current JSON value is not a JSON object.
-    fn insert(&mut self, key: String, value: impl Into<Self>) -> Result<Option<Self>, Error>;
+    /// This method fails with [`Error::UnresolvedToken`] holding:
+    /// - the JSON pointer up to the offending reference token if the parent JSON value does not exist, see
+    ///   [`ValueExt::resolve`].
+    /// - the whole JSON pointer along with the error of [`ValueExt::insert_at`] otherwise.
+    fn insert_at_verbose(&mut self, pointer: &Pointer<'_>, value: impl Into<Self>) -> Result<Option<Self>, Error> {
+        if let Some(parent_pointer) = pointer.parent() {
+            self.resolve(&parent_pointer)?;
+        }
+
+        self.insert_at(pointer, value).map_err(|e| Error::UnresolvedToken {
+            pointer: pointer.to_string(),
+            source: Box::new(e),
+        })
+    }
+
+    /// Inserts any data at the given pointee JSON value, provided the document stays within a maximum number of nodes.
+    ///
+    /// Every JSON value is a node, including JSON objects and JSON arrays. The cap is checked against the nodes of the
+    /// current JSON value once the inserted one replaces the existing pointee JSON value, if any, before inserting it.
+    ///
+    /// # Arguments
+    /// * `pointer`: A JSON pointer.
+    /// * `value`: A data to insert at the pointee JSON value.
+    /// * `max_nodes`: The maximum number of nodes of the document.
+    ///
+    /// # Errors
+    /// This method fails with [`Error::DocumentTooLarge`] if the insertion would exceed the cap, and otherwise behaves
+    /// like [`ValueExt::insert_at`].
+    fn insert_at_capped(
+        &mut self,
+        pointer: &Pointer<'_>,
+        value: impl Into<Self>,
+        max_nodes: usize,
+    ) -> Result<Option<Self>, Error> {
+        let value = value.into();
+
+        let replaced_nodes = self.pointer(pointer).map_or(0, count_nodes);
+
+        if count_nodes(self) - replaced_nodes + count_nodes(&value) > max_nodes {
+            return Err(Error::DocumentTooLarge);
+        }
+
+        self.insert_at(pointer, value)
+    }
+
+    /// Inserts any data at the given pointee JSON value according to the given options.
+    ///
+    /// If the JSON pointer's key already exists in the JSON pointee value, it will be overrided.
+    ///
+    /// # Arguments
+    /// * `pointer`: A JSON pointer.
+    /// * `value`: A data to insert at the pointee JSON value.
+    /// * `options`: Options tuning the insertion, see [`InsertOptions`].
+    ///
+    /// # Errors
+    /// This method may fail if the pointee JSON value is not a JSON object, if it does not exist while
+    /// [`InsertOptions::create_intermediate`] is disabled or if an intermediate JSON value is a JSON scalar value.
+    fn insert_at_with(
+        &mut self,
+        pointer: &Pointer<'_>,
+        value: impl Into<Self>,
+        options: &InsertOptions,
+    ) -> Result<Option<Self>, Error> {
+        let mut value = value.into();
+
+        if pointer.is_root() {
+            std::mem::swap(self, &mut value);
+
+            return Ok(Some(value));
+        }
+
+        // both `unwrap` calls are safe here since we checked earlier than the given pointer is not a root JSON pointer.
+        let parent_pointer = pointer.parent().unwrap();
+        let pointer_key = pointer.key().unwrap();
+
+        let pointee_value = if options.create_intermediate {
+            pointer_mut_or_create(self, &parent_pointer)?
+        } else {
+            self.pointer_mut(&parent_pointer).ok_or(Error::KeyNotFound)?
+        };
+
+        if options.append_on_overflow {
+            if let (Some(array), Some(i)) = (pointee_value.as_array_mut(), parse_index(&pointer_key)) {
+                if i >= array.len() {
+                    array.push(value);
+
+                    return Ok(None);
+                }
+            }
+        }
+
+        pointee_value.insert(pointer_key, value)
+    }
+
+    /// Inserts any data at the given pointee JSON value, creating missing intermediate JSON values as empty JSON
+    /// objects.
+    ///
+    /// This is a shorthand for [`ValueExt::insert_at_with`] with [`InsertOptions::create_intermediate`] enabled.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({});
+    ///
+    /// json.insert_at_recursive(&Pointer::new("/a/b/c").unwrap(), 42).unwrap();
+    /// assert_eq!(json, json!({ "a": { "b": { "c": 42 } } }));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// This method may fail if an intermediate JSON value is a JSON scalar value, or if the JSON pointer's key is not a
+    /// valid index of a JSON array.
+    fn insert_at_recursive(&mut self, pointer: &Pointer<'_>, value: impl Into<Self>) -> Result<Option<Self>, Error> {
+        self.insert_at_with(pointer, value, &InsertOptions::new().create_intermediate(true))
+    }
+
+    /// Looks up a JSON value by a JSON pointer and returns a mutable reference to that value, inserting the JSON value
+    /// returned by the given function first if it does not exist.
+    ///
+    /// Missing intermediate JSON values are created as empty JSON objects, see [`ValueExt::insert_at_recursive`].
+    ///
+    /// # Errors
+    /// This method may fail if an intermediate JSON value is a JSON scalar value, or if the JSON pointer's key is not a
+    /// valid index of a JSON array.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({});
+    /// let tags = json.pointer_or_insert_with(&Pointer::new("/a/tags").unwrap(), || json!([])).unwrap();
+    ///
+    /// tags.as_array_mut().unwrap().push("foo".into());
+    /// assert_eq!(json, json!({ "a": { "tags": ["foo"] } }));
+    /// # }
+    /// ```
+    fn pointer_or_insert_with(
+        &mut self,
+        pointer: &Pointer<'_>,
+        default: impl FnOnce() -> Self,
+    ) -> Result<&mut Self, Error> {
+        let (parent_pointer, mut pointer_key) = match pointer.parent().zip(pointer.key()) {
+            Some(parent_and_key) => parent_and_key,
+            // the root JSON value always exists.
+            None if pointer.is_root() => return Ok(self),
+            None => return Err(Error::MissingLeadingBackslash),
+        };
+        let parent_value = pointer_mut_or_create(self, &parent_pointer)?;
+
+        if parent_value.lookup_token(&pointer_key).is_none() {
+            parent_value.insert(pointer_key.clone(), default())?;
+
+            if pointer_key == "-" {
+                if let Some(array) = parent_value.as_array() {
+                    pointer_key = (array.len() - 1).to_string();
+                }
+            }
+        }
+
+        parent_value.lookup_token_mut(&pointer_key).ok_or(Error::KeyNotFound)
+    }
+
+    /// Looks up a JSON object by a JSON pointer and returns a mutable reference to it, creating it and its missing
+    /// intermediate JSON values as empty JSON objects first if it does not exist.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "a": {} });
+    /// let object = json.ensure_object_mut(&Pointer::new("/a/b/c").unwrap()).unwrap();
+    ///
+    /// object.insert("d".to_string(), 1).unwrap();
+    /// object.insert("e".to_string(), 2).unwrap();
+    /// assert_eq!(json, json!({ "a": { "b": { "c": { "d": 1, "e": 2 } } } }));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// This method may fail if:
+    /// - an intermediate JSON value is a JSON scalar value, or the JSON pointer's key is not a valid index of a JSON
+    ///   array, see [`ValueExt::pointer_or_insert_with`].
+    /// - the pointee JSON value exists but is not a JSON object, returning [`Error::TypeMismatch`].
+    fn ensure_object_mut(&mut self, pointer: &Pointer<'_>) -> Result<&mut Self, Error> {
+        let value = self.pointer_or_insert_with(pointer, Self::new_object)?;
+
+        if value.is_object() {
+            Ok(value)
+        } else {
+            Err(Error::TypeMismatch {
+                expected: "object",
+                found: value.json_type().as_str(),
+            })
+        }
+    }
+
+    /// Looks up a JSON array by a JSON pointer and returns a mutable reference to it, creating it as an empty JSON
+    /// array and its missing intermediate JSON values as empty JSON objects first if it does not exist.
+    ///
+    /// # Errors
+    /// This method may fail if:
+    /// - an intermediate JSON value is a JSON scalar value, or the JSON pointer's key is not a valid index of a JSON
+    ///   array, see [`ValueExt::pointer_or_insert_with`].
+    /// - the pointee JSON value exists but is not a JSON array, returning [`Error::TypeMismatch`].
+    fn ensure_array_mut(&mut self, pointer: &Pointer<'_>) -> Result<&mut Vec<Self>, Error> {
+        let value = self.pointer_or_insert_with(pointer, Self::new_array)?;
+        let found = value.json_type().as_str();
+
+        value.as_array_mut().ok_or(Error::TypeMismatch {
+            expected: "array",
+            found,
+        })
+    }
+
+    /// Inserts any data at the given pointee JSON value and returns the modified JSON value, enabling chained
+    /// insertions.
+    ///
+    /// Missing intermediate JSON values are created as empty JSON objects, see [`ValueExt::insert_at_recursive`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({})
+    ///     .with_inserted(&Pointer::new("/a/b").unwrap(), 1)
+    ///     .and_then(|json| json.with_inserted(&Pointer::new("/c").unwrap(), 2))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(json, json!({ "a": { "b": 1 }, "c": 2 }));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// This method fails like [`ValueExt::insert_at_recursive`], in which case the JSON value is dropped.
+    fn with_inserted(mut self, pointer: &Pointer<'_>, value: impl Into<Self>) -> Result<Self, Error> {
+        self.insert_at_recursive(pointer, value)?;
+
+        Ok(self)
+    }
+
+    /// Inserts each data at its pointee JSON value, creating missing intermediate JSON values.
+    ///
+    /// Returns the number of inserted data. The insertion stops at the first failing data, leaving the previously
+    /// inserted ones in place.
+    ///
+    /// # Arguments
+    /// * `items`: JSON pointers with the data to insert at their pointee JSON value.
+    ///
+    /// # Errors
+    /// This method fails with [`Error::InvalidListEntry`] holding the number of inserted data as position if an
+    /// insertion fails, see [`ValueExt::insert_at_with`].
+    fn insert_many(&mut self, items: impl IntoIterator<Item = (Pointer<'static>, Self)>) -> Result<usize, Error> {
+        let options = InsertOptions::new().create_intermediate(true);
+        let mut count = 0;
+
+        for (pointer, value) in items {
+            self.insert_at_with(&pointer, value, &options)
+                .map_err(|e| Error::InvalidListEntry {
+                    position: count,
+                    source: Box::new(e),
+                })?;
+
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Inserts each data at its pointee JSON value, either all of them or none.
+    ///
+    /// The data are inserted with [`ValueExt::insert_at`] in the depth-first order of their JSON pointer, so that a
+    /// parent JSON value is inserted before its children. The data sharing the same JSON pointer are inserted in
+    /// their given order. The insertions are applied on a copy of the current JSON value, which is replaced only once
+    /// all of them succeed.
+    ///
+    /// # Arguments
+    /// * `entries`: JSON pointers with the data to insert at their pointee JSON value.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({});
+    ///
+    /// json.insert_all([
+    ///     (Pointer::new("/a/b").unwrap(), json!(1)),
+    ///     (Pointer::new("/a").unwrap(), json!({})),
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(json, json!({ "a": { "b": 1 } }));
+    ///
+    /// assert!(json.insert_all([(Pointer::new("/c").unwrap(), json!(2)), (Pointer::new("/d/e").unwrap(), json!(3))]).is_err());
+    /// assert_eq!(json, json!({ "a": { "b": 1 } }));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// This method fails with the error of the first failing insertion, see [`ValueExt::insert_at`], in which case the
+    /// current JSON value is left untouched.
+    fn insert_all<I>(&mut self, entries: I) -> Result<(), Error>
+    where
+        Self: Clone,
+        I: IntoIterator<Item = (Pointer<'static>, Self)>,
+    {
+        let mut entries = entries.into_iter().collect::<Vec<_>>();
+        // a stable sort keeps the given order of the data sharing the same JSON pointer.
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut inserted_value = self.clone();
+
+        for (pointer, value) in entries {
+            inserted_value.insert_at(&pointer, value)?;
+        }
+
+        *self = inserted_value;
+
+        Ok(())
+    }
+
+    /// Expands the given shape template into the JSON pointers of the matching JSON values, in a depth-first order.
+    ///
+    /// The shape template is a [`PointerPattern`], `*` standing for any single reference token and `**` for any
+    /// sequence of reference tokens. Returns an empty list if the shape template is not a valid [`PointerPattern`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "items": [{ "name": "foo" }, { "name": "bar" }] });
+    ///
+    /// assert_eq!(
+    ///     json.expand_shape("/items/*/name"),
+    ///     vec![Pointer::new("/items/0/name").unwrap(), Pointer::new("/items/1/name").unwrap()]
+    /// );
+    /// # }
+    /// ```
+    fn expand_shape(&self, shape: &str) -> Vec<Pointer<'static>> {
+        PointerPattern::new(shape)
+            .map(|pattern| pattern.select(self).into_iter().map(|(pointer, _)| pointer).collect())
+            .unwrap_or_default()
+    }
+
+    /// Looks up all the JSON values whose JSON pointer matches the given pattern, alongside their concrete JSON
+    /// pointer, in a depth-first order.
+    ///
+    /// `*` matches any single JSON object key or JSON array index while `~2` encodes a literal `*` JSON object key,
+    /// see [`PointerPattern`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, PointerPattern, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "items": [{ "name": "a" }, { "name": "b" }] });
+    ///
+    /// assert_eq!(
+    ///     json.query(&PointerPattern::new("/items/*/name").unwrap()),
+    ///     vec![
+    ///         (Pointer::new("/items/0/name").unwrap(), &json!("a")),
+    ///         (Pointer::new("/items/1/name").unwrap(), &json!("b")),
+    ///     ]
+    /// );
+    /// # }
+    /// ```
+    fn query(&self, pattern: &PointerPattern) -> Vec<(Pointer<'static>, &Self)> {
+        pattern.select(self)
+    }
+
+    /// Sets every JSON value whose JSON pointer matches the given pattern to a clone of the given JSON value.
+    ///
+    /// Matching JSON values are set in a depth-first order, hence a matching JSON value located under another matching
+    /// JSON value is overridden along with its ancestor and is neither set again nor counted, even if the given JSON
+    /// value contains it.
+    ///
+    /// Returns the number of JSON values set.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{PointerPattern, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "items": [{ "active": false }, { "active": false }] });
+    /// let count = json.set_where_matches(&PointerPattern::new("/items/*/active").unwrap(), true.into());
+    ///
+    /// assert_eq!(count, 2);
+    /// assert_eq!(json, json!({ "items": [{ "active": true }, { "active": true }] }));
+    /// # }
+    /// ```
+    fn set_where_matches(&mut self, pattern: &PointerPattern, value: Self) -> usize
+    where
+        Self: Clone,
+    {
+        let mut pointers = pattern
+            .select(self)
+            .into_iter()
+            .map(|(pointer, _)| pointer)
+            .collect::<Vec<_>>();
+        let mut set_pointers = HashSet::new();
+
+        // ancestors are sorted first, hence they are set before their descendants.
+        pointers.sort();
+
+        for pointer in pointers {
+            if pointer
+                .ancestors()
+                .any(|ancestor| set_pointers.contains(ancestor.as_str()))
+            {
+                continue;
+            }
+
+            if let Some(pointee_value) = self.pointer_mut(&pointer) {
+                *pointee_value = value.clone();
+                set_pointers.insert(pointer.to_string());
+            }
+        }
+
+        set_pointers.len()
+    }
+
+    /// Returns a deep copy of the current JSON value where every JSON value matching any of the given patterns is
+    /// replaced by a clone of the given mask.
+    ///
+    /// A matching JSON object or JSON array is masked as a whole, its descendants being never checked against the
+    /// patterns. The current JSON value is left untouched, which makes it handy to redact secrets before logging.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{PointerPattern, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "user": { "name": "foo", "password": "bar" } });
+    /// let masked_json = json.masked(&[PointerPattern::new("/**/password").unwrap()], &json!("***"));
+    ///
+    /// assert_eq!(masked_json, json!({ "user": { "name": "foo", "password": "***" } }));
+    /// # }
+    /// ```
+    fn masked(&self, patterns: &[PointerPattern], mask: &Self) -> Self
+    where
+        Self: Clone,
+    {
+        fn mask_from<V: ValueExt + Clone>(
+            value: &mut V,
+            pointer: Pointer<'static>,
+            patterns: &[PointerPattern],
+            mask: &V,
+        ) {
+            if patterns.iter().any(|pattern| pattern.matches(&pointer)) {
+                *value = mask.clone();
+
+                return;
+            }
+
+            let tokens = value.children().into_iter().map(|(token, _)| token).collect::<Vec<_>>();
+
+            for token in tokens {
+                if let Some(child) = value.lookup_token_mut(&token) {
+                    mask_from(child, pointer.join(&token), patterns, mask);
+                }
+            }
+        }
+
+        let mut masked_value = self.clone();
+
+        mask_from(&mut masked_value, Pointer::root(), patterns, mask);
+
+        masked_value
+    }
+
+    /// Inserts any data at the given pointee JSON value, failing if the JSON pointer's key already exists.
+    ///
+    /// Unlike [`ValueExt::insert_at`], the pointee JSON value is never overrided: the existence check and the insertion
+    /// happen in a single lookup, so that there is no need to check [`ValueExt::exists`] beforehand.
+    ///
+    /// # Arguments
+    /// * `pointer`: A JSON pointer.
+    /// * `value`: A data to insert at the pointee JSON value.
+    ///
+    /// # Errors
+    /// This method may fail if:
+    /// - the JSON pointer's key already exists, returning [`Error::KeyAlreadyExists`] and leaving the current JSON
+    ///   value untouched. Note that the root JSON value always exists.
+    /// - the parent JSON value does not exist, returning [`Error::KeyNotFound`].
+    /// - the parent JSON value is neither a JSON object nor a JSON array, or the JSON pointer's key is not a valid
+    ///   index of a JSON array, see [`ValueExt::insert_at`].
+    fn insert_at_strict(&mut self, pointer: &Pointer<'_>, value: impl Into<Self>) -> Result<(), Error> {
+        if pointer.is_root() {
+            return Err(Error::KeyAlreadyExists);
+        }
+
+        // both `unwrap` calls are safe here since we checked earlier than the given pointer is not a root JSON pointer.
+        let parent_pointer = pointer.parent().unwrap();
+        let pointer_key = pointer.key().unwrap();
+
+        match self.pointer_mut(&parent_pointer) {
+            Some(pointee_value) => pointee_value.insert_strict(pointer_key, value),
+            None => Err(Error::KeyNotFound),
+        }
+    }
+
+    /// Inserts any data at the given pointee JSON value, failing on conflict with the JSON pointer in the error.
+    ///
+    /// This method behaves like [`ValueExt::insert_at_strict`], the current JSON value being left untouched if the
+    /// JSON pointer's key already exists.
+    ///
+    /// # Arguments
+    /// * `pointer`: A JSON pointer.
+    /// * `value`: A data to insert at the pointee JSON value.
+    ///
+    /// # Errors
+    /// This method fails with [`Error::UnresolvedToken`] holding the JSON pointer along with
+    /// [`Error::KeyAlreadyExists`] if the JSON pointer's key already exists, including for the root JSON pointer, and
+    /// otherwise behaves like [`ValueExt::insert_at_strict`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Error, Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "a": 1 });
+    ///
+    /// json.try_insert_at(&Pointer::new("/b").unwrap(), 2).unwrap();
+    /// assert_eq!(
+    ///     json.try_insert_at(&Pointer::new("/a").unwrap(), 3),
+    ///     Err(Error::UnresolvedToken { pointer: "/a".to_string(), source: Box::new(Error::KeyAlreadyExists) })
+    /// );
+    /// assert_eq!(json, json!({ "a": 1, "b": 2 }));
+    /// # }
+    /// ```
+    fn try_insert_at(&mut self, pointer: &Pointer<'_>, value: impl Into<Self>) -> Result<(), Error> {
+        self.insert_at_strict(pointer, value).map_err(|e| match e {
+            Error::KeyAlreadyExists => Error::UnresolvedToken {
+                pointer: pointer.to_string(),
+                source: Box::new(e),
+            },
+            e => e,
+        })
+    }
+
+    /// Insert any data in the current JSON value.
+    ///
+    /// If the JSON value already contains the given key, it will be overrided. If the JSON value is a JSON array, the
+    /// key is either an index lower or equal to its length, or the `-` token which appends the data, see
+    /// [`ValueExt::insert_at`].
+    ///
+    /// # Errors
+    /// This method may fail if the current JSON value is neither a JSON object nor a JSON array, or if the key is not
+    /// a valid index of the JSON array.
+    fn insert(&mut self, key: String, value: impl Into<Self>) -> Result<Option<Self>, Error>;
+
+    /// Insert any data in the current JSON value, failing if the given key already exists.
+    ///
+    /// # Errors
+    /// This method may fail if the current JSON value is neither a JSON object nor a JSON array, if it already contains
+    /// the given key or if the key is not a valid index of the JSON array.
+    fn insert_strict(&mut self, key: String, value: impl Into<Self>) -> Result<(), Error>;
+
+    /// Looks up the parent of the pointee JSON value and returns it alongside the decoded JSON pointer's key.
+    ///
+    /// This method is useful to implement custom manipulations on the pointee JSON value, the JSON pointer's key
+    /// does not have to exist in the returned JSON object or JSON array.
+    ///
+    /// # Errors
+    /// This method may fail if the given JSON pointer is a root JSON pointer, or if the parent JSON value does not
+    /// exist or is neither a JSON object nor a JSON array.
+    fn split_target_mut(&mut self, pointer: &Pointer<'_>) -> Result<(&mut Self, String), Error> {
+        let (parent_pointer, pointer_key) = pointer
+            .parent()
+            .zip(pointer.key())
+            .ok_or(Error::UnexpectedRootPointer)?;
+
+        match self.pointer_mut(&parent_pointer) {
+            Some(pointee_value) if pointee_value.is_object() || pointee_value.as_array().is_some() => {
+                Ok((pointee_value, pointer_key))
+            }
+            Some(_) => Err(Error::NotAContainer),
+            None => Err(Error::KeyNotFound),
+        }
+    }
+
+    /// Applies a [RFC6902](https://datatracker.ietf.org/doc/html/rfc6902) JSON patch on the current JSON value.
+    ///
+    /// Operations are applied in order and the first failing one aborts the patch.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Patch, ValueExt};
+    /// use serde_json::{json, Value};
+    ///
+    /// let mut json = json!({ "foo": ["bar", "baz"] });
+    /// let patch: Patch<Value> = serde_json::from_value(json!([
+    ///     { "op": "add", "path": "/foo/1", "value": "qux" },
+    ///     { "op": "remove", "path": "/foo/0" },
+    /// ]))
+    /// .unwrap();
+    ///
+    /// json.apply_patch(&patch).unwrap();
+    /// assert_eq!(json, json!({ "foo": ["qux", "baz"] }));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// This method fails with [`Error::PatchTestFailed`] if a `test` operation fails, or with the error of any other
+    /// failing operation, in which case the current JSON value is left untouched.
+    fn apply_patch(&mut self, patch: &Patch<Self>) -> Result<(), Error>
+    where
+        Self: Clone + PartialEq,
+    {
+        let mut patched_value = self.clone();
+
+        for operation in patch.operations() {
+            operation.apply(&mut patched_value)?;
+        }
+
+        *self = patched_value;
+
+        Ok(())
+    }
+
+    /// Applies a JSON patch computed against the given base JSON value, and detects the conflicting locations.
+    ///
+    /// A location is conflicting if the current JSON value differs from the base JSON value at the `path`, or at the
+    /// `from`, location of an operation, meaning that the location was modified locally. Conflicting locations are
+    /// detected before applying the patch, and the patch is applied regardless of the conflicts.
+    ///
+    /// Returns the conflicting locations in the order of the operations, without duplicates.
+    ///
+    /// # Errors
+    /// This method fails if an operation cannot be applied, in which case the current JSON value is left untouched.
+    fn apply_with_base(&mut self, patch: &Patch<Self>, base: &Self) -> Result<Vec<Pointer<'static>>, Error>
+    where
+        Self: Clone + PartialEq,
+    {
+        let mut conflicts: Vec<Pointer<'static>> = Vec::new();
+
+        for operation in patch.operations() {
+            for pointer in operation.from().into_iter().chain(Some(operation.path())) {
+                if self.pointer(pointer) != base.pointer(pointer) && !conflicts.contains(pointer) {
+                    conflicts.push(pointer.clone());
+                }
+            }
+        }
+
+        self.apply_patch(patch)?;
+
+        Ok(conflicts)
+    }
+
+    /// Splits the current JSON value into shards, one per top-level JSON object key or JSON array index.
+    ///
+    /// Each shard is returned with its pointer, in the iteration order of the current JSON value. Returns an empty
+    /// list if the current JSON value is a JSON scalar value.
+    fn shard_top_level(&self) -> Vec<(Pointer<'static>, &Self)> {
+        self.children()
+            .into_iter()
+            .map(|(token, value)| (Pointer::root().join(&token), value))
+            .collect()
+    }
+
+    /// Moves the JSON value located at `from` to the `to` location, provided it exists.
+    ///
+    /// The JSON value is moved as described by the `move` operation of
+    /// [RFC6902](https://datatracker.ietf.org/doc/html/rfc6902#section-4.4). Returns `true` if the JSON value was
+    /// moved, or `false` if `from` does not resolve, in which case the current JSON value is left untouched.
+    ///
+    /// # Errors
+    /// This method fails with [`Error::MoveIntoDescendant`] if `from` is an ancestor of `to`, even when it does not
+    /// resolve, or if the JSON value cannot be added at the `to` location, in which case the current JSON value is left
+    /// untouched.
+    fn move_if_present(&mut self, from: &Pointer<'_>, to: &Pointer<'_>) -> Result<bool, Error>
+    where
+        Self: Clone,
+    {
+        if from != to && from.is_ancestor_of(to) {
+            return Err(Error::MoveIntoDescendant);
+        }
+
+        if self.pointer(from).is_none() {
+            return Ok(false);
+        }
+
+        patch::move_at(self, from, to).map(|_| true)
+    }
+
+    /// Swaps the pointee JSON values of the two given JSON pointers in place.
+    ///
+    /// Swapping a JSON value with itself is a no-op, the JSON pointers being compared once their reference tokens are
+    /// decoded, e.g. `/~` and `/~0` both address the same JSON value.
+    ///
+    /// # Errors
+    /// This method fails with:
+    /// - [`Error::KeyNotFound`] if either pointee JSON value does not exist.
+    /// - [`Error::OverlappingPointers`] if one JSON pointer is a strict ancestor of the other, see
+    ///   [`Pointer::is_ancestor_of`].
+    ///
+    /// In both cases the current JSON value is left untouched.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "a": [1, 2], "b": { "c": 3 } });
+    ///
+    /// json.swap_at(&Pointer::new("/a/0").unwrap(), &Pointer::new("/b/c").unwrap()).unwrap();
+    /// assert_eq!(json, json!({ "a": [3, 2], "b": { "c": 1 } }));
+    /// # }
+    /// ```
+    fn swap_at(&mut self, a: &Pointer<'_>, b: &Pointer<'_>) -> Result<(), Error> {
+        if self.pointer(a).is_none() || self.pointer(b).is_none() {
+            return Err(Error::KeyNotFound);
+        }
+
+        let (a, b) = (&a.normalized(), &b.normalized());
+
+        if a == b {
+            return Ok(());
+        }
+
+        if a.is_ancestor_of(b) || b.is_ancestor_of(a) {
+            return Err(Error::OverlappingPointers);
+        }
+
+        // both `unwrap` calls are safe here since the JSON pointers do not overlap, hence replacing one pointee JSON
+        // value leaves the other one in place.
+        let value_a = std::mem::replace(self.pointer_mut(a).unwrap(), Self::new_null());
+        let value_b = std::mem::replace(self.pointer_mut(b).unwrap(), value_a);
+
+        *self.pointer_mut(a).unwrap() = value_b;
+
+        Ok(())
+    }
+
+    /// Ensures the pointee JSON value exists, inserting a JSON null value if it does not.
+    ///
+    /// Missing intermediate JSON values are created as empty JSON objects, while an existing pointee JSON value is
+    /// left untouched.
+    ///
+    /// # Errors
+    /// This method fails if an intermediate JSON value or the parent JSON value does not support the insertion, e.g.
+    /// if it is a JSON scalar value.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "foo": 42 });
+    ///
+    /// json.touch_at(&Pointer::new("/foo").unwrap()).unwrap();
+    /// json.touch_at(&Pointer::new("/bar/zoo").unwrap()).unwrap();
+    ///
+    /// assert_eq!(json, json!({ "foo": 42, "bar": { "zoo": null } }));
+    /// # }
+    /// ```
+    fn touch_at(&mut self, pointer: &Pointer<'_>) -> Result<(), Error> {
+        if self.pointer(pointer).is_some() {
+            return Ok(());
+        }
+
+        // the root JSON value always exists, hence only a JSON pointer without leading `/` has no parent.
+        let (parent_pointer, pointer_key) = pointer
+            .parent()
+            .zip(pointer.key())
+            .ok_or(Error::MissingLeadingBackslash)?;
+
+        pointer_mut_or_create(self, &parent_pointer)?
+            .insert(pointer_key, Self::new_null())
+            .map(|_| ())
+    }
+
+    /// Replaces the pointee JSON value, returning the replaced JSON value alongside a mutable reference to the new one.
+    ///
+    /// # Errors
+    /// This method fails with [`Error::KeyNotFound`] if the pointee JSON value does not exist.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "foo": { "bar": 1 } });
+    /// let (old_value, new_value) = json.replace_returning(&Pointer::new("/foo/bar").unwrap(), 2).unwrap();
+    ///
+    /// assert_eq!(old_value, json!(1));
+    /// *new_value = json!(3);
+    /// assert_eq!(json, json!({ "foo": { "bar": 3 } }));
+    /// # }
+    /// ```
+    fn replace_returning<'s>(
+        &'s mut self,
+        pointer: &Pointer<'_>,
+        value: impl Into<Self>,
+    ) -> Result<(Self, &'s mut Self), Error> {
+        let target = self.pointer_mut(pointer).ok_or(Error::KeyNotFound)?;
+        let old_value = std::mem::replace(target, value.into());
+
+        Ok((old_value, target))
+    }
+
+    /// Replaces the pointee JSON value, and returns the replaced JSON value.
+    ///
+    /// Unlike [`ValueExt::insert_at`], the pointee JSON value must already exist as required by the `replace` operation
+    /// of [RFC6902](https://datatracker.ietf.org/doc/html/rfc6902#section-4.3), hence a missing JSON key is never
+    /// created and an out-of-bounds JSON array index never appends the value.
+    ///
+    /// # Errors
+    /// This method fails with [`Error::KeyNotFound`] if the pointee JSON value does not exist.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Error, Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "foo": [1, 2] });
+    ///
+    /// assert_eq!(json.replace_at(&Pointer::new("/foo/1").unwrap(), 3), Ok(json!(2)));
+    /// assert_eq!(json.replace_at(&Pointer::new("/foo/2").unwrap(), 4), Err(Error::KeyNotFound));
+    /// assert_eq!(json, json!({ "foo": [1, 3] }));
+    /// # }
+    /// ```
+    fn replace_at(&mut self, pointer: &Pointer<'_>, value: impl Into<Self>) -> Result<Self, Error> {
+        self.replace_returning(pointer, value).map(|(old_value, _)| old_value)
+    }
+
+    /// Returns the pointers of the leaf JSON values which were modified, added or removed in the other JSON value.
+    ///
+    /// Leaf JSON values are JSON values without children, i.e. JSON scalar values, empty JSON objects and empty JSON
+    /// arrays. When a JSON value is replaced by a JSON value of another kind, the pointers of the leaf JSON values of
+    /// both are returned. Pointers are sorted as defined by the [`Ord`] implementation of [`Pointer`], without
+    /// duplicates.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "a": 1, "b": { "c": 2 } });
+    /// let other = json!({ "a": 1, "b": { "c": 3 } });
+    ///
+    /// assert_eq!(json.changed_pointers(&other), vec![Pointer::new("/b/c").unwrap()]);
+    /// # }
+    /// ```
+    fn changed_pointers(&self, other: &Self) -> Vec<Pointer<'static>>
+    where
+        Self: PartialEq,
+    {
+        let mut changed_pointers = Vec::new();
+
+        collect_changed_pointers(Some(self), Some(other), &Pointer::root(), &mut changed_pointers);
+
+        changed_pointers.sort();
+        changed_pointers.dedup();
+
+        changed_pointers
+    }
+
+    /// Computes the JSON patch operations turning the JSON value located at the given prefix into the one located at
+    /// the same prefix in the other JSON value.
+    ///
+    /// Other JSON values are ignored and the operations' locations are rooted at the prefix. Applying the operations
+    /// on the current JSON value makes its subtree at the prefix equal to the other one.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{PatchOperation, Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "a": 1, "b": { "c": 2 } });
+    /// let other = json!({ "a": 2, "b": { "c": 3 } });
+    ///
+    /// assert_eq!(
+    ///     json.diff_under(&other, &Pointer::new("/b").unwrap()),
+    ///     vec![PatchOperation::Replace { path: Pointer::new("/b/c").unwrap(), value: json!(3) }]
+    /// );
+    /// # }
+    /// ```
+    fn diff_under(&self, other: &Self, prefix: &Pointer<'_>) -> Vec<PatchOperation<Self>>
+    where
+        Self: Clone + PartialEq,
+    {
+        let mut operations = Vec::new();
+
+        collect_patch_operations(
+            self.pointer(prefix),
+            other.pointer(prefix),
+            &prefix.clone().into_owned(),
+            &mut operations,
+        );
+
+        operations
+    }
+
+    /// Computes the [RFC6902](https://datatracker.ietf.org/doc/html/rfc6902) JSON patch turning the current JSON value
+    /// into the other one.
+    ///
+    /// JSON objects are diffed key by key while JSON arrays are diffed index by index, hence the patch only holds
+    /// `add`, `remove` and `replace` operations. Applying it with [`ValueExt::apply_patch`] on the current JSON value
+    /// makes it equal to the other one.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{PatchOperation, Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "a/b": 1, "c": [1, 2] });
+    /// let other = json!({ "a/b": 2, "c": [1] });
+    /// let patch = json.diff(&other);
+    ///
+    /// assert_eq!(
+    ///     patch.operations(),
+    ///     [
+    ///         PatchOperation::Replace { path: Pointer::new("/a~1b").unwrap(), value: json!(2) },
+    ///         PatchOperation::Remove { path: Pointer::new("/c/1").unwrap() },
+    ///     ]
+    /// );
+    ///
+    /// json.apply_patch(&patch).unwrap();
+    /// assert_eq!(json, other);
+    /// # }
+    /// ```
+    fn diff(&self, other: &Self) -> Patch<Self>
+    where
+        Self: Clone + PartialEq,
+    {
+        Patch::new(self.diff_under(other, &Pointer::root()))
+    }
+
+    /// Applies each update function to its pointee JSON value, on a best-effort basis.
+    ///
+    /// Unlike [`ValueExt::apply_with_base`], a failing update does not abort the remaining ones. Returns the result of
+    /// each update, in the given order.
+    ///
+    /// # Errors
+    /// Each returned result fails with [`Error::KeyNotFound`] if the pointee JSON value of the update does not exist.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Error, Pointer, ValueExt};
+    /// use serde_json::{json, Value};
+    ///
+    /// let mut json = json!({ "count": 1 });
+    /// let results = json.update_each(vec![
+    ///     (Pointer::new("/count").unwrap(), Box::new(|value: &mut Value| *value = json!(2))),
+    ///     (Pointer::new("/missing").unwrap(), Box::new(|value: &mut Value| *value = json!(3))),
+    /// ]);
+    ///
+    /// assert_eq!(results, vec![Ok(()), Err(Error::KeyNotFound)]);
+    /// assert_eq!(json, json!({ "count": 2 }));
+    /// # }
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn update_each(&mut self, updates: Vec<(Pointer<'_>, Box<dyn FnOnce(&mut Self)>)>) -> Vec<Result<(), Error>> {
+        updates
+            .into_iter()
+            .map(|(pointer, update)| {
+                let pointee_value = self.pointer_mut(&pointer).ok_or(Error::KeyNotFound)?;
+
+                update(pointee_value);
+
+                Ok(())
+            })
+            .collect()
+    }
+
+    /// Gets the entry of the pointee JSON value for in-place manipulation.
+    ///
+    /// # Errors
+    /// This method fails if:
+    /// - The given JSON pointer is the root JSON pointer, as it has no parent JSON value.
+    /// - The parent JSON value does not exist or is neither a JSON object nor a JSON array.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, PointerEntry, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "foo": {} });
+    ///
+    /// if let PointerEntry::Vacant(entry) = json.entry_at(&Pointer::new("/foo/bar").unwrap()).unwrap() {
+    ///     entry.insert(42).unwrap();
+    /// }
+    ///
+    /// assert_eq!(json, json!({ "foo": { "bar": 42 } }));
+    /// # }
+    /// ```
+    fn entry_at(&mut self, pointer: &Pointer<'_>) -> Result<PointerEntry<'_, Self>, Error> {
+        let (parent, key) = self.split_target_mut(pointer)?;
+
+        Ok(PointerEntry::new(parent, key))
+    }
+
+    /// Looks up the pointer of the first JSON value satisfying the given predicate.
+    ///
+    /// JSON values are visited in a depth-first order, starting from the current JSON value which is pointed by the
+    /// root JSON pointer.
+    ///
+    /// # Arguments
+    /// * `pred`: A predicate applied on each visited JSON value.
+    fn locate(&self, pred: impl Fn(&Self) -> bool) -> Option<Pointer<'static>> {
+        fn locate_from<V: ValueExt>(
+            value: &V,
+            pointer: Pointer<'static>,
+            pred: &impl Fn(&V) -> bool,
+        ) -> Option<Pointer<'static>> {
+            if pred(value) {
+                return Some(pointer);
+            }
+
+            value
+                .children()
+                .into_iter()
+                .find_map(|(token, child)| locate_from(child, pointer.join(&token), pred))
+        }
+
+        locate_from(self, Pointer::root(), &pred)
+    }
+
+    /// Flattens the current JSON value down to the given depth.
+    ///
+    /// Every JSON value without children (JSON scalar values, empty JSON objects and empty JSON arrays) is returned
+    /// with its pointer, while JSON values located at the given depth are returned as a whole without being expanded.
+    /// JSON values are returned in a depth-first order.
+    ///
+    /// # Arguments
+    /// * `depth`: The maximum depth of the returned JSON pointers, see [`Pointer::depth`].
+    fn flatten_to_depth(&self, depth: usize) -> Vec<(Pointer<'static>, &Self)> {
+        fn flatten_from<'v, V: ValueExt>(
+            value: &'v V,
+            pointer: Pointer<'static>,
+            depth: usize,
+            values: &mut Vec<(Pointer<'static>, &'v V)>,
+        ) {
+            let children = value.children();
+
+            if depth == 0 || children.is_empty() {
+                values.push((pointer, value));
+            } else {
+                for (token, child) in children {
+                    flatten_from(child, pointer.join(&token), depth - 1, values);
+                }
+            }
+        }
+
+        let mut values = Vec::new();
+
+        flatten_from(self, Pointer::root(), depth, &mut values);
+
+        values
+    }
+
+    /// Flattens the current JSON value into a map from the pointer of each leaf JSON value to the leaf JSON value.
+    ///
+    /// Leaf JSON values are JSON values without children, i.e. JSON scalar values, empty JSON objects and empty JSON
+    /// arrays, so that no location of the current JSON value is lost. Non-empty JSON objects and JSON arrays are not
+    /// recorded themselves. The map is sorted by the depth-first order of [`Pointer`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "foo": { "bar": [1, {}] } });
+    /// let flattened = json.flatten();
+    ///
+    /// assert_eq!(
+    ///     flattened.keys().map(Pointer::as_str).collect::<Vec<_>>(),
+    ///     vec!["/foo/bar/0", "/foo/bar/1"]
+    /// );
+    /// assert_eq!(flattened[&Pointer::new("/foo/bar/1").unwrap()], &json!({}));
+    /// # }
+    /// ```
+    fn flatten(&self) -> BTreeMap<Pointer<'static>, &Self> {
+        self.flatten_to_depth(usize::MAX).into_iter().collect()
+    }
+
+    /// Converts the current JSON value into a map from the pointer of each leaf JSON value to the leaf JSON value.
+    ///
+    /// Leaf JSON values are JSON values without children, i.e. JSON scalar values, empty JSON objects and empty JSON
+    /// arrays, hence the current JSON value can be rebuilt with [`ValueExt::from_flat_map`].
+    ///
+    /// Note that the map only holds leaf JSON values, hence a non-empty JSON object whose keys are exactly the JSON
+    /// array indexes `0` to `n - 1`, e.g. `{ "0": 1 }`, cannot be told apart from a JSON array and is rebuilt as such.
+    fn into_flat_map(self) -> HashMap<Pointer<'static>, Self> {
+        fn drain_into<V: ValueExt>(mut value: V, pointer: Pointer<'static>, map: &mut HashMap<Pointer<'static>, V>) {
+            let tokens = value.children().into_iter().map(|(token, _)| token).collect::<Vec<_>>();
+
+            if tokens.is_empty() {
+                map.insert(pointer, value);
+
+                return;
+            }
+
+            // JSON array elements are removed from the last one to avoid shifting the other ones.
+            for token in tokens.into_iter().rev() {
+                if let Some(child) = value.remove_token(&token) {
+                    drain_into(child, pointer.join(&token), map);
+                }
+            }
+        }
+
+        let mut map = HashMap::new();
+
+        drain_into(self, Pointer::root(), &mut map);
+
+        map
+    }
+
+    /// Rebuilds a JSON value from a map from the pointer of each leaf JSON value to the leaf JSON value, see
+    /// [`ValueExt::into_flat_map`].
+    ///
+    /// A JSON value whose children's reference tokens are exactly the JSON array indexes `0` to `n - 1` is rebuilt as
+    /// a JSON array, and as a JSON object otherwise. Note that a JSON object with such keys, e.g. `{ "0": 1 }`, is then
+    /// rebuilt as a JSON array.
+    ///
+    /// # Errors
+    /// This method fails with [`Error::KeyNotFound`] if the map is empty, or with [`Error::KeyAlreadyExists`] if a leaf
+    /// JSON value's pointer is the ancestor of another pointer of the map.
+    fn from_flat_map(map: HashMap<Pointer<'static>, Self>) -> Result<Self, Error> {
+        let entries = map
+            .into_iter()
+            .map(|(pointer, value)| (pointer.tokenize().collect(), value))
+            .collect();
+
+        build_from_flat_entries(entries, 0)
+    }
+
+    /// Dumps the current JSON value as a deterministic text, one line per leaf JSON value.
+    ///
+    /// Every JSON value without children (JSON scalar values, empty JSON objects and empty JSON arrays) is rendered as
+    /// `<JSON pointer>\t<JSON text>` on its own line, lines being sorted by JSON pointers as defined by the [`Ord`]
+    /// implementation of [`Pointer`]. This is handy for readable golden-file snapshots.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::ValueExt;
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "b": { "c": "foo" }, "a": [true, null] });
+    ///
+    /// assert_eq!(json.to_pointer_dump(), "/a/0\ttrue\n/a/1\tnull\n/b/c\t\"foo\"\n");
+    /// # }
+    /// ```
+    fn to_pointer_dump(&self) -> String {
+        let mut leaves = self.flatten_to_depth(usize::MAX);
+
+        leaves.sort_by(|(pointer_a, _), (pointer_b, _)| pointer_a.cmp(pointer_b));
+
+        leaves
+            .into_iter()
+            .map(|(pointer, value)| format!("{}\t{}\n", pointer, render_leaf(value)))
+            .collect()
+    }
+
+    /// Recursively compacts all the JSON arrays of the current JSON value.
+    ///
+    /// JSON arrays are always dense, hence this method is a no-op unless `remove_nulls` is enabled, in which case JSON
+    /// null elements are removed from every JSON array and the subsequent elements are shifted. This is handy when
+    /// JSON null values are used to mark elements as deleted. Note that JSON null values of JSON objects are kept.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::ValueExt;
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "items": [1, null, 2, null] });
+    ///
+    /// json.compact_arrays(true);
+    ///
+    /// assert_eq!(json, json!({ "items": [1, 2] }));
+    /// # }
+    /// ```
+    fn compact_arrays(&mut self, remove_nulls: bool) {
+        if let Some(array) = self.as_array_mut() {
+            if remove_nulls {
+                array.retain(|value| !value.is_null());
+            }
+
+            for value in array {
+                value.compact_arrays(remove_nulls);
+            }
+        } else if self.is_object() {
+            let keys = self.children().into_iter().map(|(key, _)| key).collect::<Vec<_>>();
+
+            for key in keys {
+                if let Some(value) = self.lookup_token_mut(&key) {
+                    value.compact_arrays(remove_nulls);
+                }
+            }
+        }
+    }
+
+    /// Applies a [RFC7386](https://datatracker.ietf.org/doc/html/rfc7386) JSON merge patch on the current JSON value.
+    ///
+    /// JSON objects are merged key by key, a JSON null value in the patch removing the key from the current JSON value,
+    /// while any other patch JSON value, including a JSON array, wholly replaces the current JSON value. A current JSON
+    /// value which is not a JSON object is replaced by an empty JSON object before merging a JSON object patch.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::ValueExt;
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "title": "Goodbye!", "author": { "givenName": "John", "familyName": "Doe" } });
+    ///
+    /// json.merge_patch(&json!({ "title": "Hello!", "author": { "familyName": null } }));
+    ///
+    /// assert_eq!(json, json!({ "title": "Hello!", "author": { "givenName": "John" } }));
+    /// # }
+    /// ```
+    fn merge_patch(&mut self, patch: &Self)
+    where
+        Self: Clone,
+    {
+        if !patch.is_object() {
+            *self = patch.clone();
+
+            return;
+        }
+
+        if !self.is_object() {
+            *self = Self::new_object();
+        }
+
+        for (key, patch_value) in patch.children() {
+            if patch_value.is_null() {
+                self.remove_token(&key);
+            } else if let Some(value) = self.lookup_token_mut(&key) {
+                value.merge_patch(patch_value);
+            } else {
+                let mut value = Self::new_null();
+
+                value.merge_patch(patch_value);
+                // inserting into a JSON object never fails.
+                let _ = self.insert(key, value);
+            }
+        }
+    }
+
+    /// Sorts the pointee JSON array by the JSON value each element has at the given relative JSON pointer.
+    ///
+    /// JSON numbers are sorted numerically and JSON strings lexicographically, while JSON values of distinct kinds are
+    /// sorted as JSON null, JSON boolean, JSON number, JSON string, JSON array then JSON object. Elements where the
+    /// relative JSON pointer does not resolve are sorted last. The sort is stable.
+    ///
+    /// # Errors
+    /// This method fails if the pointee JSON value does not exist or is not a JSON array.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "users": [{ "id": 2 }, { "id": 1 }] });
+    ///
+    /// json.sort_array_at(&Pointer::new("/users").unwrap(), &Pointer::new("/id").unwrap()).unwrap();
+    ///
+    /// assert_eq!(json, json!({ "users": [{ "id": 1 }, { "id": 2 }] }));
+    /// # }
+    /// ```
+    fn sort_array_at(&mut self, pointer: &Pointer<'_>, by: &Pointer<'_>) -> Result<(), Error> {
+        let array = self
+            .pointer_mut(pointer)
+            .ok_or(Error::KeyNotFound)?
+            .as_array_mut()
+            .ok_or(Error::NotAnArray)?;
+
+        array.sort_by(|value, other| match (value.pointer(by), other.pointer(by)) {
+            (Some(value), Some(other)) => compare_values(value, other),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+
+        Ok(())
+    }
+
+    /// Inserts all the given JSON values contiguously into the parent JSON array, starting at the JSON pointer's
+    /// index and shifting the following elements to the right.
+    ///
+    /// The `-` token or an index equal to the JSON array length appends the JSON values. All the JSON values are
+    /// inserted at once, which is more efficient than inserting them one by one.
+    ///
+    /// # Arguments
+    /// * `pointer`: A JSON pointer whose key is an index of the parent JSON array.
+    /// * `values`: The JSON values to insert.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "foo": [1, 4] });
+    ///
+    /// json.splice_at(&Pointer::new("/foo/1").unwrap(), [json!(2), json!(3)]).unwrap();
+    /// assert_eq!(json, json!({ "foo": [1, 2, 3, 4] }));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// This method may fail if:
+    /// - the JSON pointer is a root JSON pointer, returning [`Error::UnexpectedRootPointer`].
+    /// - the parent JSON value does not exist, returning [`Error::KeyNotFound`].
+    /// - the parent JSON value is not a JSON array, returning [`Error::NotAnArray`].
+    /// - the JSON pointer's key is neither an index nor `-`, returning [`Error::InvalidIndex`].
+    /// - the JSON pointer's key is an index beyond the JSON array length, returning [`Error::IndexOutOfBounds`].
+    fn splice_at<I>(&mut self, pointer: &Pointer<'_>, values: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        let (parent_pointer, pointer_key) = pointer
+            .parent()
+            .zip(pointer.key())
+            .ok_or(Error::UnexpectedRootPointer)?;
+        let array = self
+            .pointer_mut(&parent_pointer)
+            .ok_or(Error::KeyNotFound)?
+            .as_array_mut()
+            .ok_or(Error::NotAnArray)?;
+
+        let index = match parse_index(&pointer_key) {
+            Some(index) if index > array.len() => {
+                return Err(Error::IndexOutOfBounds {
+                    index,
+                    len: array.len(),
+                })
+            }
+            Some(index) => index,
+            None if pointer_key == "-" => array.len(),
+            None => return Err(Error::InvalidIndex(pointer_key)),
+        };
+
+        array.splice(index..index, values);
+
+        Ok(())
+    }
+
+    /// Inserts any data at the given pointee JSON value, shifting the following elements of a parent JSON array to the
+    /// right instead of replacing the existing element as [`ValueExt::insert_at`] does.
+    ///
+    /// When the parent JSON value is a JSON array, the previous occupant of an existing index is returned once shifted,
+    /// while the `-` token or an index equal to the JSON array length appends the data and returns `None`. Any other
+    /// pointee JSON value is inserted as with [`ValueExt::insert_at`].
+    ///
+    /// # Arguments
+    /// * `pointer`: A JSON pointer.
+    /// * `value`: A data to insert at the pointee JSON value.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "foo": [1, 3] });
+    ///
+    /// let old_value = json.insert_at_shifting(&Pointer::new("/foo/1").unwrap(), 2).unwrap();
+    /// assert_eq!(old_value, Some(json!(3)));
+    /// assert_eq!(json, json!({ "foo": [1, 2, 3] }));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// This method fails for the same reasons as [`ValueExt::insert_at`].
+    fn insert_at_shifting(&mut self, pointer: &Pointer<'_>, value: impl Into<Self>) -> Result<Option<Self>, Error>
+    where
+        Self: Clone,
+    {
+        let (parent_pointer, pointer_key) = match pointer.parent().zip(pointer.key()) {
+            Some(parent_and_key) => parent_and_key,
+            None => return self.insert_at(pointer, value),
+        };
+        let parent_value = self.pointer_mut(&parent_pointer).ok_or(Error::KeyNotFound)?;
+
+        let array = match parent_value.as_array_mut() {
+            Some(array) => array,
+            None => return parent_value.insert(pointer_key, value),
+        };
+
+        match parse_index(&pointer_key) {
+            Some(index) if index < array.len() => {
+                array.insert(index, value.into());
+
+                Ok(array.get(index + 1).cloned())
+            }
+            Some(index) if index > array.len() => Err(Error::IndexOutOfBounds {
+                index,
+                len: array.len(),
+            }),
+            Some(_) => {
+                array.push(value.into());
+
+                Ok(None)
+            }
+            None if pointer_key == "-" => {
+                array.push(value.into());
+
+                Ok(None)
+            }
+            None => Err(Error::InvalidIndex(pointer_key)),
+        }
+    }
+
+    /// Substitutes the `${<JSON pointer>}` placeholders found in JSON strings by the value they point to.
+    ///
+    /// Placeholders are resolved against the document as it is before any substitution, hence a substituted value is
+    /// never rendered again. Only JSON strings, JSON booleans and JSON numbers can be substituted.
+    ///
+    /// # Errors
+    /// This method may fail if a placeholder does not contain a valid JSON pointer or if it points to a JSON value
+    /// which does not exist or cannot be substituted, in which case the current JSON value is left untouched.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::ValueExt;
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "host": "localhost", "url": "http://${/host}/" });
+    ///
+    /// json.render_placeholders().unwrap();
+    /// assert_eq!(json, json!({ "host": "localhost", "url": "http://localhost/" }));
+    /// # }
+    /// ```
+    fn render_placeholders(&mut self) -> Result<(), Error>
+    where
+        Self: From<String>,
+    {
+        let rendered_values = self
+            .flatten_to_depth(usize::MAX)
+            .into_iter()
+            .filter_map(|(pointer, value)| value.as_str().filter(|s| s.contains("${")).map(|s| (pointer, s)))
+            .map(|(pointer, template)| Ok((pointer, render_template(template, self)?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        for (pointer, rendered_value) in rendered_values {
+            // the JSON pointer has just been produced by flattening the current JSON value.
+            if let Some(value) = self.pointer_mut(&pointer) {
+                *value = Self::from(rendered_value);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces all the occurrences of a substring in all the JSON string values, and returns the number of modified
+    /// JSON string values.
+    ///
+    /// JSON object keys and other JSON values are left untouched. Nothing is replaced if the substring is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::ValueExt;
+    /// use serde_json::json;
+    ///
+    /// let mut json = json!({ "host": "old.example.com", "aliases": ["www.old.example.com", "new.example.com"] });
+    ///
+    /// assert_eq!(json.replace_in_strings("old.", "new."), 2);
+    /// assert_eq!(json, json!({ "host": "new.example.com", "aliases": ["www.new.example.com", "new.example.com"] }));
+    /// # }
+    /// ```
+    fn replace_in_strings(&mut self, from: &str, to: &str) -> usize
+    where
+        Self: From<String>,
+    {
+        if from.is_empty() || from == to {
+            return 0;
+        }
+
+        if let Some(replaced) = self.as_str().filter(|s| s.contains(from)).map(|s| s.replace(from, to)) {
+            *self = Self::from(replaced);
+
+            return 1;
+        }
+
+        if let Some(array) = self.as_array_mut() {
+            return array.iter_mut().map(|value| value.replace_in_strings(from, to)).sum();
+        }
+
+        let keys = self.children().into_iter().map(|(key, _)| key).collect::<Vec<_>>();
+        let mut count = 0;
+
+        for key in keys {
+            if let Some(value) = self.lookup_token_mut(&key) {
+                count += value.replace_in_strings(from, to);
+            }
+        }
+
+        count
+    }
+
+    /// Returns the JSON pointers of the direct children of the pointee JSON value.
+    ///
+    /// Returns `None` if the pointee JSON value does not exist or is neither a JSON object nor a JSON array.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "foo": ["a", "b"] });
+    /// let children = json.children_at(&Pointer::new("/foo").unwrap());
+    ///
+    /// assert_eq!(children, Some(vec![Pointer::new("/foo/0").unwrap(), Pointer::new("/foo/1").unwrap()]));
+    /// # }
+    /// ```
+    fn children_at(&self, pointer: &Pointer<'_>) -> Option<Vec<Pointer<'static>>> {
+        self.pointer(pointer)
+            .filter(|value| value.is_object() || value.as_array().is_some())
+            .map(|value| {
+                value
+                    .children()
+                    .into_iter()
+                    .map(|(token, _)| pointer.join(&token))
+                    .collect()
+            })
+    }
+
+    /// Returns the sibling JSON values of the pointee JSON value alongside their JSON pointer, i.e. the other children
+    /// of its parent JSON value, see [`Pointer::is_sibling_of`].
+    ///
+    /// Returns `None` if the JSON pointer is the root JSON pointer or if the pointee JSON value does not exist.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "foo": ["a", "b", "c"] });
+    /// let siblings = json.siblings_at(&Pointer::new("/foo/1").unwrap());
+    ///
+    /// assert_eq!(
+    ///     siblings,
+    ///     Some(vec![(Pointer::new("/foo/0").unwrap(), &json!("a")), (Pointer::new("/foo/2").unwrap(), &json!("c"))])
+    /// );
+    /// # }
+    /// ```
+    fn siblings_at(&self, pointer: &Pointer<'_>) -> Option<Vec<(Pointer<'static>, &Self)>> {
+        let parent = pointer.parent()?;
+
+        self.pointer(pointer)?;
+
+        let siblings = self
+            .pointer(&parent)?
+            .children()
+            .into_iter()
+            .map(|(token, value)| (parent.join(&token), value))
+            .filter(|(sibling, _)| sibling.is_sibling_of(pointer))
+            .collect();
+
+        Some(siblings)
+    }
+
+    /// Computes a stable hash of the current JSON value, ignoring the pointee JSON values of the excluded JSON pointers.
+    ///
+    /// The hash is computed over the canonical form of the JSON value where JSON object members are sorted by key,
+    /// hence it does not depend on the JSON object members order. Like [`Pointer::stable_id`], the hash does not
+    /// depend on the process, the platform or the crate version.
+    ///
+    /// Note that excluded JSON array elements still count in their JSON array length so that the other JSON array
+    /// elements keep their index.
+    ///
+    /// # Arguments
+    /// * `exclude`: JSON pointers whose pointee JSON values are omitted from the hash.
+    fn content_hash_excluding(&self, exclude: &[Pointer<'_>]) -> u64 {
+        let mut hasher = Fnv64::new();
+
+        if !exclude.contains(&Pointer::root()) {
+            hash_canonical(self, &Pointer::root(), exclude, &mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    /// Computes the stable hash of every JSON object and JSON array of the current JSON value, including itself.
+    ///
+    /// Hashes are keyed by the normalized Unicode string of the JSON pointer of their JSON value. They are computed in a
+    /// single post-order pass, the hash of a JSON object or JSON array being combined from the hashes of its children,
+    /// hence they differ from [`ValueExt::content_hash_excluding`] while being as stable and independent of the order
+    /// of JSON object members. Comparing the hashes of two versions of a document tells which subtrees changed.
+    fn subtree_hashes(&self) -> BTreeMap<String, u64> {
+        fn hash_from<V: ValueExt>(value: &V, pointer: &Pointer<'_>, hashes: &mut BTreeMap<String, u64>) -> u64 {
+            let mut hasher = Fnv64::new();
+
+            if !value.is_object() && value.as_array().is_none() {
+                hash_canonical(value, pointer, &[], &mut hasher);
+
+                return hasher.finish();
+            }
+
+            let mut children = value.children();
+
+            if value.is_object() {
+                children.sort_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
+                hasher.write(b"o");
+            } else {
+                hasher.write(b"a");
+            }
+
+            hasher.write(&(children.len() as u64).to_le_bytes());
+
+            for (token, child) in children {
+                let child_hash = hash_from(child, &pointer.join(&token), hashes);
+
+                if value.is_object() {
+                    hasher.write_prefixed(token.as_bytes());
+                }
+
+                hasher.write(&child_hash.to_le_bytes());
+            }
+
+            let hash = hasher.finish();
+
+            hashes.insert(pointer.to_string(), hash);
+
+            hash
+        }
+
+        let mut hashes = BTreeMap::new();
+
+        hash_from(self, &Pointer::root(), &mut hashes);
+
+        hashes
+    }
+
+    /// Returns the direct children of the current JSON value alongside their decoded reference token.
+    ///
+    /// JSON object members are returned with their key and JSON array elements with their index. Note that JSON scalar
+    /// values do not have any children.
+    fn children(&self) -> Vec<(String, &Self)>;
+
+    /// Creates an empty JSON object.
+    fn new_object() -> Self;
+
+    /// Creates an empty JSON array.
+    fn new_array() -> Self;
+
+    /// Creates a JSON null value.
+    fn new_null() -> Self;
+
+    /// Indicates if the current JSON value is a JSON object.
+    fn is_object(&self) -> bool;
+
+    /// Indicates if the current JSON value is a JSON null value.
+    fn is_null(&self) -> bool;
+
+    /// Returns the underlying elements if the current JSON value is a JSON array.
+    fn as_array(&self) -> Option<&Vec<Self>>;
+
+    /// Returns the underlying mutable elements if the current JSON value is a JSON array.
+    fn as_array_mut(&mut self) -> Option<&mut Vec<Self>>;
+
+    /// Returns the number of entries if the current JSON value is a JSON object.
+    fn object_len(&self) -> Option<usize>;
+
+    /// Looks up a JSON value by a JSON pointer through a stack of layered JSON values.
+    ///
+    /// Layers are ordered by ascending priority, e.g. `[base, env, overrides]`, and are searched from the top-most one,
+    /// i.e. the last one. Returns the pointee JSON value of the first layer where the JSON pointer resolves.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::{json, Value};
+    ///
+    /// let layers = [json!({ "port": 80, "host": "localhost" }), json!({ "port": 8080 })];
+    ///
+    /// assert_eq!(Value::resolve_layered(&layers, &Pointer::new("/port").unwrap()), Some(&json!(8080)));
+    /// assert_eq!(Value::resolve_layered(&layers, &Pointer::new("/host").unwrap()), Some(&json!("localhost")));
+    /// # }
+    /// ```
+    fn resolve_layered<'a>(layers: &'a [Self], pointer: &Pointer<'_>) -> Option<&'a Self> {
+        layers.iter().rev().find_map(|layer| layer.pointer(pointer))
+    }
+
+    /// Checks that all the given JSON pointers resolve in the current JSON value.
+    ///
+    /// # Errors
+    /// This method fails with the list of the JSON pointers which do not resolve, in the given order.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "name": "foo" });
+    /// let required = [Pointer::new("/name").unwrap(), Pointer::new("/age").unwrap()];
+    ///
+    /// assert_eq!(json.require_all(&required), Err(vec![Pointer::new("/age").unwrap()]));
+    /// # }
+    /// ```
+    fn require_all(&self, required: &[Pointer<'_>]) -> Result<(), Vec<Pointer<'static>>> {
+        let missing_pointers = required
+            .iter()
+            .filter(|pointer| self.pointer(pointer).is_none())
+            .map(|pointer| pointer.clone().into_owned())
+            .collect::<Vec<_>>();
+
+        if missing_pointers.is_empty() {
+            Ok(())
+        } else {
+            Err(missing_pointers)
+        }
+    }
+
+    /// Checks that all the required JSON pointers resolve in the current JSON value and that none of the forbidden
+    /// ones does.
+    ///
+    /// # Errors
+    /// This method fails with a [`PolicyViolation`] listing both the missing required JSON pointers and the present
+    /// forbidden ones, in the given order.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{PolicyViolation, Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "name": "foo", "password": "bar" });
+    /// let required = [Pointer::new("/name").unwrap(), Pointer::new("/age").unwrap()];
+    /// let forbidden = [Pointer::new("/password").unwrap()];
+    ///
+    /// assert_eq!(
+    ///     json.check_policy(&required, &forbidden),
+    ///     Err(PolicyViolation {
+    ///         missing: vec![Pointer::new("/age").unwrap()],
+    ///         forbidden: vec![Pointer::new("/password").unwrap()],
+    ///     })
+    /// );
+    /// # }
+    /// ```
+    fn check_policy(&self, required: &[Pointer<'_>], forbidden: &[Pointer<'_>]) -> Result<(), PolicyViolation> {
+        let violation = PolicyViolation {
+            missing: required
+                .iter()
+                .filter(|pointer| self.pointer(pointer).is_none())
+                .map(|pointer| pointer.clone().into_owned())
+                .collect(),
+            forbidden: forbidden
+                .iter()
+                .filter(|pointer| self.pointer(pointer).is_some())
+                .map(|pointer| pointer.clone().into_owned())
+                .collect(),
+        };
+
+        if violation.is_empty() {
+            Ok(())
+        } else {
+            Err(violation)
+        }
+    }
+
+    /// Looks up a JSON value by a JSON pointer, reporting precisely why the lookup failed.
+    ///
+    /// # Errors
+    /// This method fails with [`Error::UnresolvedToken`] holding the JSON pointer up to the offending reference token,
+    /// whose source is:
+    /// - [`Error::KeyNotFound`] if a JSON object does not contain the reference token.
+    /// - [`Error::IndexOutOfBounds`] if a JSON array index is beyond the JSON array length, `-` referencing the
+    ///   element after the last one.
+    /// - [`Error::InvalidIndex`] if a reference token is not a valid index of a JSON array.
+    /// - [`Error::NotAContainer`] if a JSON scalar value is met before the last reference token.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Error, Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "arr": [1, 2] });
+    ///
+    /// assert_eq!(
+    ///     json.resolve(&Pointer::new("/arr/99/foo").unwrap()),
+    ///     Err(Error::UnresolvedToken {
+    ///         pointer: "/arr/99".to_string(),
+    ///         source: Box::new(Error::IndexOutOfBounds { index: 99, len: 2 }),
+    ///     })
+    /// );
+    /// # }
+    /// ```
+    fn resolve(&self, pointer: &Pointer<'_>) -> Result<&Self, Error> {
+        let mut value = self;
+
+        for ((range, _), token) in pointer.token_spans().zip(pointer.tokens()) {
+            value = value.lookup_token(&token).ok_or_else(|| Error::UnresolvedToken {
+                pointer: pointer.as_str()[..range.end].to_string(),
+                source: Box::new(lookup_error(value, &token)),
+            })?;
+        }
+
+        Ok(value)
+    }
+
+    /// Resolves a relative JSON pointer against the given base JSON pointer.
+    ///
+    /// The base JSON pointer is first shortened by [`RelativePointer::up`] reference tokens. The JSON pointer of the
+    /// relative JSON pointer is then applied on the reached JSON value, or the key or index of the reached JSON value
+    /// is returned if the relative JSON pointer ends with a `#`.
+    ///
+    /// Returns `None` if the base JSON pointer does not resolve, if it has fewer reference tokens than the ones to go
+    /// up, if the resulting JSON value does not exist, or if a key or index is requested for the root JSON value.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, RelativePointer, RelativeTarget, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "foo": ["bar", "baz"], "highly": { "nested": { "objects": true } } });
+    /// let base = Pointer::new("/foo/1").unwrap();
+    ///
+    /// assert_eq!(
+    ///     json.resolve_relative(&base, &RelativePointer::new("2/highly/nested/objects").unwrap()),
+    ///     Some(RelativeTarget::Value(&json!(true)))
+    /// );
+    /// assert_eq!(
+    ///     json.resolve_relative(&base, &RelativePointer::new("0#").unwrap()),
+    ///     Some(RelativeTarget::Index(1))
+    /// );
+    /// # }
+    /// ```
+    fn resolve_relative(&self, base: &Pointer<'_>, relative: &RelativePointer<'_>) -> Option<RelativeTarget<'_, Self>> {
+        self.pointer(base)?;
+
+        let ancestor = base.ancestors().nth(relative.up())?;
+
+        match relative.pointer() {
+            Some(pointer) => self.pointer(&ancestor.concat(pointer)).map(RelativeTarget::Value),
+            None => {
+                let key = ancestor.key()?;
+
+                // the parent JSON value exists since the base JSON pointer resolves.
+                match self.pointer(&ancestor.parent()?)?.as_array() {
+                    Some(_) => key.parse().ok().map(RelativeTarget::Index),
+                    None => Some(RelativeTarget::Key(key)),
+                }
+            }
+        }
+    }
+
+    /// Looks up a JSON value by a JSON pointer and deserializes it into the given type.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "zoo": { "id": 42 } });
+    /// let id: u64 = json.pointer_as(&Pointer::new("/zoo/id").unwrap()).unwrap();
+    ///
+    /// assert_eq!(id, 42);
+    /// ```
+    ///
+    /// # Errors
+    /// This method fails with [`Error::KeyNotFound`] if the pointee JSON value does not exist, or with
+    /// [`Error::Deserialization`] if it cannot be deserialized into the given type.
+    #[cfg(feature = "serde")]
+    fn pointer_as<T: ::serde::de::DeserializeOwned>(&self, pointer: &Pointer<'_>) -> Result<T, Error>
+    where
+        Self: ::serde::Serialize,
+    {
+        let value = self.pointer(pointer).ok_or(Error::KeyNotFound)?;
+
+        serde_json::to_value(value)
+            .and_then(serde_json::from_value)
+            .map_err(|e| Error::Deserialization(e.to_string()))
+    }
+
+    /// Indicates if the given JSON pointer resolves to a JSON value.
+    fn exists(&self, pointer: &Pointer<'_>) -> bool {
+        self.pointer(pointer).is_some()
+    }
+
+    /// Indicates, for each given JSON pointer, if it resolves to a JSON value.
+    ///
+    /// Unlike calling [`ValueExt::exists`] on each JSON pointer, the JSON pointers are visited in lexicographic order
+    /// so that the lookup of the reference tokens shared with the previously visited JSON pointer is not repeated,
+    /// which pays off for numerous JSON pointers sharing long prefixes.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "a": { "b": 1, "c": [2] } });
+    /// let pointers = [
+    ///     Pointer::new("/a/c/0").unwrap(),
+    ///     Pointer::new("/a/d").unwrap(),
+    ///     Pointer::new("/a/b").unwrap(),
+    /// ];
+    ///
+    /// assert_eq!(json.contains_all_mask(&pointers), vec![true, false, true]);
+    /// # }
+    /// ```
+    fn contains_all_mask(&self, pointers: &[Pointer<'_>]) -> Vec<bool> {
+        let mut order = (0..pointers.len()).collect::<Vec<_>>();
+        let mut mask = vec![false; pointers.len()];
+        // the decoded reference tokens of the last visited JSON pointer which resolve, alongside their JSON value.
+        let mut resolved_tokens: Vec<(Cow<'_, str>, &Self)> = Vec::new();
+
+        order.sort_unstable_by(|i, j| pointers[*i].as_str().cmp(pointers[*j].as_str()));
+
+        for i in order {
+            let mut tokens = pointers[i].tokens().peekable();
+            let mut shared_len = 0;
+
+            while shared_len < resolved_tokens.len() && tokens.peek() == Some(&resolved_tokens[shared_len].0) {
+                tokens.next();
+                shared_len += 1;
+            }
+
+            resolved_tokens.truncate(shared_len);
+
+            let mut value = resolved_tokens.last().map_or(self, |(_, value)| *value);
+            let mut found = true;
+
+            for token in tokens {
+                match value.lookup_token(&token) {
+                    Some(child) => {
+                        value = child;
+                        resolved_tokens.push((token, child));
+                    }
+                    None => {
+                        found = false;
+                        break;
+                    }
+                }
+            }
+
+            mask[i] = found;
+        }
+
+        mask
+    }
+
+    /// Looks up a JSON value by a JSON pointer, like [`ValueExt::pointer`] but composing with the `?` operator.
+    ///
+    /// # Errors
+    /// This method fails with [`Error::KeyNotFound`] if the pointee JSON value does not exist.
+    fn get_at(&self, pointer: &Pointer<'_>) -> Result<&Self, Error> {
+        self.pointer(pointer).ok_or(Error::KeyNotFound)
+    }
+
+    /// Looks up several JSON values by their JSON pointer at once, see [`ValueExt::pointer`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "name": "foo", "tags": ["bar"] });
+    /// let [name, tag, age] = json.get_many([
+    ///     &Pointer::new("/name").unwrap(),
+    ///     &Pointer::new("/tags/0").unwrap(),
+    ///     &Pointer::new("/age").unwrap(),
+    /// ]);
+    ///
+    /// assert_eq!(name, Some(&json!("foo")));
+    /// assert_eq!(tag, Some(&json!("bar")));
+    /// assert_eq!(age, None);
+    /// # }
+    /// ```
+    fn get_many<const N: usize>(&self, pointers: [&Pointer<'_>; N]) -> [Option<&Self>; N] {
+        pointers.map(|pointer| self.pointer(pointer))
+    }
+
+    /// Returns the length of the pointee JSON array, e.g. to paginate through its elements.
+    ///
+    /// Returns `None` if the pointee JSON value does not exist or is not a JSON array.
+    fn array_len_at(&self, pointer: &Pointer<'_>) -> Option<usize> {
+        self.pointer(pointer).and_then(ValueExt::as_array).map(Vec::len)
+    }
+
+    /// Returns the number of entries of the pointee JSON object or JSON array.
+    ///
+    /// # Errors
+    /// This method may fail if the pointee JSON value does not exist, or if it is neither a JSON object nor a JSON
+    /// array.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Error, Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let value = json!({ "foo": [1, 2, 3], "bar": {}, "zoo": 4 });
+    ///
+    /// assert_eq!(value.len_at(&Pointer::new("/foo").unwrap()).unwrap(), 3);
+    /// assert!(value.is_empty_at(&Pointer::new("/bar").unwrap()).unwrap());
+    /// assert!(matches!(value.len_at(&Pointer::new("/zoo").unwrap()), Err(Error::NotAContainer)));
+    /// # }
+    /// ```
+    fn len_at(&self, pointer: &Pointer<'_>) -> Result<usize, Error> {
+        match self.pointer(pointer) {
+            Some(pointee_value) => match pointee_value.as_array() {
+                Some(array) => Ok(array.len()),
+                None => pointee_value.object_len().ok_or(Error::NotAContainer),
+            },
+            None => Err(Error::KeyNotFound),
+        }
+    }
+
+    /// Returns whether the pointee JSON object or JSON array has no entries.
+    ///
+    /// # Errors
+    /// This method fails for the same reasons as [`len_at`](Self::len_at).
+    fn is_empty_at(&self, pointer: &Pointer<'_>) -> Result<bool, Error> {
+        self.len_at(pointer).map(|len| len == 0)
+    }
+
+    /// Returns a detached deep copy of the pointee JSON value.
+    ///
+    /// The returned JSON value owns all its data, hence it can be mutated in isolation without affecting the current
+    /// JSON value. Returns `None` if the pointee JSON value does not exist.
+    fn clone_at(&self, pointer: &Pointer<'_>) -> Option<Self>
+    where
+        Self: Clone,
+    {
+        self.pointer(pointer).cloned()
+    }
+
+    /// Looks up a string slice by a JSON pointer.
+    ///
+    /// Returns `None` if the pointee JSON value does not exist or is not a JSON string.
+    fn get_str(&self, pointer: &Pointer<'_>) -> Option<&str> {
+        self.pointer(pointer).and_then(ValueExt::as_str)
+    }
+
+    /// Looks up an `i64` by a JSON pointer.
+    ///
+    /// Returns `None` if the pointee JSON value does not exist or is not a JSON number representable as `i64`.
+    fn get_i64(&self, pointer: &Pointer<'_>) -> Option<i64> {
+        self.pointer(pointer).and_then(ValueExt::as_i64)
+    }
+
+    /// Looks up an `f64` by a JSON pointer.
+    ///
+    /// Returns `None` if the pointee JSON value does not exist or is not a JSON number.
+    fn get_f64(&self, pointer: &Pointer<'_>) -> Option<f64> {
+        self.pointer(pointer).and_then(ValueExt::as_f64)
+    }
+
+    /// Looks up a `bool` by a JSON pointer.
+    ///
+    /// Returns `None` if the pointee JSON value does not exist or is not a JSON boolean.
+    fn get_bool(&self, pointer: &Pointer<'_>) -> Option<bool> {
+        self.pointer(pointer).and_then(ValueExt::as_bool)
+    }
+
+    /// Looks up a `bool` by a JSON pointer, coercing JSON strings.
+    ///
+    /// Accepted forms are JSON booleans and the `"true"`, `"false"`, `"1"` and `"0"` JSON strings. Returns `None` if the
+    /// pointee JSON value does not exist or is in any other form.
+    fn get_coerced_bool(&self, pointer: &Pointer<'_>) -> Option<bool> {
+        let value = self.pointer(pointer)?;
+
+        value.as_bool().or_else(|| match value.as_str()? {
+            "true" | "1" => Some(true),
+            "false" | "0" => Some(false),
+            _ => None,
+        })
+    }
+
+    /// Looks up an `i64` by a JSON pointer, coercing JSON strings.
+    ///
+    /// Accepted forms are JSON numbers representable as `i64` and JSON strings holding a decimal integer
+    /// representable as `i64`, e.g. `"-42"`, without any surrounding whitespaces. Returns `None` if the pointee JSON
+    /// value does not exist or is in any other form.
+    fn get_coerced_i64(&self, pointer: &Pointer<'_>) -> Option<i64> {
+        let value = self.pointer(pointer)?;
+
+        value.as_i64().or_else(|| value.as_str()?.parse().ok())
+    }
+
+    /// Looks up an `f64` by a JSON pointer, coercing JSON strings.
+    ///
+    /// Accepted forms are JSON numbers and JSON strings holding a finite decimal number, e.g. `"1.5"` or `"-2e3"`,
+    /// without any surrounding whitespaces. Returns `None` if the pointee JSON value does not exist or is in any other
+    /// form, including the `"inf"` and `"NaN"` JSON strings.
+    fn get_coerced_f64(&self, pointer: &Pointer<'_>) -> Option<f64> {
+        let value = self.pointer(pointer)?;
+
+        value
+            .as_f64()
+            .or_else(|| value.as_str()?.parse::<f64>().ok().filter(|f| f.is_finite()))
+    }
+
+    /// Looks up a string slice by a JSON pointer, reporting why the lookup failed.
+    ///
+    /// # Errors
+    /// This method may fail if:
+    /// - the pointee JSON value does not exist, returning [`Error::KeyNotFound`].
+    /// - the pointee JSON value is not a JSON string, returning [`Error::TypeMismatch`].
+    fn get_str_strict(&self, pointer: &Pointer<'_>) -> Result<&str, Error> {
+        let value = self.pointer(pointer).ok_or(Error::KeyNotFound)?;
+
+        value.as_str().ok_or_else(|| Error::TypeMismatch {
+            expected: "string",
+            found: value.json_type().as_str(),
+        })
+    }
+
+    /// Returns the JSON type of the current JSON value.
+    fn json_type(&self) -> JsonType {
+        if self.is_null() {
+            JsonType::Null
+        } else if self.as_bool().is_some() {
+            JsonType::Boolean
+        } else if self.as_f64().is_some() {
+            JsonType::Number
+        } else if self.as_str().is_some() {
+            JsonType::String
+        } else if self.as_array().is_some() {
+            JsonType::Array
+        } else {
+            JsonType::Object
+        }
+    }
+
+    /// Returns the JSON type of the pointee JSON value, if any.
+    fn type_at(&self, pointer: &Pointer<'_>) -> Option<JsonType> {
+        self.pointer(pointer).map(ValueExt::json_type)
+    }
+
+    /// Produces a depth-first iterator over every JSON value of the current JSON value alongside its JSON pointer.
+    ///
+    /// The current JSON value comes first with the root JSON pointer, then each JSON value comes before its children.
+    /// Children are visited in the iteration order of their parent, see [`ValueExt::children`].
+    ///
+    /// Note that the inherent `entries` method of `json::JsonValue` takes precedence over this one, which is then
+    /// called as `ValueExt::entries(&value)`.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "a/b": [1] });
+    /// let pointers = json.entries().map(|(pointer, _)| pointer).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     pointers,
+    ///     vec![Pointer::root(), Pointer::new("/a~1b").unwrap(), Pointer::new("/a~1b/0").unwrap()]
+    /// );
+    /// # }
+    /// ```
+    fn entries(&self) -> Entries<'_, Self> {
+        Entries::new(self)
+    }
+
+    /// Collects the distinct decoded keys of all the JSON objects of the current JSON value, at any nesting level.
+    ///
+    /// JSON array indexes are not keys, hence they are excluded.
+    fn all_keys(&self) -> BTreeSet<String> {
+        let mut keys = BTreeSet::new();
+        let mut stack = vec![self];
+
+        while let Some(value) = stack.pop() {
+            let is_object = value.is_object();
+
+            for (token, child) in value.children() {
+                if is_object {
+                    keys.insert(token);
+                }
+
+                stack.push(child);
+            }
+        }
+
+        keys
+    }
+
+    /// Looks up all the JSON values of the given JSON type, along with their JSON pointer.
+    ///
+    /// JSON values are returned in a depth-first order, a JSON value coming before its children.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use json_toolkit::{JsonType, Pointer, ValueExt};
+    /// use serde_json::json;
+    ///
+    /// let json = json!({ "a": "foo", "b": [1, "bar"] });
+    /// let pointers = json
+    ///     .values_of_type(JsonType::String)
+    ///     .into_iter()
+    ///     .map(|(pointer, _)| pointer)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(pointers, vec![Pointer::new("/a").unwrap(), Pointer::new("/b/1").unwrap()]);
+    /// # }
+    /// ```
+    fn values_of_type(&self, ty: JsonType) -> Vec<(Pointer<'static>, &Self)> {
+        self.entries().filter(|(_, value)| value.json_type() == ty).collect()
+    }
+
+    /// Returns the string slice if the current JSON value is a JSON string.
+    fn as_str(&self) -> Option<&str>;
+
+    /// Returns the `i64` if the current JSON value is a JSON number representable as `i64`.
+    fn as_i64(&self) -> Option<i64>;
+
+    /// Returns the `f64` if the current JSON value is a JSON number.
+    fn as_f64(&self) -> Option<f64>;
+
+    /// Returns the `bool` if the current JSON value is a JSON boolean.
+    fn as_bool(&self) -> Option<bool>;
+
+    /// Looks up a direct child of the current JSON value by its decoded reference token.
+    ///
+    /// The reference token is a key for JSON objects and an index for JSON arrays.
+    fn lookup_token(&self, token: &str) -> Option<&Self>;
+
+    /// Looks up a direct child of the current JSON value by its decoded reference token and returns a mutable
+    /// reference to that child.
+    fn lookup_token_mut(&mut self, token: &str) -> Option<&mut Self>;
+
+    /// Removes the child JSON value referenced by a single decoded reference token, and returns it.
+    ///
+    /// Subsequent elements of a JSON array are shifted. Returns `None` if the child JSON value does not exist.
+    fn remove_token(&mut self, token: &str) -> Option<Self>;
+
+    /// Looks up a value by a JSON pointer, recording the JSON pointer into the audit log when the lookup succeeds.
+    ///
+    /// # Arguments
+    /// * `pointer`: A JSON pointer.
+    /// * `audit`: An audit log of the successfully accessed JSON pointers.
+    fn pointer_audited<'s>(&'s self, pointer: &Pointer<'_>, audit: &mut Vec<Pointer<'static>>) -> Option<&'s Self> {
+        let value = self.pointer(pointer);
+
+        if value.is_some() {
+            audit.push(pointer.clone().into_owned());
+        }
+
+        value
+    }
+
+    /// Looks up a value by a JSON pointer made of at most the given number of reference tokens.
+    ///
+    /// This method guards against extremely long JSON pointers coming from untrusted sources, since the JSON pointer
+    /// depth is checked before traversing the current JSON value.
+    ///
+    /// # Errors
+    /// This method fails if the JSON pointer has more reference tokens than `max_steps`.
+    fn pointer_bounded(&self, pointer: &Pointer<'_>, max_steps: usize) -> Result<Option<&Self>, Error> {
+        if pointer.depth() > max_steps {
+            Err(Error::StepLimitExceeded)
+        } else {
+            Ok(self.pointer(pointer))
+        }
+    }
+
+    /// Looks up a value by a JSON pointer, interpreting `-n` reference tokens as indexes from the end of JSON arrays.
+    ///
+    /// A `-n` reference token with `n >= 1` points to the `n`-th last element of a JSON array, so `/array/-1` points
+    /// to the last element of `/array`. Any other reference token is looked up like [`ValueExt::pointer`] does.
+    ///
+    /// Note that this is an opt-in extension deviating from [RFC6901](https://datatracker.ietf.org/doc/html/rfc6901)
+    /// where `-` refers to the nonexistent element after the last JSON array element.
+    fn pointer_ext(&self, pointer: &Pointer<'_>) -> Option<&Self> {
+        pointer.tokenize().try_fold(self, |value, token| {
+            let reverse_index = token.strip_prefix('-').and_then(parse_index).filter(|n| *n >= 1);
+
+            match (value.as_array(), reverse_index) {
+                (Some(array), Some(n)) => array.len().checked_sub(n).and_then(|i| array.get(i)),
+                _ => value.lookup_token(&token),
+            }
+        })
+    }
+
+    /// Looks up a value by a compiled JSON pointer.
+    fn pointer_compiled(&self, pointer: &CompiledPointer) -> Option<&Self> {
+        pointer
+            .tokens()
+            .iter()
+            .try_fold(self, |value, token| value.lookup_token(token))
+    }
+
+    /// Looks up a value by a compiled JSON pointer and returns a mutable reference to that value.
+    fn pointer_compiled_mut(&mut self, pointer: &CompiledPointer) -> Option<&mut Self> {
+        pointer
+            .tokens()
+            .iter()
+            .try_fold(self, |value, token| value.lookup_token_mut(token))
+    }
 
     /// Looks up a value by a JSON pointer.
     fn pointer(&self, pointer: &Pointer<'_>) -> Option<&Self>;
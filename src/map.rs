@@ -0,0 +1,154 @@
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+use crate::Pointer;
+
+/// An ordered map keyed by JSON pointers, supporting efficient queries of all the entries located under a JSON pointer.
+///
+/// Entries are stored in a [`BTreeMap`] keyed by the normalized Unicode string of their JSON pointer, so that all the
+/// descendants of a JSON pointer are contiguous and can be retrieved with a single range scan, see
+/// [`PointerMap::range_under`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PointerMap<V> {
+    entries: BTreeMap<String, V>,
+}
+
+impl<V> PointerMap<V> {
+    /// Creates an empty `PointerMap`.
+    pub fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Indicates if the map contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Inserts a value keyed by the given JSON pointer, returning the old value if the JSON pointer was already present.
+    pub fn insert(&mut self, pointer: &Pointer<'_>, value: V) -> Option<V> {
+        self.entries.insert(pointer.normalized().as_str().to_string(), value)
+    }
+
+    /// Returns the value keyed by the given JSON pointer.
+    pub fn get(&self, pointer: &Pointer<'_>) -> Option<&V> {
+        self.entries.get(pointer.normalized().as_str())
+    }
+
+    /// Removes the value keyed by the given JSON pointer and returns it.
+    pub fn remove(&mut self, pointer: &Pointer<'_>) -> Option<V> {
+        self.entries.remove(pointer.normalized().as_str())
+    }
+
+    /// Produces an iterator over all the entries, sorted by the Unicode string of their JSON pointer.
+    pub fn iter(&self) -> impl Iterator<Item = (Pointer<'_>, &V)> {
+        self.entries.iter().map(to_entry)
+    }
+
+    /// Produces an iterator over all the entries whose JSON pointer is a descendant of the given JSON pointer.
+    ///
+    /// Like [`Pointer::is_ancestor_of`], a JSON pointer is considered as a descendant of itself.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::{Pointer, PointerMap};
+    ///
+    /// let mut map = PointerMap::new();
+    ///
+    /// map.insert(&Pointer::new("/a").unwrap(), 1);
+    /// map.insert(&Pointer::new("/a/b").unwrap(), 2);
+    /// map.insert(&Pointer::new("/ab").unwrap(), 3);
+    ///
+    /// let values = map.range_under(&Pointer::new("/a").unwrap()).map(|(_, v)| *v).collect::<Vec<_>>();
+    /// assert_eq!(values, vec![1, 2]);
+    /// ```
+    pub fn range_under<'m>(&'m self, pointer: &Pointer<'_>) -> impl Iterator<Item = (Pointer<'m>, &'m V)> {
+        let pointer = pointer.normalized();
+        // apart from the pointer itself, descendants all start with `<pointer>/` and so sort before `<pointer>0`
+        // since `0` is the character following `/`.
+        let lower_bound = format!("{}/", pointer);
+        let upper_bound = format!("{}0", pointer);
+
+        self.entries
+            .get_key_value(pointer.as_str())
+            .into_iter()
+            .chain(self.entries.range::<str, _>((
+                Bound::Included(lower_bound.as_str()),
+                Bound::Excluded(upper_bound.as_str()),
+            )))
+            .map(to_entry)
+    }
+}
+
+impl<V> Default for PointerMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn to_entry<'m, V>((s, value): (&'m String, &'m V)) -> (Pointer<'m>, &'m V) {
+    // the `unwrap` call is safe here since keys are built from valid JSON pointers.
+    (Pointer::new(s.as_str()).unwrap(), value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn it_range_queries_entries_under_json_pointer() -> Result<(), Error> {
+        let mut map = PointerMap::new();
+
+        for (i, s) in ["/a/b", "/a", "/a!", "/ab", "/a/b/c", "/a~1b", "/b", "/a/", ""]
+            .iter()
+            .enumerate()
+        {
+            map.insert(&Pointer::new(*s)?, i);
+        }
+
+        let tests = [
+            ("", vec!["", "/a", "/a!", "/a/", "/a/b", "/a/b/c", "/ab", "/a~1b", "/b"]),
+            ("/a", vec!["/a", "/a/", "/a/b", "/a/b/c"]),
+            ("/a/b", vec!["/a/b", "/a/b/c"]),
+            ("/a~1b", vec!["/a~1b"]),
+            ("/c", vec![]),
+        ];
+
+        for (s, expected_pointers) in tests {
+            let pointers = map
+                .range_under(&Pointer::new(s)?)
+                .map(|(pointer, _)| pointer)
+                .collect::<Vec<_>>();
+            let expected_pointers = expected_pointers
+                .into_iter()
+                .map(Pointer::new)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            assert_eq!(pointers, expected_pointers, "Entries under '{}' JSON pointer", s);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_manipulates_entries_by_normalized_json_pointer() -> Result<(), Error> {
+        let mut map = PointerMap::new();
+
+        assert!(map.is_empty());
+        assert_eq!(map.insert(&Pointer::new("/~2")?, 1), None);
+        assert_eq!(map.insert(&Pointer::new("/~02")?, 2), Some(1));
+        assert_eq!(map.get(&Pointer::new("/~2")?), Some(&2));
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.remove(&Pointer::new("/~02")?), Some(2));
+        assert_eq!(map.get(&Pointer::new("/~2")?), None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+/// Options tuning the behavior of [`ValueExt::insert_at_with`](crate::ValueExt::insert_at_with) method.
+///
+/// All the options are disabled by default, making the insertion behave like
+/// [`ValueExt::insert_at`](crate::ValueExt::insert_at) method.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InsertOptions {
+    /// Creates missing intermediate JSON values as empty JSON objects.
+    pub create_intermediate: bool,
+    /// Appends the data at the end of the pointee JSON array when the JSON pointer's key is an out-of-bounds index.
+    pub append_on_overflow: bool,
+}
+
+impl InsertOptions {
+    /// Creates `InsertOptions` with all options disabled.
+    pub const fn new() -> Self {
+        Self {
+            create_intermediate: false,
+            append_on_overflow: false,
+        }
+    }
+
+    /// Enables or disables the creation of missing intermediate JSON values.
+    pub const fn create_intermediate(mut self, enabled: bool) -> Self {
+        self.create_intermediate = enabled;
+        self
+    }
+
+    /// Enables or disables the append of the data on out-of-bounds JSON array index.
+    pub const fn append_on_overflow(mut self, enabled: bool) -> Self {
+        self.append_on_overflow = enabled;
+        self
+    }
+}
@@ -0,0 +1,160 @@
+use crate::pointer::parse_index;
+use crate::{Error, Pointer, ValueExt};
+
+/// A single [RFC6902](https://datatracker.ietf.org/doc/html/rfc6902) JSON patch operation.
+///
+/// When the `serde` feature is enabled, an operation is {de}serialized as a JSON object tagged by its `op` member, e.g.
+/// `{ "op": "add", "path": "/a", "value": 1 }`, its JSON pointers being rejected if they do not start with a leading
+/// `/`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "op", rename_all = "lowercase"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchOperation<V> {
+    /// Adds a JSON value at the target location.
+    Add { path: Pointer<'static>, value: V },
+    /// Removes the JSON value at the target location.
+    Remove { path: Pointer<'static> },
+    /// Replaces the JSON value at the target location.
+    Replace { path: Pointer<'static>, value: V },
+    /// Removes the JSON value at the `from` location and adds it at the target location.
+    Move {
+        from: Pointer<'static>,
+        path: Pointer<'static>,
+    },
+    /// Copies the JSON value at the `from` location to the target location.
+    Copy {
+        from: Pointer<'static>,
+        path: Pointer<'static>,
+    },
+    /// Tests that the JSON value at the target location is equal to the given JSON value.
+    Test { path: Pointer<'static>, value: V },
+}
+
+impl<V> PatchOperation<V> {
+    /// Returns the target location of the operation.
+    pub fn path(&self) -> &Pointer<'static> {
+        match self {
+            Self::Add { path, .. }
+            | Self::Remove { path }
+            | Self::Replace { path, .. }
+            | Self::Move { path, .. }
+            | Self::Copy { path, .. }
+            | Self::Test { path, .. } => path,
+        }
+    }
+
+    /// Returns the `from` location of the operation, if any.
+    pub fn from(&self) -> Option<&Pointer<'static>> {
+        match self {
+            Self::Move { from, .. } | Self::Copy { from, .. } => Some(from),
+            _ => None,
+        }
+    }
+}
+
+impl<V: ValueExt + Clone + PartialEq> PatchOperation<V> {
+    /// Applies the operation on the given JSON value.
+    pub(crate) fn apply(&self, value: &mut V) -> Result<(), Error> {
+        match self {
+            Self::Add { path, value: new_value } => add_at(value, path, new_value.clone()),
+            Self::Remove { path } => remove_at(value, path).map(|_| ()),
+            Self::Replace { path, value: new_value } => value.replace_at(path, new_value.clone()).map(|_| ()),
+            Self::Move { from, path } => move_at(value, from, path),
+            Self::Copy { from, path } => {
+                let copied_value = value.clone_at(from).ok_or(Error::KeyNotFound)?;
+
+                add_at(value, path, copied_value)
+            }
+            Self::Test {
+                path,
+                value: expected_value,
+            } => match value.pointer(path) {
+                Some(value) if value == expected_value => Ok(()),
+                _ => Err(Error::PatchTestFailed),
+            },
+        }
+    }
+}
+
+/// Adds the JSON value at the pointee location, as described by the `add` operation.
+///
+/// Unlike [`ValueExt::insert_at`], adding a JSON value into a JSON array shifts the elements located at and after the
+/// JSON array index.
+fn add_at<V: ValueExt>(value: &mut V, pointer: &Pointer<'_>, new_value: V) -> Result<(), Error> {
+    if pointer.is_root() {
+        *value = new_value;
+
+        return Ok(());
+    }
+
+    let (parent, key) = value.split_target_mut(pointer)?;
+
+    match parent.as_array_mut() {
+        Some(array) if key == "-" => array.push(new_value),
+        Some(array) => match parse_index(&key) {
+            Some(index) if index <= array.len() => array.insert(index, new_value),
+            Some(index) => {
+                return Err(Error::IndexOutOfBounds {
+                    index,
+                    len: array.len(),
+                })
+            }
+            None => return Err(Error::InvalidIndex(key)),
+        },
+        None => {
+            parent.insert(key, new_value)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Moves the JSON value from a location to another one, as described by the `move` operation.
+///
+/// The JSON value is moved back to its location if it cannot be added at the target location.
+pub(crate) fn move_at<V: ValueExt + Clone>(value: &mut V, from: &Pointer<'_>, path: &Pointer<'_>) -> Result<(), Error> {
+    if from != path && from.is_ancestor_of(path) {
+        return Err(Error::MoveIntoDescendant);
+    }
+
+    let moved_value = remove_at(value, from)?;
+
+    match add_at(value, path, moved_value.clone()) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            add_at(value, from, moved_value)?;
+
+            Err(e)
+        }
+    }
+}
+
+/// Removes the pointee JSON value from its parent JSON value, and returns it.
+fn remove_at<V: ValueExt>(value: &mut V, pointer: &Pointer<'_>) -> Result<V, Error> {
+    let (parent, key) = value.split_target_mut(pointer)?;
+
+    parent.remove_token(&key).ok_or(Error::KeyNotFound)
+}
+
+/// A sequence of [RFC6902](https://datatracker.ietf.org/doc/html/rfc6902) JSON patch operations.
+///
+/// When the `serde` feature is enabled, a patch is {de}serialized as a JSON array of operations, see
+/// [`PatchOperation`], and is applied with [`ValueExt::apply_patch`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Patch<V> {
+    operations: Vec<PatchOperation<V>>,
+}
+
+impl<V> Patch<V> {
+    /// Creates a `Patch` from a sequence of operations.
+    pub fn new(operations: Vec<PatchOperation<V>>) -> Self {
+        Self { operations }
+    }
+
+    /// Returns the operations of the patch.
+    pub fn operations(&self) -> &[PatchOperation<V>] {
+        &self.operations
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::HashSet;
+
+use derive_more::Display;
+
+use crate::{Error, Pointer, ValueExt};
+
+fn decode_pattern_token(s: &str) -> String {
+    s.replace("~1", "/").replace("~2", "*").replace("~0", "~")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Segment {
+    Token(String),
+    Wildcard,
+    RecursiveWildcard,
+}
+
+/// JSON pointer pattern matching several JSON pointers.
+///
+/// A pattern is written like a JSON pointer, where some reference tokens have a special meaning:
+/// - `*` matches any single reference token, i.e. any JSON object key or JSON array index.
+/// - `**` matches any sequence of reference tokens, including the empty one.
+///
+/// Besides the usual `~0` and `~1` escapes, `~2` encodes a literal `*` so that `/~2` and `/~2~2` match the literal
+/// `*` and `**` JSON object keys.
+///
+/// # Examples
+/// ```
+/// # use json_toolkit::{Pointer, PointerPattern};
+///
+/// let pattern = PointerPattern::new("/items/*/name").unwrap();
+///
+/// assert!(pattern.matches(&Pointer::new("/items/0/name").unwrap()));
+/// assert!(!pattern.matches(&Pointer::new("/items/0/id").unwrap()));
+///
+/// let pattern = PointerPattern::new("/**/password").unwrap();
+///
+/// assert!(pattern.matches(&Pointer::new("/password").unwrap()));
+/// assert!(pattern.matches(&Pointer::new("/users/0/password").unwrap()));
+/// ```
+#[derive(Debug, Display, Clone, PartialEq, Eq, Hash)]
+#[display(fmt = "{}", pattern)]
+pub struct PointerPattern {
+    pattern: String,
+    segments: Vec<Segment>,
+}
+
+impl PointerPattern {
+    /// Creates a `PointerPattern` from a Unicode string.
+    ///
+    /// # Errors
+    /// This function fails if the pattern is not empty and does not start with a leading `/`, like [`Pointer::new`].
+    pub fn new(s: impl Into<String>) -> Result<Self, Error> {
+        let pattern = s.into();
+
+        if !pattern.is_empty() && !pattern.starts_with('/') {
+            return Err(Error::MissingLeadingBackslash);
+        }
+
+        let mut segments = Vec::new();
+
+        for token in pattern.split('/').skip(1) {
+            let segment = match token {
+                "*" => Segment::Wildcard,
+                "**" => Segment::RecursiveWildcard,
+                token => Segment::Token(decode_pattern_token(token)),
+            };
+
+            // consecutive recursive wildcards are equivalent to a single one.
+            if !(segment == Segment::RecursiveWildcard && segments.last() == Some(&Segment::RecursiveWildcard)) {
+                segments.push(segment);
+            }
+        }
+
+        Ok(Self { pattern, segments })
+    }
+
+    /// Returns the Unicode string representation of the pattern.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Indicates if the given JSON pointer matches the pattern.
+    pub fn matches(&self, pointer: &Pointer<'_>) -> bool {
+        fn matches_from(segments: &[Segment], tokens: &[String]) -> bool {
+            match (segments.first(), tokens.first()) {
+                (None, None) => true,
+                (Some(Segment::RecursiveWildcard), _) => {
+                    matches_from(&segments[1..], tokens) || (!tokens.is_empty() && matches_from(segments, &tokens[1..]))
+                }
+                (Some(Segment::Wildcard), Some(_)) => matches_from(&segments[1..], &tokens[1..]),
+                (Some(Segment::Token(expected_token)), Some(token)) if expected_token == token => {
+                    matches_from(&segments[1..], &tokens[1..])
+                }
+                _ => false,
+            }
+        }
+
+        matches_from(&self.segments, &pointer.tokenize().collect::<Vec<_>>())
+    }
+
+    /// Returns the value associated with the most specific pattern matching the given JSON pointer.
+    ///
+    /// Patterns are compared segment by segment, from left to right, a reference token being more specific than `*`
+    /// which is more specific than `**`. The first pattern wins among equally specific ones.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::{Pointer, PointerPattern};
+    ///
+    /// let handlers = [
+    ///     (PointerPattern::new("/**").unwrap(), "fallback"),
+    ///     (PointerPattern::new("/users/*").unwrap(), "user"),
+    ///     (PointerPattern::new("/users/admin").unwrap(), "admin"),
+    /// ];
+    ///
+    /// let pointer = Pointer::new("/users/admin").unwrap();
+    /// assert_eq!(PointerPattern::best_match(&handlers, &pointer), Some(&"admin"));
+    ///
+    /// let pointer = Pointer::new("/users/foo").unwrap();
+    /// assert_eq!(PointerPattern::best_match(&handlers, &pointer), Some(&"user"));
+    /// ```
+    pub fn best_match<'a, T>(patterns: &'a [(PointerPattern, T)], p: &Pointer<'_>) -> Option<&'a T> {
+        let mut best_match: Option<(Vec<u8>, &'a T)> = None;
+
+        for (pattern, value) in patterns.iter().filter(|(pattern, _)| pattern.matches(p)) {
+            let specificity = pattern.specificity();
+
+            if best_match
+                .as_ref()
+                .map_or(true, |(best_specificity, _)| specificity > *best_specificity)
+            {
+                best_match = Some((specificity, value));
+            }
+        }
+
+        best_match.map(|(_, value)| value)
+    }
+
+    /// Returns the specificity of each segment of the pattern, see [`PointerPattern::best_match`].
+    ///
+    /// The end of the pattern ranks above `**` so that `/a` is more specific than `/a/**`.
+    fn specificity(&self) -> Vec<u8> {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Token(_) => 3,
+                Segment::Wildcard => 2,
+                Segment::RecursiveWildcard => 0,
+            })
+            .chain(std::iter::once(1))
+            .collect()
+    }
+
+    /// Looks up all the JSON values whose JSON pointer matches the pattern, in a depth-first order.
+    pub(crate) fn select<'v, V: ValueExt>(&self, value: &'v V) -> Vec<(Pointer<'static>, &'v V)> {
+        fn select_from<'v, V: ValueExt>(
+            segments: &[Segment],
+            value: &'v V,
+            pointer: Pointer<'static>,
+            selected_values: &mut Vec<(Pointer<'static>, &'v V)>,
+        ) {
+            match segments.first() {
+                None => selected_values.push((pointer, value)),
+                Some(Segment::Token(token)) => {
+                    if let Some(child) = value.lookup_token(token) {
+                        select_from(&segments[1..], child, pointer.join(token), selected_values);
+                    }
+                }
+                Some(Segment::Wildcard) => {
+                    for (token, child) in value.children() {
+                        select_from(&segments[1..], child, pointer.join(&token), selected_values);
+                    }
+                }
+                Some(Segment::RecursiveWildcard) => {
+                    select_from(&segments[1..], value, pointer.clone(), selected_values);
+
+                    for (token, child) in value.children() {
+                        select_from(segments, child, pointer.join(&token), selected_values);
+                    }
+                }
+            }
+        }
+
+        let mut selected_values = Vec::new();
+
+        select_from(&self.segments, value, Pointer::root(), &mut selected_values);
+
+        // a JSON pointer may be matched several times when the pattern has several recursive wildcards.
+        let mut selected_pointers = HashSet::new();
+
+        selected_values.retain(|(pointer, _)| selected_pointers.insert(pointer.clone()));
+
+        selected_values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_rejects_pattern_without_leading_backslash() {
+        assert_eq!(PointerPattern::new("items/*"), Err(Error::MissingLeadingBackslash));
+    }
+
+    #[test]
+    fn it_matches_json_pointers() -> Result<(), Error> {
+        let tests = [
+            ("", ""),
+            ("/a/b", "/a/b"),
+            ("/a/*", "/a/b"),
+            ("/*/*", "/a/0"),
+            ("/**", ""),
+            ("/**", "/a/b/c"),
+            ("/**/c", "/c"),
+            ("/**/c", "/a/b/c"),
+            ("/a/**/c", "/a/c"),
+            ("/a/**/**/c", "/a/b/c"),
+            ("/a~1b/~2", "/a~1b/*"),
+            ("/~2~2", "/**"),
+        ];
+
+        for (s, pointer) in tests {
+            let pattern = PointerPattern::new(s)?;
+
+            assert!(
+                pattern.matches(&Pointer::new(pointer)?),
+                "'{}' matches '{}'",
+                s,
+                pointer
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_does_not_match_json_pointers() -> Result<(), Error> {
+        let tests = [
+            ("", "/a"),
+            ("/a/b", "/a/c"),
+            ("/a/*", "/a"),
+            ("/a/*", "/a/b/c"),
+            ("/**/c", "/a/b/c/d"),
+            ("/a/**/c", "/b/c"),
+            ("/~2", "/a"),
+        ];
+
+        for (s, pointer) in tests {
+            let pattern = PointerPattern::new(s)?;
+
+            assert!(
+                !pattern.matches(&Pointer::new(pointer)?),
+                "'{}' does not match '{}'",
+                s,
+                pointer
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_finds_best_matching_pattern() -> Result<(), Error> {
+        let patterns = [
+            (PointerPattern::new("/**")?, "**"),
+            (PointerPattern::new("/a/*")?, "/a/*"),
+            (PointerPattern::new("/a/b")?, "/a/b"),
+            (PointerPattern::new("/*/b")?, "/*/b"),
+            (PointerPattern::new("/a/**")?, "/a/**"),
+            (PointerPattern::new("/a/b/**")?, "/a/b/**"),
+        ];
+
+        let tests = [
+            ("/a/b", Some("/a/b")),
+            ("/a/c", Some("/a/*")),
+            ("/c/b", Some("/*/b")),
+            ("/a/c/d", Some("/a/**")),
+            ("/a/b/c", Some("/a/b/**")),
+            ("/c", Some("**")),
+            ("", Some("**")),
+        ];
+
+        for (s, expected_value) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                PointerPattern::best_match(&patterns, &pointer),
+                expected_value.as_ref(),
+                "Best pattern matching '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        assert_eq!(PointerPattern::best_match(&patterns[1..], &Pointer::new("/c")?), None);
+
+        Ok(())
+    }
+}
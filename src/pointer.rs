@@ -1,21 +1,377 @@
-use std::borrow::Cow;
-use std::cmp::Ordering;
-use std::str::FromStr;
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Write;
+use core::ops::{Add, Deref, Range};
+use core::str::FromStr;
 
 use derive_more::Display;
 
-use crate::Error;
+use crate::hash::{Fnv128, Fnv64};
+use crate::{Error, PointerBuilder, RelativePointer};
 
 fn decode_token(s: &str) -> String {
     s.replace("~1", "/").replace("~0", "~")
 }
 
+/// Decodes the reference token, borrowing it if it does not contain any `~`.
+fn decode_token_cow(s: &str) -> Cow<'_, str> {
+    if s.contains('~') {
+        Cow::Owned(decode_token(s))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// Parses a JSON array index as described in [RFC6901](https://datatracker.ietf.org/doc/html/rfc6901#section-4), made
+/// of ASCII digits without leading zeros, hence rejecting `+1` or `01`.
+pub(crate) fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty() || !token.bytes().all(|b| b.is_ascii_digit()) || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+
+    token.parse().ok()
+}
+
+/// Encodes a raw string into a reference token as described in
+/// [RFC6901](https://datatracker.ietf.org/doc/html/rfc6901#section-3).
+///
+/// `~` is replaced by `~0` before `/` is replaced by `~1`, so that decoding the reference token gives back the raw
+/// string.
+///
+/// # Examples
+/// ```
+/// # use json_toolkit::encode_token;
+///
+/// assert_eq!(encode_token("a/b~c"), "a~1b~0c");
+/// ```
+pub fn encode_token(s: &str) -> String {
+    s.replace('~', "~0").replace('/', "~1")
+}
+
+/// Appends the decimal representation of the JSON array index to the string.
+#[cfg(feature = "fast-fmt")]
+fn push_decimal(s: &mut String, index: usize) {
+    s.push_str(itoa::Buffer::new().format(index));
+}
+
+/// Appends the decimal representation of the JSON array index to the string.
+#[cfg(not(feature = "fast-fmt"))]
+fn push_decimal(s: &mut String, index: usize) {
+    // writing into a `String` never fails.
+    let _ = write!(s, "{}", index);
+}
+
+/// Checks that every `~` of the Unicode string starts either a `~0` or a `~1` escape sequence.
+///
+/// ASCII bytes are checked in a tight loop, falling back to the char handling once a non-ASCII byte is found.
+fn check_escape_sequences(s: &str) -> Result<(), Error> {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'~' => match bytes.get(i + 1) {
+                Some(b'0' | b'1') => i += 2,
+                _ => return Err(Error::InvalidEscapeSequence),
+            },
+            byte if byte.is_ascii() => i += 1,
+            // every previous byte is ASCII so `i` is a char boundary.
+            _ => return check_escape_sequences_slow(&s[i..]),
+        }
+    }
+
+    Ok(())
+}
+
+/// Indicates if every `~` in the Unicode string is followed by either `0` or `1`, usable in a `const` context.
+///
+/// Bytes of multi-byte UTF-8 sequences never equal to an ASCII byte so the Unicode string is safely checked byte by
+/// byte.
+const fn has_valid_escape_sequences(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'~' {
+            if i + 1 == bytes.len() || (bytes[i + 1] != b'0' && bytes[i + 1] != b'1') {
+                return false;
+            }
+
+            i += 2;
+        } else {
+            i += 1;
+        }
+    }
+
+    true
+}
+
+/// Checks the escape sequences of the Unicode string char by char, see [`check_escape_sequences`].
+fn check_escape_sequences_slow(s: &str) -> Result<(), Error> {
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '~' && !matches!(chars.next(), Some('0' | '1')) {
+            return Err(Error::InvalidEscapeSequence);
+        }
+    }
+
+    Ok(())
+}
+
+/// Indicates if the byte is allowed as is in a URI fragment as describe in
+/// [RFC3986](https://datatracker.ietf.org/doc/html/rfc3986#section-3.5).
+fn is_uri_fragment_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@/?".contains(&byte)
+}
+
+/// Creates a [`Pointer`] from a string literal, validating it at compile time.
+///
+/// The string literal must be empty or start with a leading `/`, and every `~` must be followed by either `0` or `1`
+/// as described in [RFC6901](https://datatracker.ietf.org/doc/html/rfc6901#section-3). Any other string literal fails
+/// the build, see [`Pointer::from_static`].
+///
+/// # Examples
+/// ```
+/// # use json_toolkit::{pointer, Pointer};
+///
+/// let pointer = pointer!("/a~1b/c");
+///
+/// assert_eq!(pointer, Pointer::new("/a~1b/c").unwrap());
+/// ```
+///
+/// A string literal with an ill-formed escape sequence does not compile:
+/// ```compile_fail
+/// # use json_toolkit::pointer;
+///
+/// let pointer = pointer!("/a~2b");
+/// ```
+///
+/// ```compile_fail
+/// # use json_toolkit::pointer;
+///
+/// let pointer = pointer!("/a~");
+/// ```
+///
+/// ```compile_fail
+/// # use json_toolkit::pointer;
+///
+/// let pointer = pointer!("a/b");
+/// ```
+#[macro_export]
+macro_rules! pointer {
+    ($s:expr) => {{
+        const POINTER: $crate::Pointer<'static> = $crate::Pointer::from_static($s);
+
+        POINTER
+    }};
+}
+
+/// Base letters of the Latin-1 Supplement and Latin Extended-A characters, from U+00C0 to U+017F, folded to lowercase
+/// ASCII. `_` marks a character which is not a letter.
+#[cfg(feature = "unicode")]
+const LATIN_BASE_LETTERS: &[u8; 192] = b"aaaaaaaceeeeiiiidnooooo_ouuuuytsaaaaaaaceeeeiiiidnooooo_ouuuuyty\
+aaaaaaccccccccddddeeeeeeeeeegggggggghhhhiiiiiiiiiiiijjkkklllllll\
+lllnnnnnnnnnoooooooorrrrrrssssssssttttttuuuuuuuuuuuuwwyyyzzzzzzs";
+
+/// Returns the primary collation weight of the char, ignoring its case and its diacritics.
+#[cfg(feature = "unicode")]
+fn collation_weight(c: char) -> char {
+    match c as usize {
+        i @ 0xC0..=0x17F if LATIN_BASE_LETTERS[i - 0xC0] != b'_' => LATIN_BASE_LETTERS[i - 0xC0] as char,
+        _ => c.to_lowercase().next().unwrap_or(c),
+    }
+}
+
+/// Compares two Unicode strings by the primary collation weight of their chars, ties being broken by their bytes.
+#[cfg(feature = "unicode")]
+fn collate(s: &str, other: &str) -> Ordering {
+    s.chars()
+        .map(collation_weight)
+        .cmp(other.chars().map(collation_weight))
+        .then_with(|| s.cmp(other))
+}
+
+/// Compares the Unicode string truncated to the length of the prefix with the prefix, consistently with the [`Ord`]
+/// implementation of [`Pointer`] for JSON pointers with the same depth.
+///
+/// Unicode strings comparing equal to the prefix are contiguous in a sorted slice, though with the `unicode` feature
+/// some of them may only collate equal to the prefix without starting with it.
+fn compare_with_prefix(s: &str, prefix: &str) -> Ordering {
+    #[cfg(feature = "unicode")]
+    {
+        s.chars()
+            .map(collation_weight)
+            .take(prefix.chars().count())
+            .cmp(prefix.chars().map(collation_weight))
+    }
+    #[cfg(not(feature = "unicode"))]
+    {
+        if s.starts_with(prefix) {
+            Ordering::Equal
+        } else {
+            s.cmp(prefix)
+        }
+    }
+}
+
+/// Looks up the JSON pointers of a sorted slice which are descendants of the given JSON pointer, using binary
+/// searches.
+///
+/// Like [`Pointer::is_ancestor_of`], a JSON pointer is considered as a descendant of itself. Since JSON pointers are
+/// sorted by depth first, see [`Pointer`], descendants are not contiguous in the slice but are contiguous among the
+/// JSON pointers with the same depth: the returned index ranges are non-empty, non-adjacent and in ascending order.
+///
+/// # Arguments
+/// * `sorted`: JSON pointers sorted as defined by the [`Ord`] implementation of [`Pointer`].
+/// * `prefix`: The ancestor JSON pointer.
+///
+/// # Examples
+/// ```
+/// # use json_toolkit::{binary_search_prefix, Pointer};
+///
+/// let mut pointers = ["/a", "/b", "/a/x", "/a/y", "/b/x", "/a/x/z"]
+///     .into_iter()
+///     .map(|s| Pointer::new(s).unwrap())
+///     .collect::<Vec<_>>();
+/// pointers.sort();
+///
+/// // sorted as `/a`, `/b`, `/a/x`, `/a/y`, `/b/x`, `/a/x/z`.
+/// let ranges = binary_search_prefix(&pointers, &Pointer::new("/a").unwrap());
+///
+/// assert_eq!(ranges, vec![0..1, 2..4, 5..6]);
+/// ```
+pub fn binary_search_prefix(sorted: &[Pointer<'_>], prefix: &Pointer<'_>) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let child_prefix = format!("{}/", prefix.as_str());
+    let max_depth = sorted.last().map_or(0, Pointer::depth);
+    let mut start = 0;
+
+    for depth in prefix.depth()..=max_depth {
+        start += sorted[start..].partition_point(|pointer| pointer.depth() < depth);
+
+        let end = start + sorted[start..].partition_point(|pointer| pointer.depth() == depth);
+        let same_depth_pointers = &sorted[start..end];
+
+        let (lower_bound, upper_bound) = if depth == prefix.depth() {
+            match same_depth_pointers.binary_search_by(|pointer| pointer.cmp(prefix)) {
+                Ok(i) => (i, i + 1),
+                Err(_) => (0, 0),
+            }
+        } else {
+            (
+                same_depth_pointers
+                    .partition_point(|pointer| compare_with_prefix(pointer.as_str(), &child_prefix) == Ordering::Less),
+                same_depth_pointers.partition_point(|pointer| {
+                    compare_with_prefix(pointer.as_str(), &child_prefix) != Ordering::Greater
+                }),
+            )
+        };
+
+        for (i, pointer) in same_depth_pointers
+            .iter()
+            .enumerate()
+            .take(upper_bound)
+            .skip(lower_bound)
+        {
+            // with the `unicode` feature, JSON pointers collating equal to the prefix may not be descendants.
+            if depth != prefix.depth() && !pointer.as_str().starts_with(&child_prefix) {
+                continue;
+            }
+
+            match ranges.last_mut() {
+                Some(range) if range.end == start + i => range.end += 1,
+                _ => ranges.push(start + i..start + i + 1),
+            }
+        }
+
+        start = end;
+    }
+
+    ranges
+}
+
+/// Precomputed URI fragment representation of a JSON pointer.
+///
+/// This type is returned by [`Pointer::to_fragment_cached`] and implements [`Display`](std::fmt::Display) without
+/// encoding the JSON pointer again, which is handy when the same JSON pointer is formatted many times.
+#[derive(Debug, Display, Clone, PartialEq, Eq, Hash)]
+#[display(fmt = "{}", .0)]
+pub struct DisplayFragment(String);
+
+impl DisplayFragment {
+    /// Returns the URI fragment representation of the JSON pointer.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A component of a JSON pointer, analogous to [`std::path::Component`].
+///
+/// This type is yielded by [`Pointer::components`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Component<'a> {
+    /// The root JSON value, which starts every JSON pointer.
+    RootDir,
+    /// A decoded reference token, which borrows the JSON pointer unless it needs to be decoded.
+    Normal(Cow<'a, str>),
+}
+
+/// JSON pointer wrapper ordered by its reference tokens rather than by depth first, unlike [`Pointer`].
+///
+/// Reference tokens are compared one by one as raw Unicode strings, hence a JSON pointer sorts right before its
+/// descendants, which sort before its next siblings, as in a document order: `/a`, `/a/b`, `/a/c`, `/b`.
+///
+/// # Examples
+/// ```
+/// # use std::collections::BTreeSet;
+/// # use json_toolkit::{LexicalPointer, Pointer};
+///
+/// let pointers = ["/b", "/a/b", "/a"].map(|s| LexicalPointer(Pointer::new(s).unwrap()));
+/// let pointers = pointers.into_iter().collect::<BTreeSet<_>>();
+///
+/// assert_eq!(
+///     pointers.iter().map(|pointer| pointer.0.as_str()).collect::<Vec<_>>(),
+///     vec!["/a", "/a/b", "/b"]
+/// );
+/// ```
+#[derive(Debug, Display, Clone, PartialEq, Eq, Hash)]
+#[display(fmt = "{}", .0)]
+pub struct LexicalPointer<'a>(pub Pointer<'a>);
+
+impl<'a> From<Pointer<'a>> for LexicalPointer<'a> {
+    fn from(pointer: Pointer<'a>) -> Self {
+        Self(pointer)
+    }
+}
+
+impl Ord for LexicalPointer<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.as_str().split('/').cmp(other.0.as_str().split('/'))
+    }
+}
+
+impl PartialOrd for LexicalPointer<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /// JSON pointer representation based on [RFC6901](https://datatracker.ietf.org/doc/html/rfc6901).
 ///
 /// This type offers strong ordering over the underlying Unicode string:
 /// - JSON pointers are sorted by ascending depth.
 /// - JSON pointers with the same depth are alphanumerically sorted.
+///
+/// When the `unicode` feature is enabled, JSON pointers with the same depth are sorted regardless of the case and of
+/// the diacritics of Latin letters, e.g. `/éclair` sorts between `/eb` and `/ed`, before falling back to the
+/// alphanumerical order.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String"))]
 #[derive(Debug, Display, Clone, PartialEq, Eq, Hash)]
 #[display(fmt = "{}", .0)]
 pub struct Pointer<'a>(Cow<'a, str>);
@@ -36,7 +392,6 @@ impl<'a> Pointer<'a> {
     /// // Construct a `Pointer` from a owned string.
     /// let pointer = Pointer::new(String::from("/a/b/c")).unwrap();
     /// ```
-
     pub fn new(s: impl Into<Cow<'a, str>>) -> Result<Self, Error> {
         let pointer = s.into();
 
@@ -47,270 +402,2558 @@ impl<'a> Pointer<'a> {
         }
     }
 
-    /// Creates a root JSON pointer.
-    pub const fn root() -> Self {
-        Self(Cow::Borrowed(""))
-    }
+    /// Creates a `Pointer` from a static Unicode string in a `const` context.
+    ///
+    /// # Panics
+    /// This function panics if the Unicode string is not empty and does not start with a leading `/`, or contains an
+    /// ill-formed escape sequence as described in [`Pointer::new_strict`], which fails the build when evaluated in a
+    /// `const` context, see [`pointer!`](crate::pointer!).
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// const POINTER: Pointer<'static> = Pointer::from_static("/a/b");
+    ///
+    /// assert_eq!(POINTER, Pointer::new("/a/b").unwrap());
+    /// ```
+    pub const fn from_static(s: &'static str) -> Pointer<'static> {
+        let bytes = s.as_bytes();
 
-    /// Indicates if the JSON pointer points to root value.
-    pub fn is_root(&self) -> bool {
-        self.0.is_empty()
+        if !bytes.is_empty() && bytes[0] != b'/' {
+            panic!("JSON pointer must start with a leading '/' if not empty");
+        }
+
+        if !has_valid_escape_sequences(s) {
+            panic!("JSON pointer contains an escape sequence other than '~0' and '~1'");
+        }
+
+        Pointer(Cow::Borrowed(s))
     }
 
-    /// Returns the Unicode string representation of the JSON pointer.
-    pub fn as_str(&self) -> &str {
-        &*self.0
+    /// Creates a `Pointer` from a Unicode string, strictly validating its escape sequences.
+    ///
+    /// Unlike [`Pointer::new`], a `~` that is not followed by either `0` or `1` is rejected as described in
+    /// [RFC6901](https://datatracker.ietf.org/doc/html/rfc6901#section-3).
+    ///
+    /// # Errors
+    /// This function fails if the Unicode string is not empty and does not start with a leading `/`, or contains an
+    /// ill-formed escape sequence.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::{Error, Pointer};
+    ///
+    /// assert!(Pointer::new_strict("/a~1b/~0c").is_ok());
+    /// assert_eq!(Pointer::new_strict("/a~2b"), Err(Error::InvalidEscapeSequence));
+    /// ```
+    pub fn new_strict(s: impl Into<Cow<'a, str>>) -> Result<Self, Error> {
+        let pointer = Self::new(s)?;
+
+        check_escape_sequences(pointer.as_str())?;
+
+        Ok(pointer)
     }
 
-    /// Returns the last reference token of the JSON pointer, also called JSON key.
+    /// Creates a `Pointer` from a vector of bytes representing a UTF-8 encoded Unicode string.
     ///
-    /// Note that the root pointer does not contains any reference tokens and so no JSON key.
+    /// # Arguments
+    /// * `bytes`: A vector of bytes representing a JSON pointer.
     ///
-    /// # Example
+    /// # Errors
+    /// This function may fail if the given bytes are not UTF-8 encoded or do not represent a valid JSON pointer.
+    ///
+    /// # Examples
     /// ```
-    /// # use json_toolkit::Pointer;
+    /// # use json_toolkit::{Error, Pointer};
     ///
-    /// let pointer = Pointer::new("/key").unwrap();
-    /// assert_eq!(pointer.key(), Some("key".to_string()));
+    /// let pointer = Pointer::from_utf8(b"/a/b/c".to_vec()).unwrap();
+    /// assert_eq!(pointer.as_str(), "/a/b/c");
     ///
-    /// let pointer = Pointer::root();
-    /// assert!(pointer.key().is_none());
+    /// let e = Pointer::from_utf8(vec![b'/', 0xff]);
+    /// assert_eq!(e, Err(Error::InvalidUtf8));
     /// ```
-    pub fn key(&self) -> Option<String> {
-        self.0.rsplit_once('/').map(|(_, token)| decode_token(token))
+    pub fn from_utf8(bytes: Vec<u8>) -> Result<Pointer<'static>, Error> {
+        String::from_utf8(bytes)
+            .map_err(|_| Error::InvalidUtf8)
+            .and_then(Pointer::new)
     }
 
-    /// Returns the parent JSON pointer.
+    /// Creates a `Pointer` from its URI fragment representation as describe in
+    /// [RFC6901](https://datatracker.ietf.org/doc/html/rfc6901#section-6).
     ///
-    /// Note that the returned JSON pointer borrows a part of the underlying Unicode string then it can be
-    /// [`clone`](Clone::clone) without any extra allocation.
+    /// The leading `#` is stripped and the percent-encoded bytes are decoded, the resulting bytes being strictly
+    /// validated as UTF-8.
     ///
-    /// # Example
+    /// # Errors
+    /// This function fails if:
+    /// - The URI fragment does not start with a `#` or contains an ill-formed percent-encoded byte.
+    /// - The decoded bytes are not a well-formed UTF-8 string, including overlong encodings.
+    /// - The decoded Unicode string is not a valid JSON pointer.
+    ///
+    /// # Examples
     /// ```
-    /// # use json_toolkit::Pointer;
+    /// # use json_toolkit::{Error, Pointer};
     ///
-    /// let pointer = Pointer::new("/nested/key").unwrap();
-    /// let parent_pointer = Pointer::new("/nested").unwrap();
+    /// let pointer = Pointer::from_uri_fragment("#/a%20b/c%25d").unwrap();
+    /// assert_eq!(pointer.as_str(), "/a b/c%d");
     ///
-    /// assert_eq!(pointer.parent(), Some(parent_pointer));
+    /// let e = Pointer::from_uri_fragment("#/%C0%AF");
+    /// assert_eq!(e, Err(Error::InvalidUtf8));
     /// ```
-    pub fn parent(&self) -> Option<Pointer<'_>> {
-        self.0
-            .rsplit_once('/')
-            .map(|(parent, _)| Pointer(Cow::Borrowed(parent)))
+    pub fn from_uri_fragment(s: &str) -> Result<Pointer<'static>, Error> {
+        let fragment = s.strip_prefix('#').ok_or(Error::InvalidUriFragment)?;
+        let mut bytes = Vec::with_capacity(fragment.len());
+        let mut fragment_bytes = fragment.bytes();
+
+        while let Some(byte) = fragment_bytes.next() {
+            if byte == b'%' {
+                let high = fragment_bytes.next().and_then(|byte| char::from(byte).to_digit(16));
+                let low = fragment_bytes.next().and_then(|byte| char::from(byte).to_digit(16));
+
+                match high.zip(low) {
+                    // both digits are lower than 16 so the decoded byte always fits in a `u8`.
+                    Some((high, low)) => bytes.push((high * 16 + low) as u8),
+                    None => return Err(Error::InvalidUriFragment),
+                }
+            } else {
+                bytes.push(byte);
+            }
+        }
+
+        Pointer::from_utf8(bytes)
     }
 
-    /// Produces an iterator over `Pointer` and its parent JSON pointers.
+    /// Creates a `Pointer` from an environment variable name, e.g. `APP__server__port` for the `/server/port` JSON
+    /// pointer.
     ///
-    /// As [`Pointer::parent`] method, all the returned JSON pointers borrow parts of the underlying Unicode string
-    /// then any of them can be [`clone`](Clone::clone) without any extra allocation.
+    /// # Arguments
+    /// * `name`: An environment variable name.
+    /// * `prefix`: The prefix that the environment variable name must start with, which is not part of the JSON pointer.
+    /// * `separator`: The separator between reference tokens, like `__`. Anything else, e.g. a single `_` when the
+    ///   separator is `__`, is part of the reference tokens. Note that the letter case is kept as is.
     ///
-    /// The iterator will yield the `Pointer` then its parents like `self`, `self.parent().unwrap()`,
-    /// `self.parent().unwrap().parent().unwrap()` and so on until reaching the root JSON pointer.
+    /// Returns `None` if the environment variable name does not start with the prefix followed by the separator.
+    /// The environment variable name equal to the prefix is the root JSON pointer.
     ///
     /// # Examples
     /// ```
     /// # use json_toolkit::Pointer;
     ///
-    /// let pointer = Pointer::new("/foo/bar/zoo").unwrap();
-    /// let ancestors = pointer.ancestors().collect::<Vec<_>>();
-    ///
-    /// assert_eq!(
-    ///     ancestors,
-    ///     vec![
-    ///         Pointer::new("/foo/bar/zoo").unwrap(),
-    ///         Pointer::new("/foo/bar").unwrap(),
-    ///         Pointer::new("/foo").unwrap(),
-    ///         Pointer::root()
-    ///     ]
-    /// );
+    /// let pointer = Pointer::from_env_name("APP__server__max_connections", "APP", "__");
     ///
+    /// assert_eq!(pointer, Some(Pointer::new("/server/max_connections").unwrap()));
+    /// assert_eq!(Pointer::from_env_name("OTHER__server", "APP", "__"), None);
     /// ```
-    pub fn ancestors(&self) -> impl Iterator<Item = Pointer<'_>> {
-        self.0
-            .match_indices('/')
-            .map(|(i, _)| i)
-            .chain([self.0.len()])
-            .rev()
-            .map(|i| Pointer(Cow::Borrowed(&self.0[0..i])))
+    pub fn from_env_name(name: &str, prefix: &str, separator: &str) -> Option<Pointer<'static>> {
+        let rest = name.strip_prefix(prefix)?;
+
+        if rest.is_empty() {
+            return Some(Pointer::root());
+        }
+
+        rest.strip_prefix(separator)
+            .filter(|_| !separator.is_empty())
+            .map(|rest| Pointer::from_tokens(rest.split(separator)))
     }
 
-    /// Indicates if `Pointer` is an ancestor of the given JSON pointer.
+    /// Parses a comma-separated list of JSON pointers.
     ///
-    /// Note that `Pointer` is an ancestor of itself.
-    pub fn is_ancestor_of(&self, other: &Pointer<'_>) -> bool {
-        other.ancestors().any(|pointer| pointer == *self)
+    /// Each entry is trimmed before being parsed, and an empty input yields an empty list. Note that an empty entry
+    /// between two commas is parsed as the root JSON pointer.
+    ///
+    /// # Errors
+    /// This function fails on the first invalid entry, reporting its zero-based position in the list.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointers = Pointer::parse_csv("/a/b, /c/d").unwrap();
+    ///
+    /// assert_eq!(pointers, vec![Pointer::new("/a/b").unwrap(), Pointer::new("/c/d").unwrap()]);
+    /// ```
+    pub fn parse_csv(s: &str) -> Result<Vec<Pointer<'static>>, Error> {
+        if s.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        s.split(',')
+            .enumerate()
+            .map(|(position, s)| {
+                Pointer::new(s.trim().to_string()).map_err(|e| Error::InvalidListEntry {
+                    position,
+                    source: Box::new(e),
+                })
+            })
+            .collect()
     }
 
-    /// Indicates if `Pointer` is a parent of the given JSON pointer.
+    /// Converts a [MongoDB dot notation](https://www.mongodb.com/docs/manual/core/document/#dot-notation) path into a
+    /// JSON pointer, e.g. `a.b.0.c` into `/a/b/0/c`.
     ///
-    /// Note that the root JSON pointer is the only one with no parent.
-    pub fn is_parent_of(&self, other: &Pointer<'_>) -> bool {
-        other.parent().as_ref() == Some(self)
-    }
+    /// The empty path is converted into the root JSON pointer.
+    ///
+    /// # Errors
+    /// This function fails with [`Error::UnsupportedConversion`] if the path contains an empty field name, e.g. `a..b`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::from_mongo("a.b/c.0").unwrap();
+    ///
+    /// assert_eq!(pointer.as_str(), "/a/b~1c/0");
+    /// ```
+    pub fn from_mongo(s: &str) -> Result<Pointer<'static>, Error> {
+        if s.is_empty() {
+            return Ok(Pointer::root());
+        }
 
-    /// Indicates if `Pointer` is a sibling of the given JSON pointer.
-    pub fn is_sibling_of(&self, other: &Pointer<'_>) -> bool {
-        self != other && self.parent() == other.parent()
-    }
+        if s.split('.').any(str::is_empty) {
+            return Err(Error::UnsupportedConversion(s.to_string()));
+        }
 
-    /// Indicates the number of reference tokens in the JSON pointer, in a zero-based indexed way.
-    pub fn depth(&self) -> usize {
-        self.0.split('/').skip(1).count()
+        Ok(Pointer::from_tokens(s.split('.')))
     }
 
-    /// Creates an owned instance of `Pointer`.
+    /// Converts a simple [JSONPath](https://goessner.net/articles/JsonPath/) expression into a JSON pointer.
     ///
-    /// Note that this function may call `Clone::clone` if the underlying Unicode string is borrowed.
-    pub fn into_owned(self) -> Pointer<'static> {
-        Pointer(Cow::Owned(self.0.into_owned()))
-    }
-
-    /// Evaluates `Pointer` into tokens as define in [RFC6901](https://datatracker.ietf.org/doc/html/rfc6901#section-4).
+    /// Only the JSONPath subset mapping to a single JSON pointer is supported: the `$` root followed by dot-notation
+    /// keys (`.key`), bracket-notation keys (`['key']` or `["key"]`) and array indexes (`[0]`).
+    ///
+    /// # Errors
+    /// This function fails with [`Error::UnsupportedJsonPath`] on any other JSONPath construct, such as wildcards,
+    /// recursive descents, slices, unions or filters.
     ///
     /// # Examples
     /// ```
     /// # use json_toolkit::Pointer;
     ///
-    /// let pointer = Pointer::new("/~1foo/~0bar/zoo").unwrap();
-    /// let tokens = pointer.tokenize().collect::<Vec<_>>();
+    /// let pointer = Pointer::try_from_jsonpath("$.store.book[0]['title']").unwrap();
     ///
-    /// assert_eq!(
-    ///     tokens,
-    ///     vec![
-    ///         "/foo".to_string(),
-    ///         "~bar".to_string(),
-    ///         "zoo".to_string(),
-    ///     ]
-    /// );
+    /// assert_eq!(pointer.as_str(), "/store/book/0/title");
     /// ```
-    pub fn tokenize(&'a self) -> impl Iterator<Item = String> + 'a {
-        self.0.split('/').skip(1).map(decode_token)
-    }
-}
+    pub fn try_from_jsonpath(expr: &str) -> Result<Pointer<'static>, Error> {
+        let unsupported = || Error::UnsupportedJsonPath(expr.to_string());
+        let rest = expr.strip_prefix('$').ok_or_else(unsupported)?;
+        let mut chars = rest.chars().peekable();
+        let mut pointer = Pointer::root();
+
+        while let Some(c) = chars.next() {
+            let token = match c {
+                '.' => {
+                    let mut token = String::new();
+
+                    while let Some(&c) = chars.peek() {
+                        if c == '.' || c == '[' {
+                            break;
+                        }
+
+                        token.push(c);
+                        chars.next();
+                    }
+
+                    if token.is_empty() || token == "*" {
+                        return Err(unsupported());
+                    }
+
+                    token
+                }
+                '[' => {
+                    let mut token = String::new();
+
+                    match chars.next() {
+                        Some(quote @ ('\'' | '"')) => loop {
+                            match chars.next() {
+                                Some(c) if c == quote => break,
+                                Some('\\') => token.push(chars.next().ok_or_else(unsupported)?),
+                                Some(c) => token.push(c),
+                                None => return Err(unsupported()),
+                            }
+                        },
+                        Some(c) if c.is_ascii_digit() => {
+                            token.push(c);
+
+                            while let Some(&c) = chars.peek() {
+                                if !c.is_ascii_digit() {
+                                    break;
+                                }
+
+                                token.push(c);
+                                chars.next();
+                            }
+                        }
+                        _ => return Err(unsupported()),
+                    }
+
+                    if chars.next() != Some(']') {
+                        return Err(unsupported());
+                    }
+
+                    token
+                }
+                _ => return Err(unsupported()),
+            };
+
+            pointer = pointer.join(&token);
+        }
 
-impl FromStr for Pointer<'_> {
-    type Err = Error;
+        Ok(pointer)
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    /// Creates an owned JSON pointer from raw reference tokens.
+    ///
+    /// Each token is encoded before being appended, hence any Unicode string, including control characters, is a
+    /// valid reference token. This is the inverse operation of [`Pointer::tokenize`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::from_tokens(["foo", "a/b"]);
+    ///
+    /// assert_eq!(pointer.as_str(), "/foo/a~1b");
+    /// ```
+    pub fn from_tokens<I, S>(tokens: I) -> Pointer<'static>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        tokens
+            .into_iter()
+            .fold(Pointer::root(), |pointer, token| pointer.join(token.as_ref()))
+    }
+
+    /// Creates a root JSON pointer.
+    pub const fn root() -> Self {
+        Self(Cow::Borrowed(""))
+    }
+
+    /// Parses a relative JSON pointer, see [`RelativePointer::new`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let relative_pointer = Pointer::parse_relative("1/foo").unwrap();
+    /// assert_eq!(relative_pointer.to_string(), "1/foo");
+    /// ```
+    ///
+    /// # Errors
+    /// This method fails with [`Error::InvalidRelativePointer`] if the Unicode string is not a valid relative JSON
+    /// pointer.
+    pub fn parse_relative(s: &str) -> Result<RelativePointer<'_>, Error> {
+        RelativePointer::new(s)
+    }
+
+    /// Creates a [`PointerBuilder`] starting from the root JSON pointer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// assert_eq!(Pointer::builder().key("a").index(0).key("b").build().as_str(), "/a/0/b");
+    /// ```
+    pub fn builder() -> PointerBuilder {
+        PointerBuilder::new()
+    }
+
+    /// Indicates if the JSON pointer points to root value.
+    pub fn is_root(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Indicates if the JSON pointer has a JSON key, i.e. is not the root JSON pointer, see [`Pointer::key_cow`].
+    ///
+    /// Note that an empty reference token is a valid JSON key.
+    pub fn has_key(&self) -> bool {
+        !self.is_root()
+    }
+
+    /// Returns the Unicode string representation of the JSON pointer.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Writes the Unicode string representation of the JSON pointer into the given writer, without any intermediate
+    /// allocation.
+    ///
+    /// This is handy to write many JSON pointers into a single buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let mut s = String::new();
+    ///
+    /// Pointer::new("/a/b").unwrap().write_to(&mut s).unwrap();
+    ///
+    /// assert_eq!(s, "/a/b");
+    /// ```
+    pub fn write_to(&self, out: &mut impl Write) -> core::fmt::Result {
+        out.write_str(&self.0)
+    }
+
+    /// Returns the last reference token of the JSON pointer, also called JSON key.
+    ///
+    /// Note that the root pointer does not contains any reference tokens and so no JSON key.
+    ///
+    /// # Example
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/key").unwrap();
+    /// assert_eq!(pointer.key(), Some("key".to_string()));
+    ///
+    /// let pointer = Pointer::root();
+    /// assert!(pointer.key().is_none());
+    /// ```
+    pub fn key(&self) -> Option<String> {
+        self.0.rsplit_once('/').map(|(_, token)| decode_token(token))
+    }
+
+    /// Returns the last reference token of the JSON pointer without allocating if it does not need to be decoded.
+    ///
+    /// The JSON key borrows the underlying Unicode string unless it contains `~0` or `~1` escape sequences.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::borrow::Cow;
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/foo/key").unwrap();
+    /// assert!(matches!(pointer.key_cow(), Some(Cow::Borrowed("key"))));
+    ///
+    /// let pointer = Pointer::new("/foo/a~1b").unwrap();
+    /// assert!(matches!(pointer.key_cow(), Some(Cow::Owned(key)) if key == "a/b"));
+    /// ```
+    pub fn key_cow(&self) -> Option<Cow<'_, str>> {
+        self.0.rsplit_once('/').map(|(_, token)| decode_token_cow(token))
+    }
+
+    /// Returns the last reference token of the JSON pointer parsed as a JSON array index, see [`Pointer::with_index`].
+    ///
+    /// As described in [RFC6901](https://datatracker.ietf.org/doc/html/rfc6901#section-4), a JSON array index is made
+    /// of ASCII digits without leading zeros. Returns `None` for the root JSON pointer or if the last reference token is
+    /// not a JSON array index, including `-`.
+    ///
+    /// # Example
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/items").unwrap();
+    ///
+    /// assert_eq!(pointer.with_index(3).key_as_index(), Some(3));
+    /// assert_eq!(pointer.key_as_index(), None);
+    /// ```
+    pub fn key_as_index(&self) -> Option<usize> {
+        self.0.rsplit_once('/').and_then(|(_, token)| parse_index(token))
+    }
+
+    /// Returns the decoded reference token located at the given zero-based index.
+    ///
+    /// Note that the root pointer does not contains any reference tokens, hence `None` is always returned.
+    ///
+    /// # Example
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/a/b~1c/d").unwrap();
+    ///
+    /// assert_eq!(pointer.token_at(1), Some("b/c".to_string()));
+    /// assert_eq!(pointer.token_at(3), None);
+    /// ```
+    pub fn token_at(&self, index: usize) -> Option<String> {
+        self.0.split('/').skip(1).nth(index).map(decode_token)
+    }
+
+    /// Returns the first decoded reference token of the JSON pointer, see [`Pointer::token_at`].
+    ///
+    /// # Example
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// assert_eq!(Pointer::new("/users/0").unwrap().first_token(), Some("users".to_string()));
+    /// assert_eq!(Pointer::root().first_token(), None);
+    /// ```
+    pub fn first_token(&self) -> Option<String> {
+        self.token_at(0)
+    }
+
+    /// Returns the parent JSON pointer.
+    ///
+    /// Note that the returned JSON pointer borrows a part of the underlying Unicode string then it can be
+    /// [`clone`](Clone::clone) without any extra allocation.
+    ///
+    /// # Example
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/nested/key").unwrap();
+    /// let parent_pointer = Pointer::new("/nested").unwrap();
+    ///
+    /// assert_eq!(pointer.parent(), Some(parent_pointer));
+    /// ```
+    pub fn parent(&self) -> Option<Pointer<'_>> {
+        self.0
+            .rsplit_once('/')
+            .map(|(parent, _)| Pointer(Cow::Borrowed(parent)))
+    }
+
+    /// Creates an owned JSON pointer without its empty reference tokens, e.g. turning `/a//b/` into `/a/b`.
+    ///
+    /// Note that this cleanup is lossy: an empty reference token is a valid JSON object key as described in
+    /// [RFC6901](https://datatracker.ietf.org/doc/html/rfc6901#section-5), hence the returned JSON pointer may point to
+    /// another JSON value. Use it only when empty reference tokens are known to be accidental.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/a//b/").unwrap();
+    ///
+    /// assert_eq!(pointer.collapse_empty_tokens().as_str(), "/a/b");
+    /// ```
+    pub fn collapse_empty_tokens(&self) -> Pointer<'static> {
+        let mut s = String::with_capacity(self.0.len());
+
+        for token in self.0.split('/').filter(|token| !token.is_empty()) {
+            s.push('/');
+            s.push_str(token);
+        }
+
+        Pointer(Cow::Owned(s))
+    }
+
+    #[cfg(any(feature = "std", test))]
+    /// Creates an owned JSON pointer whose reference tokens are decoded then encoded again.
+    ///
+    /// Normalization makes JSON pointers with the same decoded reference tokens equal, e.g. lax escapes like `~2` are
+    /// encoded as `~02`.
+    pub(crate) fn normalized(&self) -> Pointer<'static> {
+        let mut s = String::with_capacity(self.0.len());
+
+        for token in self.tokenize() {
+            s.push('/');
+            s.push_str(&encode_token(&token));
+        }
+
+        Pointer(Cow::Owned(s))
+    }
+
+    /// Normalizes the given JSON pointers in place.
+    ///
+    /// Normalization makes JSON pointers with the same decoded reference tokens equal, e.g. lax escapes like `~2` are
+    /// encoded as `~02`. JSON pointers without any `~` are already normalized and are left untouched, while a single
+    /// scratch buffer is reused across the other ones to minimize allocations.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let mut pointers = [Pointer::new("/a~2b").unwrap(), Pointer::new("/a/b").unwrap()];
+    ///
+    /// Pointer::normalize_all(&mut pointers);
+    ///
+    /// assert_eq!(pointers, [Pointer::new("/a~02b").unwrap(), Pointer::new("/a/b").unwrap()]);
+    /// ```
+    pub fn normalize_all(pointers: &mut [Pointer<'static>]) {
+        let mut scratch = String::new();
+
+        for pointer in pointers {
+            if !pointer.0.contains('~') {
+                continue;
+            }
+
+            scratch.clear();
+
+            for token in pointer.tokenize() {
+                scratch.push('/');
+                scratch.push_str(&encode_token(&token));
+            }
+
+            if scratch != pointer.0 {
+                // the previous string of the JSON pointer becomes the next scratch buffer.
+                core::mem::swap(pointer.0.to_mut(), &mut scratch);
+            }
+        }
+    }
+
+    /// Creates an owned child JSON pointer by appending the given raw reference token.
+    ///
+    /// The reference token is encoded, `~` becoming `~0` and `/` becoming `~1`, making this method the inverse of
+    /// [`Pointer::key`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/foo").unwrap().join("a/b");
+    ///
+    /// assert_eq!(pointer.as_str(), "/foo/a~1b");
+    /// assert_eq!(pointer.key(), Some("a/b".to_string()));
+    /// ```
+    pub fn join(&self, token: &str) -> Pointer<'static> {
+        let mut pointer = self.clone().into_owned();
+
+        pointer.push_token(token);
+
+        pointer
+    }
+
+    /// Appends the given raw reference token to the JSON pointer, encoding it as described in [`Pointer::join`].
+    ///
+    /// Note that a borrowed JSON pointer is converted into an owned one.
+    pub fn push_token(&mut self, token: &str) {
+        let s = self.0.to_mut();
+
+        s.push('/');
+        s.push_str(&encode_token(token));
+    }
+
+    /// Creates an owned child JSON pointer by appending the given JSON array index.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/items").unwrap();
+    ///
+    /// assert_eq!(pointer.with_index(42).as_str(), "/items/42");
+    /// ```
+    pub fn with_index(&self, index: usize) -> Pointer<'static> {
+        let mut pointer = self.clone().into_owned();
+
+        pointer.push_index(index);
+
+        pointer
+    }
+
+    /// Appends the given JSON array index to the JSON pointer.
+    ///
+    /// Note that a borrowed JSON pointer is converted into an owned one.
+    pub fn push_index(&mut self, index: usize) {
+        let s = self.0.to_mut();
+
+        s.push('/');
+        push_decimal(s, index);
+    }
+
+    /// Removes the last reference token of the JSON pointer and returns it decoded, or `None` for the root JSON
+    /// pointer.
+    ///
+    /// A borrowed JSON pointer borrows a shorter string, while an owned one is truncated without any reallocation.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let mut pointer = Pointer::new("/foo/a~1b").unwrap();
+    ///
+    /// assert_eq!(pointer.pop(), Some("a/b".to_string()));
+    /// assert_eq!(pointer.as_str(), "/foo");
+    /// ```
+    pub fn pop(&mut self) -> Option<String> {
+        let i = self.0.rfind('/')?;
+        let token = decode_token(&self.0[i + 1..]);
+
+        match &mut self.0 {
+            Cow::Borrowed(s) => {
+                let borrowed: &'a str = s;
+
+                *s = &borrowed[..i];
+            }
+            Cow::Owned(s) => s.truncate(i),
+        }
+
+        Some(token)
+    }
+
+    /// Creates an owned child JSON pointer by appending the given JSON key, provided it belongs to the allowed ones.
+    ///
+    /// # Arguments
+    /// * `key`: A raw JSON key, which is encoded as a reference token.
+    /// * `allowed`: The allowed JSON keys.
+    ///
+    /// # Errors
+    /// This method fails if the given JSON key is not allowed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::{Error, Pointer};
+    ///
+    /// let pointer = Pointer::new("/server").unwrap();
+    /// let allowed = ["host", "port"];
+    ///
+    /// assert_eq!(pointer.child_checked("port", &allowed), Pointer::new("/server/port"));
+    /// assert_eq!(pointer.child_checked("prot", &allowed), Err(Error::UnknownKey("prot".to_string())));
+    /// ```
+    pub fn child_checked(&self, key: &str, allowed: &[&str]) -> Result<Pointer<'static>, Error> {
+        if allowed.contains(&key) {
+            Ok(self.join(key))
+        } else {
+            Err(Error::UnknownKey(key.to_string()))
+        }
+    }
+
+    /// Concatenates the given JSON pointer at the end of `Pointer`.
+    ///
+    /// Note that concatenating the root JSON pointer is a no-op. The `+` operator may be used as well.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let base = Pointer::new("/foo").unwrap();
+    /// let suffix = Pointer::new("/bar/0").unwrap();
+    ///
+    /// assert_eq!(base.concat(&suffix), Pointer::new("/foo/bar/0").unwrap());
+    /// assert_eq!(base + &suffix, Pointer::new("/foo/bar/0").unwrap());
+    /// ```
+    pub fn concat(&self, other: &Pointer<'_>) -> Pointer<'static> {
+        Pointer(Cow::Owned(format!("{}{}", self.0, other.0)))
+    }
+
+    /// Rewrites the JSON pointer according to the given mapping between source and target JSON pointers.
+    ///
+    /// The first mapping whose source JSON pointer is an ancestor of `Pointer`, see [`Pointer::is_ancestor_of`], is
+    /// applied by replacing the source prefix with the target JSON pointer. Returns `None` if no mapping applies.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let mapping = [(Pointer::new("/a/b").unwrap(), Pointer::new("/x/y").unwrap())];
+    /// let pointer = Pointer::new("/a/b/c").unwrap();
+    ///
+    /// assert_eq!(pointer.map_pointer(&mapping), Some(Pointer::new("/x/y/c").unwrap()));
+    /// ```
+    pub fn map_pointer(&self, mapping: &[(Pointer<'_>, Pointer<'_>)]) -> Option<Pointer<'static>> {
+        mapping
+            .iter()
+            .find_map(|(source, target)| self.strip_prefix(source).map(|suffix| target.concat(&suffix)))
+    }
+
+    /// Gets the JSON pointer relative to the given base JSON pointer, i.e. `Pointer` without the base prefix.
+    ///
+    /// Returns `None` if the base JSON pointer is not an ancestor of `Pointer`, see [`Pointer::is_ancestor_of`], and
+    /// the root JSON pointer if both JSON pointers are equal. As [`Pointer::parent`] method, the returned JSON pointer
+    /// borrows a part of the underlying Unicode string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/a/b/c").unwrap();
+    ///
+    /// assert_eq!(pointer.strip_prefix(&Pointer::new("/a").unwrap()), Some(Pointer::new("/b/c").unwrap()));
+    /// assert_eq!(pointer.strip_prefix(&pointer), Some(Pointer::root()));
+    /// assert_eq!(pointer.strip_prefix(&Pointer::new("/b").unwrap()), None);
+    /// ```
+    pub fn strip_prefix(&self, base: &Pointer<'_>) -> Option<Pointer<'_>> {
+        if base.is_ancestor_of(self) {
+            Some(Pointer(Cow::Borrowed(&self.0[base.0.len()..])))
+        } else {
+            None
+        }
+    }
+
+    /// Gets the deepest JSON pointer which is an ancestor of both `Pointer` and the given JSON pointer, see
+    /// [`Pointer::is_ancestor_of`].
+    ///
+    /// Reference tokens are compared once decoded, and the root JSON pointer is returned if the first reference tokens
+    /// already differ. As [`Pointer::parent`] method, the returned JSON pointer borrows a part of the underlying Unicode
+    /// string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/a/b/c").unwrap();
+    ///
+    /// assert_eq!(pointer.common_ancestor(&Pointer::new("/a/b/d").unwrap()), Pointer::new("/a/b").unwrap());
+    /// assert_eq!(pointer.common_ancestor(&Pointer::new("/x").unwrap()), Pointer::root());
+    /// ```
+    pub fn common_ancestor(&self, other: &Pointer<'_>) -> Pointer<'_> {
+        let end = self
+            .token_spans()
+            .zip(other.tokens())
+            .take_while(|((_, token), other_token)| decode_token_cow(token) == *other_token)
+            .last()
+            .map_or(0, |((span, _), _)| span.end);
+
+        Pointer(Cow::Borrowed(&self.0[..end]))
+    }
+
+    /// Returns the value associated with the deepest JSON pointer of the table which is an ancestor of the given JSON
+    /// pointer, see [`Pointer::is_ancestor_of`].
+    ///
+    /// The first JSON pointer wins among equally deep ones.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let mounts = [
+    ///     (Pointer::root(), "root"),
+    ///     (Pointer::new("/a").unwrap(), "a"),
+    ///     (Pointer::new("/a/b").unwrap(), "b"),
+    /// ];
+    ///
+    /// assert_eq!(Pointer::longest_prefix_match(&mounts, &Pointer::new("/a/b/c").unwrap()), Some(&"b"));
+    /// assert_eq!(Pointer::longest_prefix_match(&mounts, &Pointer::new("/x").unwrap()), Some(&"root"));
+    /// ```
+    pub fn longest_prefix_match<'t, T>(table: &'t [(Pointer<'_>, T)], query: &Pointer<'_>) -> Option<&'t T> {
+        let mut longest_match: Option<(usize, &'t T)> = None;
+
+        for (prefix, value) in table.iter().filter(|(prefix, _)| prefix.is_ancestor_of(query)) {
+            let depth = prefix.depth();
+
+            if longest_match.map_or(true, |(longest_depth, _)| depth > longest_depth) {
+                longest_match = Some((depth, value));
+            }
+        }
+
+        longest_match.map(|(_, value)| value)
+    }
+
+    /// Produces an iterator over `Pointer` and its parent JSON pointers.
+    ///
+    /// As [`Pointer::parent`] method, all the returned JSON pointers borrow parts of the underlying Unicode string
+    /// then any of them can be [`clone`](Clone::clone) without any extra allocation.
+    ///
+    /// The iterator will yield the `Pointer` then its parents like `self`, `self.parent().unwrap()`,
+    /// `self.parent().unwrap().parent().unwrap()` and so on until reaching the root JSON pointer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/foo/bar/zoo").unwrap();
+    /// let ancestors = pointer.ancestors().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     ancestors,
+    ///     vec![
+    ///         Pointer::new("/foo/bar/zoo").unwrap(),
+    ///         Pointer::new("/foo/bar").unwrap(),
+    ///         Pointer::new("/foo").unwrap(),
+    ///         Pointer::root()
+    ///     ]
+    /// );
+    ///
+    /// ```
+    pub fn ancestors(&self) -> impl Iterator<Item = Pointer<'_>> {
+        self.0
+            .match_indices('/')
+            .map(|(i, _)| i)
+            .chain([self.0.len()])
+            .rev()
+            .map(|i| Pointer(Cow::Borrowed(&self.0[0..i])))
+    }
+
+    /// Indicates if `Pointer` is an ancestor of the given JSON pointer.
+    ///
+    /// Note that `Pointer` is an ancestor of itself.
+    pub fn is_ancestor_of(&self, other: &Pointer<'_>) -> bool {
+        other.ancestors().any(|pointer| pointer == *self)
+    }
+
+    /// Indicates if `Pointer` is a parent of the given JSON pointer.
+    ///
+    /// Note that the root JSON pointer is the only one with no parent.
+    pub fn is_parent_of(&self, other: &Pointer<'_>) -> bool {
+        other.parent().as_ref() == Some(self)
+    }
+
+    /// Indicates if `Pointer` is a sibling of the given JSON pointer.
+    pub fn is_sibling_of(&self, other: &Pointer<'_>) -> bool {
+        self != other && self.parent() == other.parent()
+    }
+
+    /// Indicates if `Pointer` and the given JSON pointer have the same reference tokens once the reference tokens
+    /// located at the ignored depths are removed from both of them.
+    ///
+    /// Depths are zero-based reference token indexes, so ignoring the depth `0` removes the first reference token.
+    /// Reference tokens are compared once decoded.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/records/wrapper/0/name").unwrap();
+    /// let other = Pointer::new("/records/inner/0/name").unwrap();
+    ///
+    /// assert!(pointer.eq_ignoring_depths(&other, &[1]));
+    /// assert!(!pointer.eq_ignoring_depths(&other, &[0]));
+    /// ```
+    pub fn eq_ignoring_depths(&self, other: &Pointer<'_>, ignore: &[usize]) -> bool {
+        let kept_tokens = |pointer: &Pointer<'_>| {
+            pointer
+                .tokenize()
+                .enumerate()
+                .filter(|(depth, _)| !ignore.contains(depth))
+                .map(|(_, token)| token)
+                .collect::<Vec<_>>()
+        };
+
+        kept_tokens(self) == kept_tokens(other)
+    }
+
+    /// Indicates the number of reference tokens in the JSON pointer, in a zero-based indexed way.
+    pub fn depth(&self) -> usize {
+        self.0.split('/').skip(1).count()
+    }
+
+    /// Creates an owned instance of `Pointer`.
+    ///
+    /// Note that this function may call `Clone::clone` if the underlying Unicode string is borrowed. Otherwise the
+    /// underlying Unicode string is moved as is, hence it is guaranteed to be a no-op without any extra allocation.
+    pub fn into_owned(self) -> Pointer<'static> {
+        Pointer(Cow::Owned(self.0.into_owned()))
+    }
+
+    /// Evaluates `Pointer` into tokens as define in [RFC6901](https://datatracker.ietf.org/doc/html/rfc6901#section-4).
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/~1foo/~0bar/zoo").unwrap();
+    /// let tokens = pointer.tokenize().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     tokens,
+    ///     vec![
+    ///         "/foo".to_string(),
+    ///         "~bar".to_string(),
+    ///         "zoo".to_string(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn tokenize(&'a self) -> impl Iterator<Item = String> + 'a {
+        self.0.split('/').skip(1).map(decode_token)
+    }
+
+    /// Produces an iterator over the decoded reference tokens of the JSON pointer, borrowing the ones which do not
+    /// need to be decoded.
+    ///
+    /// Unlike [`Pointer::tokenize`], a reference token is allocated only if it contains an escape sequence.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    /// use std::borrow::Cow;
+    ///
+    /// let pointer = Pointer::new("/foo/~0bar").unwrap();
+    /// let tokens = pointer.tokens().collect::<Vec<_>>();
+    ///
+    /// assert!(matches!(tokens[0], Cow::Borrowed("foo")));
+    /// assert_eq!(tokens[1], "~bar");
+    /// ```
+    pub fn tokens(&self) -> impl Iterator<Item = Cow<'_, str>> {
+        self.0.split('/').skip(1).map(decode_token_cow)
+    }
+
+    /// Produces an iterator over the raw reference tokens of the JSON pointer alongside their byte range in the
+    /// underlying Unicode string.
+    ///
+    /// Reference tokens are not decoded, and their byte range excludes the leading `/`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/a/~1b").unwrap();
+    ///
+    /// assert_eq!(pointer.token_spans().collect::<Vec<_>>(), vec![(1..2, "a"), (3..6, "~1b")]);
+    /// ```
+    pub fn token_spans(&self) -> impl Iterator<Item = (Range<usize>, &str)> {
+        let s = self.as_str();
+
+        s.match_indices('/').map(move |(i, _)| {
+            let start = i + 1;
+            let end = s[start..].find('/').map_or(s.len(), |len| start + len);
+
+            (start..end, &s[start..end])
+        })
+    }
+
+    /// Produces an iterator over the components of the JSON pointer, analogous to [`Path::components`](std::path::Path::components).
+    ///
+    /// The iterator yields [`Component::RootDir`] first, then a [`Component::Normal`] for each decoded reference token.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::borrow::Cow;
+    /// # use json_toolkit::{Component, Pointer};
+    ///
+    /// let pointer = Pointer::new("/a/b~1c").unwrap();
+    ///
+    /// assert_eq!(
+    ///     pointer.components().collect::<Vec<_>>(),
+    ///     vec![
+    ///         Component::RootDir,
+    ///         Component::Normal(Cow::Borrowed("a")),
+    ///         Component::Normal(Cow::Owned("b/c".to_string())),
+    ///     ]
+    /// );
+    /// ```
+    pub fn components(&self) -> impl Iterator<Item = Component<'_>> {
+        core::iter::once(Component::RootDir).chain(
+            self.token_spans()
+                .map(|(_, token)| Component::Normal(decode_token_cow(token))),
+        )
+    }
+
+    /// Returns the URI fragment representation of the JSON pointer as describe in
+    /// [RFC6901](https://datatracker.ietf.org/doc/html/rfc6901#section-6).
+    ///
+    /// The JSON pointer is prefixed by `#` and any character not allowed in a URI fragment is percent-encoded.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/a b/c%d").unwrap();
+    /// assert_eq!(pointer.to_uri_fragment(), "#/a%20b/c%25d");
+    /// ```
+    pub fn to_uri_fragment(&self) -> String {
+        let mut fragment = String::with_capacity(self.0.len() + 1);
+
+        fragment.push('#');
+
+        for byte in self.0.bytes() {
+            if is_uri_fragment_byte(byte) {
+                fragment.push(char::from(byte));
+            } else {
+                // writing into a `String` never fails.
+                let _ = write!(fragment, "%{:02X}", byte);
+            }
+        }
+
+        fragment
+    }
+
+    /// Returns the precomputed URI fragment representation of the JSON pointer.
+    ///
+    /// See [`Pointer::to_uri_fragment`] for the encoding details.
+    pub fn to_fragment_cached(&self) -> DisplayFragment {
+        DisplayFragment(self.to_uri_fragment())
+    }
+
+    /// Renders the JSON pointer as a GraphQL-style path, as used by the `path` entry of GraphQL errors.
+    ///
+    /// Decoded reference tokens are joined with dots, array indexes being path segments on their own. The root JSON
+    /// pointer is rendered as an empty string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/hero/friends/0/name").unwrap();
+    ///
+    /// assert_eq!(pointer.to_graphql_path(), "hero.friends.0.name");
+    /// ```
+    pub fn to_graphql_path(&self) -> String {
+        self.tokenize().collect::<Vec<_>>().join(".")
+    }
+
+    /// Renders the JSON pointer as a [MongoDB dot notation](https://www.mongodb.com/docs/manual/core/document/#dot-notation)
+    /// path, e.g. `/a/b/0/c` as `a.b.0.c`.
+    ///
+    /// The root JSON pointer is rendered as an empty string.
+    ///
+    /// # Errors
+    /// This method fails with [`Error::UnsupportedConversion`] if a decoded reference token is empty or contains a dot,
+    /// since MongoDB dot notation cannot represent it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::{Error, Pointer};
+    ///
+    /// assert_eq!(Pointer::new("/a/b/0/c").unwrap().to_mongo(), Ok("a.b.0.c".to_string()));
+    /// assert_eq!(
+    ///     Pointer::new("/a.b").unwrap().to_mongo(),
+    ///     Err(Error::UnsupportedConversion("/a.b".to_string()))
+    /// );
+    /// ```
+    pub fn to_mongo(&self) -> Result<String, Error> {
+        let tokens = self.tokenize().collect::<Vec<_>>();
+
+        if tokens.iter().any(|token| token.is_empty() || token.contains('.')) {
+            return Err(Error::UnsupportedConversion(self.to_string()));
+        }
+
+        Ok(tokens.join("."))
+    }
+
+    /// Returns a stable numeric identifier of the JSON pointer.
+    ///
+    /// The identifier is a 64-bit [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/index.html) hash of the decoded
+    /// reference tokens. It does not depend on the process, the platform or the crate version, hence JSON pointers with
+    /// the same reference tokens always get the same identifier.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/foo/bar").unwrap();
+    ///
+    /// assert_eq!(pointer.stable_id(), Pointer::new("/foo/bar").unwrap().stable_id());
+    /// assert_ne!(pointer.stable_id(), Pointer::new("/foo/zoo").unwrap().stable_id());
+    /// ```
+    pub fn stable_id(&self) -> u64 {
+        let mut hasher = Fnv64::new();
+
+        self.write_stable_bytes(|bytes| hasher.write(bytes));
+
+        hasher.finish()
+    }
+
+    /// Returns a stable 128-bit numeric identifier of the JSON pointer, far less likely to collide than
+    /// [`Pointer::stable_id`].
+    ///
+    /// The identifier is a 128-bit [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/index.html) hash of the decoded
+    /// reference tokens. It does not depend on the process, the platform or the crate version, hence JSON pointers with
+    /// the same reference tokens always get the same identifier.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::Pointer;
+    ///
+    /// let pointer = Pointer::new("/foo/bar").unwrap();
+    ///
+    /// assert_eq!(pointer.stable_id_128(), Pointer::new("/foo/bar").unwrap().stable_id_128());
+    /// assert_ne!(pointer.stable_id_128(), Pointer::new("/foo/zoo").unwrap().stable_id_128());
+    /// ```
+    pub fn stable_id_128(&self) -> u128 {
+        let mut hasher = Fnv128::new();
+
+        self.write_stable_bytes(|bytes| hasher.write(bytes));
+
+        hasher.finish()
+    }
+
+    /// Feeds the bytes identifying the JSON pointer to the given stable hash function.
+    fn write_stable_bytes(&self, mut write: impl FnMut(&[u8])) {
+        for token in self.tokenize() {
+            write(token.as_bytes());
+            // `0xff` never appears in UTF-8 strings so it unambiguously delimits reference tokens.
+            write(&[0xff]);
+        }
+    }
+}
+
+impl FromStr for Pointer<'_> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         Self::new(s.to_owned())
     }
-}
+}
+
+impl<'a> TryFrom<&'a str> for Pointer<'a> {
+    type Error = Error;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for Pointer<'_> {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::new(s)
+    }
+}
+
+impl AsRef<str> for Pointer<'_> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Deref for Pointer<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+/// The default JSON pointer is the root JSON pointer.
+impl Default for Pointer<'_> {
+    fn default() -> Self {
+        Self::root()
+    }
+}
+
+impl Add<&Pointer<'_>> for Pointer<'_> {
+    type Output = Pointer<'static>;
+
+    fn add(self, other: &Pointer<'_>) -> Self::Output {
+        let mut s = self.0.into_owned();
+
+        s.push_str(&other.0);
+
+        Pointer(Cow::Owned(s))
+    }
+}
+
+impl Add<&Pointer<'_>> for &Pointer<'_> {
+    type Output = Pointer<'static>;
+
+    fn add(self, other: &Pointer<'_>) -> Self::Output {
+        self.concat(other)
+    }
+}
+
+impl Add<Pointer<'_>> for Pointer<'_> {
+    type Output = Pointer<'static>;
+
+    fn add(self, other: Pointer<'_>) -> Self::Output {
+        self + &other
+    }
+}
+
+/// Appends a raw JSON key, which is encoded as a reference token, see [`Pointer::push_token`].
+///
+/// # Examples
+/// ```
+/// # use json_toolkit::Pointer;
+///
+/// assert_eq!((Pointer::root() + "foo" + "bar/baz").as_str(), "/foo/bar~1baz");
+/// ```
+impl Add<&str> for Pointer<'_> {
+    type Output = Pointer<'static>;
+
+    fn add(self, token: &str) -> Self::Output {
+        let mut pointer = self.into_owned();
+
+        pointer.push_token(token);
+
+        pointer
+    }
+}
+
+impl Add<&str> for &Pointer<'_> {
+    type Output = Pointer<'static>;
+
+    fn add(self, token: &str) -> Self::Output {
+        self.join(token)
+    }
+}
+
+impl Ord for Pointer<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.depth().cmp(&other.depth()) {
+            #[cfg(feature = "unicode")]
+            Ordering::Equal => collate(&self.0, &other.0),
+            #[cfg(not(feature = "unicode"))]
+            Ordering::Equal => self.0.cmp(&other.0),
+            ordering => ordering,
+        }
+    }
+}
+
+impl PartialOrd for Pointer<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeSet, HashMap};
+
+    use super::*;
+
+    #[test]
+    fn it_accepts_valid_json_pointer() -> Result<(), Error> {
+        let tests = [
+            // point to root JSON value
+            "",
+            // point to an empty key in the root JSON value
+            "/",
+            "/path/to/object",
+            "/path/to/an/array/0/dummy",
+        ];
+
+        for s in tests {
+            let result = Pointer::new(s);
+
+            assert!(result.is_ok(), "'{}' is a valid JSON pointer", s);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_json_pointer_without_leading_backslash() {
+        let s = "path/without/leading/backslash";
+        let e = Pointer::new(s);
+
+        assert_eq!(e, Err(Error::MissingLeadingBackslash), "Invalid '{}' JSON pointer", s);
+    }
+
+    #[test]
+    fn it_accepts_valid_utf8_json_pointer() -> Result<(), Error> {
+        let tests = [
+            (b"".to_vec(), Pointer::root()),
+            (b"/a/b".to_vec(), Pointer::new("/a/b")?),
+            (
+                "/caf\u{e9}/\u{1f980}".as_bytes().to_vec(),
+                Pointer::new("/caf\u{e9}/\u{1f980}")?,
+            ),
+        ];
+
+        for (bytes, expected_pointer) in tests {
+            assert_eq!(Pointer::from_utf8(bytes), Ok(expected_pointer));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_invalid_utf8_json_pointer() {
+        let tests = [
+            (vec![b'/', 0xff], Error::InvalidUtf8),
+            (vec![b'/', 0xc3], Error::InvalidUtf8),
+            (b"missing/leading/backslash".to_vec(), Error::MissingLeadingBackslash),
+        ];
+
+        for (bytes, expected_error) in tests {
+            assert_eq!(Pointer::from_utf8(bytes), Err(expected_error));
+        }
+    }
+
+    #[test]
+    fn it_parses_comma_separated_json_pointers() -> Result<(), Error> {
+        let tests = [
+            ("", vec![]),
+            ("  ", vec![]),
+            ("/a/b", vec![Pointer::new("/a/b")?]),
+            (
+                " /a/b ,/c/d,  /e ",
+                vec![Pointer::new("/a/b")?, Pointer::new("/c/d")?, Pointer::new("/e")?],
+            ),
+            (
+                "/a,,/b",
+                vec![Pointer::new("/a")?, Pointer::root(), Pointer::new("/b")?],
+            ),
+        ];
+
+        for (s, expected_pointers) in tests {
+            assert_eq!(Pointer::parse_csv(s), Ok(expected_pointers), "Pointers of '{}'", s);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_comma_separated_json_pointers_with_invalid_entry() {
+        let tests = [("a/b", 0), ("/a/b, c/d, e", 1), ("/a,/b,/c,d", 3)];
+
+        for (s, expected_position) in tests {
+            assert_eq!(
+                Pointer::parse_csv(s),
+                Err(Error::InvalidListEntry {
+                    position: expected_position,
+                    source: Box::new(Error::MissingLeadingBackslash)
+                }),
+                "Pointers of '{}'",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn it_detects_root_json_pointer() -> Result<(), Error> {
+        let tests = [Pointer::new("")?, Pointer::root()];
+
+        for pointer in tests {
+            assert!(pointer.is_root(), "'{}' is a root JSON pointer", pointer);
+            assert!(!pointer.has_key(), "'{}' JSON pointer has no key", pointer);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_non_root_json_pointer() -> Result<(), Error> {
+        let tests = [
+            Pointer::new("/")?,
+            Pointer::new("/dummy_path/to/something")?,
+            Pointer::new("/0/1/2/3")?,
+        ];
+
+        for pointer in tests {
+            assert!(!pointer.is_root(), "'{}' is not a root JSON pointer", pointer);
+            assert!(pointer.has_key(), "'{}' JSON pointer has a key", pointer);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_parent_json_pointer() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), None),
+            (Pointer::new("/")?, Some(Pointer::root())),
+            (Pointer::new("/key")?, Some(Pointer::new("")?)),
+            (Pointer::new("/nested/key")?, Some(Pointer::new("/nested")?)),
+            (
+                Pointer::new("/deeper/nested/key")?,
+                Some(Pointer::new("/deeper/nested")?),
+            ),
+        ];
+
+        for (pointer, expected_parent_pointer) in tests {
+            assert_eq!(
+                pointer.parent(),
+                expected_parent_pointer,
+                "Parent of '{}' JSON pointer",
+                pointer,
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_key_from_json_pointer() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), None),
+            (Pointer::new("/")?, Some("")),
+            (Pointer::new("/key")?, Some("key")),
+            (Pointer::new("/nested/key")?, Some("key")),
+            (Pointer::new("/deeper/nested/key")?, Some("key")),
+            (Pointer::new("/with_encoded_char/~1key")?, Some("/key")),
+            (Pointer::new("/with_encoded_char/~0key")?, Some("~key")),
+            (Pointer::new("/with_encoded_char/~10key")?, Some("/0key")),
+            (Pointer::new("/with_encoded_char/~01key")?, Some("~1key")),
+        ];
+
+        for (pointer, expected_key) in tests {
+            let expected_key = expected_key.map(ToString::to_string);
+            assert_eq!(pointer.key(), expected_key, "Key of '{}' JSON pointer", pointer);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_token_at_index_from_json_pointer() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), vec![]),
+            (Pointer::new("/")?, vec![""]),
+            (Pointer::new("/key")?, vec!["key"]),
+            (Pointer::new("/a/b/c")?, vec!["a", "b", "c"]),
+            (Pointer::new("/~1a/~0b/c~01")?, vec!["/a", "~b", "c~1"]),
+            (Pointer::new("/a//0")?, vec!["a", "", "0"]),
+        ];
+
+        for (pointer, expected_tokens) in tests {
+            for index in 0..=expected_tokens.len() {
+                let expected_token = expected_tokens.get(index).map(ToString::to_string);
+
+                assert_eq!(
+                    pointer.token_at(index),
+                    expected_token,
+                    "Token at {} of '{}' JSON pointer",
+                    index,
+                    pointer
+                );
+            }
+
+            assert_eq!(pointer.first_token(), expected_tokens.first().map(ToString::to_string));
+            assert_eq!(pointer.token_at(usize::MAX), None);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_detects_parent_json_pointer() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), Pointer::new("/")?),
+            (Pointer::new("/")?, Pointer::new("//a")?),
+            (Pointer::new("/foo/0")?, Pointer::new("/foo/0/zoo")?),
+        ];
+
+        for (pointer_a, pointer_b) in tests {
+            assert!(
+                pointer_a.is_parent_of(&pointer_b),
+                "'{}' is the parent of '{}' JSON pointer",
+                pointer_a,
+                pointer_b
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_detects_non_parent_json_pointer() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), Pointer::root()),
+            (Pointer::new("/a/b")?, Pointer::new("/a")?),
+            (Pointer::new("/a/b")?, Pointer::new("/a/b")?),
+            (Pointer::new("/a/b")?, Pointer::new("/a/b/c/d")?),
+        ];
+
+        for (pointer_a, pointer_b) in tests {
+            assert!(
+                !pointer_a.is_parent_of(&pointer_b),
+                "'{}' is not the parent of '{}' JSON pointer",
+                pointer_a,
+                pointer_b,
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_detects_ancestor_json_pointer() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), Pointer::root()),
+            (Pointer::root(), Pointer::new("/")?),
+            (Pointer::new("/")?, Pointer::new("//a")?),
+            (Pointer::new("/a/b")?, Pointer::new("/a/b")?),
+            (Pointer::new("/a/b/c")?, Pointer::new("/a/b/c/d/e/f/g")?),
+            (Pointer::new("/foo/0")?, Pointer::new("/foo/0/bar/zoo")?),
+        ];
+
+        for (pointer_a, pointer_b) in tests {
+            assert!(
+                pointer_a.is_ancestor_of(&pointer_b),
+                "'{}' is an ancestor of '{}' JSON pointer",
+                pointer_a,
+                pointer_b
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_detects_non_ancestor_json_pointer() -> Result<(), Error> {
+        let tests = [
+            (Pointer::new("/a/b")?, Pointer::new("/a")?),
+            (Pointer::new("/0/foo/bar/zoo")?, Pointer::new("/1/foo/bar/zoo")?),
+            (Pointer::new("/tric")?, Pointer::new("/tricky/test")?),
+        ];
+
+        for (pointer_a, pointer_b) in tests {
+            assert!(
+                !pointer_a.is_ancestor_of(&pointer_b),
+                "'{}' is not an ancestor of '{}' JSON pointer",
+                pointer_a,
+                pointer_b,
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_detects_sibling_json_pointer() -> Result<(), Error> {
+        let tests = [
+            (Pointer::new("/")?, Pointer::new("/a")?),
+            (Pointer::new("/a")?, Pointer::new("/")?),
+            (Pointer::new("/a/b/c")?, Pointer::new("/a/b/d")?),
+            (Pointer::new("/foo/bar/zoo/0")?, Pointer::new("/foo/bar/zoo/42")?),
+        ];
+
+        for (pointer_a, pointer_b) in tests {
+            assert!(
+                pointer_a.is_sibling_of(&pointer_b),
+                "'{}' is a sibling of '{}' JSON pointer",
+                pointer_a,
+                pointer_b
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_detects_non_sibling_json_pointer() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), Pointer::root()),
+            (Pointer::new("/b/d")?, Pointer::new("/b/d")?),
+            (Pointer::new("/b/d")?, Pointer::new("/a")?),
+            (Pointer::new("/a")?, Pointer::new("/b/d")?),
+            (Pointer::new("/a/b/c")?, Pointer::new("/d/e/f")?),
+            (Pointer::new("/0/foo/bar/zoo")?, Pointer::new("/1/foo/bar/zoo")?),
+        ];
+
+        for (pointer_a, pointer_b) in tests {
+            assert!(
+                !pointer_a.is_sibling_of(&pointer_b),
+                "'{}' is not a sibling of '{}' JSON pointer",
+                pointer_a,
+                pointer_b
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_ancestor_json_pointers() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), vec![Pointer::root()]),
+            (Pointer::new("/")?, vec![Pointer::new("/")?, Pointer::root()]),
+            (
+                Pointer::new("/a/b")?,
+                vec![Pointer::new("/a/b")?, Pointer::new("/a")?, Pointer::root()],
+            ),
+            (
+                Pointer::new("/0/foo/bar/zoo")?,
+                vec![
+                    Pointer::new("/0/foo/bar/zoo")?,
+                    Pointer::new("/0/foo/bar")?,
+                    Pointer::new("/0/foo")?,
+                    Pointer::new("/0")?,
+                    Pointer::root(),
+                ],
+            ),
+        ];
+
+        for (pointer, expected_ancestor_pointers) in tests {
+            let ancestor_pointers = pointer.ancestors().collect::<Vec<_>>();
+
+            assert_eq!(
+                ancestor_pointers, expected_ancestor_pointers,
+                "Ancestors of '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_detects_equal_json_pointers_ignoring_depths() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), Pointer::root(), vec![]),
+            (Pointer::new("/a/b")?, Pointer::new("/a/b")?, vec![]),
+            (Pointer::new("/a/wrapper/b")?, Pointer::new("/a/inner/b")?, vec![1]),
+            (Pointer::new("/a/b/wrapper")?, Pointer::new("/a/b")?, vec![2]),
+            (Pointer::new("/wrapper/a")?, Pointer::new("/inner/a")?, vec![0, 5]),
+            (Pointer::new("/a~1b/0")?, Pointer::new("/a~1b/1")?, vec![1]),
+        ];
+
+        for (pointer_a, pointer_b, ignore) in tests {
+            assert!(
+                pointer_a.eq_ignoring_depths(&pointer_b, &ignore),
+                "'{}' and '{}' JSON pointers are equal ignoring {:?} depths",
+                pointer_a,
+                pointer_b,
+                ignore
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_detects_non_equal_json_pointers_ignoring_depths() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), Pointer::new("/")?, vec![]),
+            (Pointer::new("/a/wrapper/b")?, Pointer::new("/a/inner/b")?, vec![0]),
+            (Pointer::new("/wrapper/a/b")?, Pointer::new("/a/b")?, vec![0]),
+            (Pointer::new("/a/b/c")?, Pointer::new("/a/b")?, vec![]),
+        ];
+
+        for (pointer_a, pointer_b, ignore) in tests {
+            assert!(
+                !pointer_a.eq_ignoring_depths(&pointer_b, &ignore),
+                "'{}' and '{}' JSON pointers are not equal ignoring {:?} depths",
+                pointer_a,
+                pointer_b,
+                ignore
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_json_pointer_depth() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), 0),
+            (Pointer::new("/")?, 1),
+            (Pointer::new("/a")?, 1),
+            (Pointer::new("/a/b/c")?, 3),
+            (Pointer::new("/foo/0/bar/1/zoo/2")?, 6),
+        ];
+
+        for (pointer, expected_depth) in tests {
+            assert_eq!(pointer.depth(), expected_depth, "Depth of '{}' JSON pointer", pointer);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_converts_owned_json_pointer_into_owned_without_allocation() -> Result<(), Error> {
+        let pointer = Pointer::new(String::from("/foo/bar"))?;
+        let (ptr, len) = (pointer.as_str().as_ptr(), pointer.as_str().len());
+
+        let pointer = pointer.into_owned();
+
+        assert!(matches!(pointer.0, Cow::Owned(_)));
+        assert_eq!(
+            pointer.as_str().as_ptr(),
+            ptr,
+            "'{}' JSON pointer is not reallocated",
+            pointer
+        );
+        assert_eq!(pointer.as_str().len(), len);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_converts_borrowed_json_pointer_into_owned() -> Result<(), Error> {
+        let s = "/foo/bar";
+        let pointer = Pointer::new(s)?.into_owned();
+
+        assert!(matches!(pointer.0, Cow::Owned(_)));
+        assert_ne!(pointer.as_str().as_ptr(), s.as_ptr());
+        assert_eq!(pointer.as_str(), s);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_creates_child_json_pointer_from_allowed_key() -> Result<(), Error> {
+        let allowed = ["host", "a/b", ""];
+        let tests = [
+            (Pointer::root(), "host", Pointer::new("/host")?),
+            (Pointer::new("/server")?, "a/b", Pointer::new("/server/a~1b")?),
+            (Pointer::new("/server")?, "", Pointer::new("/server/")?),
+        ];
+
+        for (pointer, key, expected_pointer) in tests {
+            assert_eq!(pointer.child_checked(key, &allowed), Ok(expected_pointer));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_child_json_pointer_from_unknown_key() -> Result<(), Error> {
+        let pointer = Pointer::new("/server")?;
+        let tests = [("hots", &["host", "port"][..]), ("host", &[][..])];
+
+        for (key, allowed) in tests {
+            assert_eq!(
+                pointer.child_checked(key, allowed),
+                Err(Error::UnknownKey(key.to_string()))
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_concatenates_json_pointers() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), Pointer::root(), Pointer::root()),
+            (Pointer::root(), Pointer::new("/a")?, Pointer::new("/a")?),
+            (Pointer::new("/a")?, Pointer::root(), Pointer::new("/a")?),
+            (Pointer::new("/")?, Pointer::new("/")?, Pointer::new("//")?),
+            (
+                Pointer::new("/a~1b")?,
+                Pointer::new("/c/0")?,
+                Pointer::new("/a~1b/c/0")?,
+            ),
+        ];
+
+        for (pointer_a, pointer_b, expected_pointer) in tests {
+            assert_eq!(pointer_a.concat(&pointer_b), expected_pointer);
+            assert_eq!(&pointer_a + &pointer_b, expected_pointer);
+            assert_eq!(pointer_a.clone() + &pointer_b, expected_pointer);
+            assert_eq!(pointer_a + pointer_b, expected_pointer);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_appends_raw_json_keys_with_operator() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), "foo", Pointer::new("/foo")?),
+            (Pointer::root(), "", Pointer::new("/")?),
+            (Pointer::new("/a")?, "b/c", Pointer::new("/a/b~1c")?),
+            (Pointer::new("/a")?, "~b", Pointer::new("/a/~0b")?),
+            (Pointer::new("/a/0")?, "1", Pointer::new("/a/0/1")?),
+        ];
+
+        for (pointer, token, expected_pointer) in tests {
+            assert_eq!(&pointer + token, expected_pointer);
+            assert_eq!(pointer + token, expected_pointer);
+        }
+
+        assert_eq!(Pointer::root() + "foo" + "bar", Pointer::new("/foo/bar")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_strips_json_pointer_prefix() -> Result<(), Error> {
+        let tests = [
+            ("/a/b/c", "/a", Some("/b/c")),
+            ("/a/b/c", "/a/b/c", Some("")),
+            ("/a/b/c", "", Some("/a/b/c")),
+            ("", "", Some("")),
+            ("/a~1b/c", "/a~1b", Some("/c")),
+            ("/a//", "/a/", Some("/")),
+            ("/ab/c", "/a", None),
+            ("/a/b/c", "/b", None),
+            ("/a", "/a/b", None),
+            ("", "/a", None),
+        ];
+
+        for (s, base, expected_pointer) in tests {
+            let pointer = Pointer::new(s)?;
+            let expected_pointer = expected_pointer.map(Pointer::new).transpose()?;
+
+            assert_eq!(
+                pointer.strip_prefix(&Pointer::new(base)?),
+                expected_pointer,
+                "Relative JSON pointer of '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_common_ancestor_json_pointer() -> Result<(), Error> {
+        let tests = [
+            ("/a/b/c", "/a/b/d", "/a/b"),
+            ("/a/b/c", "/a/b/c", "/a/b/c"),
+            ("/a/b/c", "/a", "/a"),
+            ("/a", "/a/b/c", "/a"),
+            ("/a/b", "/a/c/b", "/a"),
+            ("/a", "/x", ""),
+            ("", "/a", ""),
+            ("/a", "", ""),
+            ("", "", ""),
+            ("/a~1b/c", "/a~1b/d", "/a~1b"),
+            ("/a~1b", "/a/b", ""),
+            ("/a~0/b", "/a~1/b", ""),
+            ("/a//b", "/a//c", "/a/"),
+            ("/ab", "/a", ""),
+        ];
+
+        for (s, other, expected_pointer) in tests {
+            let pointer = Pointer::new(s)?;
+            let other = Pointer::new(other)?;
+
+            assert_eq!(
+                pointer.common_ancestor(&other),
+                Pointer::new(expected_pointer)?,
+                "Common ancestor of '{}' and '{}' JSON pointers",
+                pointer,
+                other
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_longest_prefix_match() -> Result<(), Error> {
+        let table = [
+            (Pointer::new("/a")?, 1),
+            (Pointer::new("/a/b")?, 2),
+            (Pointer::new("/a/b")?, 3),
+            (Pointer::new("/x/y/z")?, 4),
+            (Pointer::new("/a~1b")?, 5),
+        ];
+
+        let tests = [
+            ("/a/b/c", Some(2)),
+            ("/a/b", Some(2)),
+            ("/a/bc", Some(1)),
+            ("/a", Some(1)),
+            ("/a~1b/c", Some(5)),
+            ("/x/y", None),
+            ("/b", None),
+            ("", None),
+        ];
+
+        for (s, expected_value) in tests {
+            let query = Pointer::new(s)?;
+
+            assert_eq!(
+                Pointer::longest_prefix_match(&table, &query),
+                expected_value.as_ref(),
+                "Longest prefix match of '{}' JSON pointer",
+                query
+            );
+        }
+
+        let table = [(Pointer::root(), 0)];
+        assert_eq!(Pointer::longest_prefix_match(&table, &Pointer::new("/b")?), Some(&0));
+        assert_eq!(Pointer::longest_prefix_match::<()>(&[], &Pointer::root()), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_evaluates_json_pointer_into_tokens() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), vec![]),
+            (Pointer::new("/")?, vec![""]),
+            (Pointer::new("/~1a")?, vec!["/a"]),
+            (Pointer::new("/~01a")?, vec!["~1a"]),
+            (Pointer::new("/~10a")?, vec!["/0a"]),
+            (Pointer::new("/~1a/~0b/c")?, vec!["/a", "~b", "c"]),
+        ];
+
+        for (pointer, expected_tokens) in tests {
+            let tokens = pointer.tokenize().collect::<Vec<_>>();
+            let tokens = tokens.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+
+            assert_eq!(tokens, expected_tokens, "Tokens of '{}' JSON pointer", pointer);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_encodes_json_pointer_into_uri_fragment() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), "#"),
+            (Pointer::new("/")?, "#/"),
+            (Pointer::new("/foo/0")?, "#/foo/0"),
+            (Pointer::new("/a~1b/m~0n")?, "#/a~1b/m~0n"),
+            (
+                Pointer::new("/c%d/e^f/g|h/i\\j/k\"l/ ")?,
+                "#/c%25d/e%5Ef/g%7Ch/i%5Cj/k%22l/%20",
+            ),
+            (Pointer::new("/caf\u{e9}")?, "#/caf%C3%A9"),
+        ];
+
+        for (pointer, expected_fragment) in tests {
+            assert_eq!(
+                pointer.to_uri_fragment(),
+                expected_fragment,
+                "URI fragment of '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        Ok(())
+    }
 
-impl<'a> TryFrom<&'a str> for Pointer<'a> {
-    type Error = Error;
+    #[test]
+    fn it_displays_cached_uri_fragment() -> Result<(), Error> {
+        let tests = [
+            Pointer::root(),
+            Pointer::new("/a b/c%d")?,
+            Pointer::new("/caf\u{e9}/0")?,
+        ];
 
-    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
-        Self::new(s)
+        for pointer in tests {
+            let fragment = pointer.to_fragment_cached();
+
+            assert_eq!(fragment.to_string(), pointer.to_uri_fragment());
+            assert_eq!(fragment.as_str(), pointer.to_uri_fragment());
+        }
+
+        Ok(())
     }
-}
 
-impl TryFrom<String> for Pointer<'_> {
-    type Error = Error;
+    #[test]
+    fn it_computes_same_stable_id_for_equal_json_pointers() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), Pointer::new("")?),
+            (Pointer::new("/a/b")?, Pointer::new(String::from("/a/b"))?),
+            // both pointers decode into the same `~2` reference token
+            (Pointer::new("/~2")?, Pointer::new("/~02")?),
+        ];
 
-    fn try_from(s: String) -> Result<Self, Self::Error> {
-        Self::new(s)
+        for (pointer_a, pointer_b) in tests {
+            assert_eq!(
+                pointer_a.stable_id(),
+                pointer_b.stable_id(),
+                "'{}' and '{}' JSON pointers have the same stable ID",
+                pointer_a,
+                pointer_b
+            );
+            assert_eq!(
+                pointer_a.stable_id_128(),
+                pointer_b.stable_id_128(),
+                "'{}' and '{}' JSON pointers have the same 128-bit stable ID",
+                pointer_a,
+                pointer_b
+            );
+        }
+
+        Ok(())
     }
-}
 
-impl AsRef<str> for Pointer<'_> {
-    fn as_ref(&self) -> &str {
-        self.as_str()
+    #[test]
+    fn it_computes_distinct_stable_id_for_distinct_json_pointers() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), Pointer::new("/")?),
+            (Pointer::new("/ab")?, Pointer::new("/a/b")?),
+            (Pointer::new("/a~1b")?, Pointer::new("/a/b")?),
+            (Pointer::new("/0/foo")?, Pointer::new("/foo/0")?),
+        ];
+
+        for (pointer_a, pointer_b) in tests {
+            assert_ne!(
+                pointer_a.stable_id(),
+                pointer_b.stable_id(),
+                "'{}' and '{}' JSON pointers have distinct stable IDs",
+                pointer_a,
+                pointer_b
+            );
+            assert_ne!(
+                pointer_a.stable_id_128(),
+                pointer_b.stable_id_128(),
+                "'{}' and '{}' JSON pointers have distinct 128-bit stable IDs",
+                pointer_a,
+                pointer_b
+            );
+        }
+
+        Ok(())
     }
-}
 
-impl Ord for Pointer<'_> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        match self.depth().cmp(&other.depth()) {
-            Ordering::Equal => self.0.cmp(&other.0),
-            ordering => ordering,
+    #[test]
+    fn it_computes_deterministic_stable_id() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), 0xcbf2_9ce4_8422_2325),
+            (Pointer::new("/foo/bar")?, 0x3fff_2c2d_7595_e046),
+        ];
+
+        for (pointer, expected_id) in tests {
+            assert_eq!(
+                pointer.stable_id(),
+                expected_id,
+                "Stable ID of '{}' JSON pointer",
+                pointer
+            );
         }
+
+        Ok(())
     }
-}
 
-impl PartialOrd for Pointer<'_> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    #[test]
+    fn it_computes_deterministic_stable_id_128() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d),
+            (Pointer::new("/foo/bar")?, 0x73c8_f8a2_0665_9b4a_56e7_ac04_7f26_997e),
+        ];
+
+        for (pointer, expected_id) in tests {
+            assert_eq!(
+                pointer.stable_id_128(),
+                expected_id,
+                "128-bit stable ID of '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn it_computes_distinct_stable_id_128_for_sample_json_pointers() {
+        let mut ids = (0..1000)
+            .flat_map(|i| {
+                [
+                    Pointer::root().with_index(i),
+                    Pointer::root().join(&format!("key{}", i)).with_index(i % 10),
+                ]
+            })
+            .map(|pointer| pointer.stable_id_128())
+            .collect::<Vec<_>>();
+        let len = ids.len();
+
+        ids.sort_unstable();
+        ids.dedup();
+
+        assert_eq!(ids.len(), len);
+    }
 
     #[test]
-    fn it_accepts_valid_json_pointer() -> Result<(), Error> {
+    fn it_converts_jsonpath_expression() -> Result<(), Error> {
+        let tests = [
+            ("$", ""),
+            ("$.a.b[0]", "/a/b/0"),
+            ("$['a/b'][\"~c\"][12]", "/a~1b/~0c/12"),
+            ("$.store.book[0]['it\\'s']", "/store/book/0/it's"),
+        ];
+
+        for (expr, expected_pointer) in tests {
+            let pointer = Pointer::try_from_jsonpath(expr)?;
+
+            assert_eq!(
+                pointer.as_str(),
+                expected_pointer,
+                "JSON pointer of '{}' JSONPath expression",
+                expr
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_unsupported_jsonpath_expression() {
+        let tests = [
+            "a.b",
+            "$.a[?(@.price < 10)]",
+            "$.a.*",
+            "$.a[*]",
+            "$..a",
+            "$.a[0:2]",
+            "$.a[0,1]",
+            "$.a['b'",
+        ];
+
+        for expr in tests {
+            assert_eq!(
+                Pointer::try_from_jsonpath(expr),
+                Err(Error::UnsupportedJsonPath(expr.to_string())),
+                "'{}' is an unsupported JSONPath expression",
+                expr
+            );
+        }
+    }
+
+    #[test]
+    fn it_round_trips_unusual_tokens() {
+        let tests = [
+            vec!["\0"],
+            vec!["a\0b", "\0"],
+            vec!["line\nbreak", "tab\tulation", "\r\n"],
+            vec!["\u{1}\u{1f}\u{7f}"],
+            vec!["🦀", "emoji 🎉/~"],
+            vec!["\0~1", "~0\n/"],
+        ];
+
+        for tokens in tests {
+            let pointer = Pointer::from_tokens(&tokens);
+
+            assert!(
+                Pointer::new(pointer.as_str()).is_ok(),
+                "'{:?}' JSON pointer is valid",
+                pointer
+            );
+            assert_eq!(
+                pointer.tokenize().collect::<Vec<_>>(),
+                tokens,
+                "Tokens of '{:?}' JSON pointer",
+                pointer
+            );
+        }
+    }
+
+    #[test]
+    fn it_renders_graphql_path() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), ""),
+            (Pointer::new("/a")?, "a"),
+            (Pointer::new("/a/b/0/c")?, "a.b.0.c"),
+            (Pointer::new("/0/1")?, "0.1"),
+            (Pointer::new("/a~1b/~0c")?, "a/b.~c"),
+        ];
+
+        for (pointer, expected_path) in tests {
+            assert_eq!(
+                pointer.to_graphql_path(),
+                expected_path,
+                "GraphQL path of '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_converts_json_pointer_to_and_from_mongo_dot_notation() -> Result<(), Error> {
+        let tests = [
+            ("", ""),
+            ("/a", "a"),
+            ("/a/b/0/c", "a.b.0.c"),
+            ("/a~1b/~0c", "a/b.~c"),
+            ("/$set/a", "$set.a"),
+        ];
+
+        for (s, expected_path) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                pointer.to_mongo().as_deref(),
+                Ok(expected_path),
+                "MongoDB path of '{}' JSON pointer",
+                pointer
+            );
+            assert_eq!(
+                Pointer::from_mongo(expected_path),
+                Ok(pointer),
+                "JSON pointer of '{}' MongoDB path",
+                expected_path
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_unsupported_mongo_dot_notation_conversion() -> Result<(), Error> {
+        for s in ["/a.b", "/a/b.c/d", "/", "/a//b"] {
+            assert_eq!(
+                Pointer::new(s)?.to_mongo(),
+                Err(Error::UnsupportedConversion(s.to_string())),
+                "'{}' JSON pointer has no MongoDB path",
+                s
+            );
+        }
+
+        for s in [".", "a.", ".a", "a..b"] {
+            assert_eq!(
+                Pointer::from_mongo(s),
+                Err(Error::UnsupportedConversion(s.to_string())),
+                "'{}' is not a MongoDB path",
+                s
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_borrows_json_key_if_possible() -> Result<(), Error> {
+        let tests = [
+            (Pointer::new("/foo")?, "foo", true),
+            (Pointer::new("/")?, "", true),
+            (Pointer::new("/foo/bar baz")?, "bar baz", true),
+            (Pointer::new("/foo/a~1b")?, "a/b", false),
+            (Pointer::new("/~0foo")?, "~foo", false),
+        ];
+
+        for (pointer, expected_key, is_borrowed) in tests {
+            let key = pointer.key_cow().ok_or(Error::UnexpectedRootPointer)?;
+
+            assert_eq!(key, expected_key, "Key of '{}' JSON pointer", pointer);
+            assert_eq!(
+                matches!(key, Cow::Borrowed(_)),
+                is_borrowed,
+                "Borrowed key of '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        assert_eq!(Pointer::root().key_cow(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_appends_json_array_index() -> Result<(), Error> {
+        let tests = [
+            (Pointer::root(), 0, "/0"),
+            (Pointer::new("/")?, 7, "//7"),
+            (Pointer::new("/items")?, 42, "/items/42"),
+            (Pointer::new("/a/b")?, 1_000_000, "/a/b/1000000"),
+        ];
+
+        for (pointer, index, expected_pointer) in tests {
+            assert_eq!(
+                pointer.with_index(index).as_str(),
+                expected_pointer,
+                "'{}' JSON pointer with '{}' index",
+                pointer,
+                index
+            );
+            assert_eq!(
+                pointer.with_index(index),
+                pointer.join(&index.to_string()),
+                "'{}' JSON pointer with '{}' index",
+                pointer,
+                index
+            );
+        }
+
+        let mut pointer = Pointer::new("/items")?;
+        pointer.push_index(1);
+        pointer.push_index(23);
+
+        assert_eq!(pointer.as_str(), "/items/1/23");
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_strictly_validates_escape_sequences() {
+        let tests = [
+            ("", Ok(())),
+            ("/a~0b/~1c", Ok(())),
+            ("/é~0/ü~1", Ok(())),
+            ("/~01~10", Ok(())),
+            ("/a~2b", Err(Error::InvalidEscapeSequence)),
+            ("/a~", Err(Error::InvalidEscapeSequence)),
+            ("/~~0", Err(Error::InvalidEscapeSequence)),
+            ("/é~2", Err(Error::InvalidEscapeSequence)),
+            ("/é/~", Err(Error::InvalidEscapeSequence)),
+            ("/~é", Err(Error::InvalidEscapeSequence)),
+            ("a~0", Err(Error::MissingLeadingBackslash)),
+        ];
+
+        for (s, expected_result) in tests {
+            assert_eq!(
+                Pointer::new_strict(s).map(|_| ()),
+                expected_result,
+                "Strict validation of '{}' JSON pointer",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn it_laxly_accepts_ill_formed_escape_sequences() -> Result<(), Error> {
+        let tests = [
+            ("/a~2b", vec!["a~2b"]),
+            ("/a~", vec!["a~"]),
+            ("/~~0/~é", vec!["~~", "~é"]),
+        ];
+
+        for (s, expected_tokens) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                pointer.tokenize().collect::<Vec<_>>(),
+                expected_tokens,
+                "Lax decoding of '{}' JSON pointer",
+                s
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_decodes_uri_fragment() -> Result<(), Error> {
+        let tests = [
+            ("#", ""),
+            ("#/a~1b/c~0d", "/a~1b/c~0d"),
+            ("#/a%20b/c%25d", "/a b/c%d"),
+            ("#/%C3%A9t%C3%A9", "/été"),
+            ("#/%e2%82%ac", "/€"),
+            ("#/%F0%9F%A6%80", "/🦀"),
+        ];
+
+        for (fragment, expected_pointer) in tests {
+            let pointer = Pointer::from_uri_fragment(fragment)?;
+
+            assert_eq!(
+                pointer.as_str(),
+                expected_pointer,
+                "JSON pointer of '{}' URI fragment",
+                fragment
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_malformed_uri_fragment() {
+        let tests = [
+            ("/a", Error::InvalidUriFragment),
+            ("#/a%2", Error::InvalidUriFragment),
+            ("#/a%zz", Error::InvalidUriFragment),
+            ("#a", Error::MissingLeadingBackslash),
+            // overlong encodings of '/' and NUL
+            ("#/%C0%AF", Error::InvalidUtf8),
+            ("#/%E0%80%AF", Error::InvalidUtf8),
+            ("#/%C0%80", Error::InvalidUtf8),
+            // invalid continuation bytes
+            ("#/%C3%28", Error::InvalidUtf8),
+            ("#/%E2%82", Error::InvalidUtf8),
+            ("#/%80", Error::InvalidUtf8),
+            // UTF-16 surrogate
+            ("#/%ED%A0%80", Error::InvalidUtf8),
+        ];
+
+        for (fragment, expected_error) in tests {
+            assert_eq!(
+                Pointer::from_uri_fragment(fragment),
+                Err(expected_error),
+                "'{}' is a malformed URI fragment",
+                fragment
+            );
+        }
+    }
+
+    #[test]
+    fn it_round_trips_uri_fragment() -> Result<(), Error> {
         let tests = [
-            // point to root JSON value
             "",
-            // point to an empty key in the root JSON value
             "/",
-            "/path/to/object",
-            "/path/to/an/array/0/dummy",
+            "/foo/0",
+            "/a~1b/m~0n",
+            "/c%d/e^f/g|h/i\\j/k\"l/ ",
+            "/#/?/&/=/+/[]/{}/<>/`",
+            "/%25/%/%%",
+            "/caf\u{e9}/\u{1f980}/\u{0}\u{7f}",
         ];
 
         for s in tests {
-            let result = Pointer::new(s);
+            let pointer = Pointer::new(s)?;
+            let fragment = pointer.to_uri_fragment();
+
+            assert_eq!(
+                Pointer::from_uri_fragment(&fragment),
+                Ok(pointer.clone()),
+                "Round trip of '{}' JSON pointer through '{}' URI fragment",
+                pointer,
+                fragment
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_normalizes_json_pointers_in_place() -> Result<(), Error> {
+        let mut pointers = [
+            Pointer::root(),
+            Pointer::new("/a/b")?,
+            Pointer::new("/a~1b/~0c")?,
+            Pointer::new("/a~2b")?,
+            Pointer::new(String::from("/~"))?,
+            Pointer::new("/~~1/c")?,
+        ];
+        let expected_pointers = [
+            Pointer::root(),
+            Pointer::new("/a/b")?,
+            Pointer::new("/a~1b/~0c")?,
+            Pointer::new("/a~02b")?,
+            Pointer::new("/~0")?,
+            Pointer::new("/~0~1/c")?,
+        ];
+
+        Pointer::normalize_all(&mut pointers);
 
-            assert!(result.is_ok(), "'{}' is a valid JSON pointer", s);
+        assert_eq!(pointers, expected_pointers);
+
+        for pointer in &pointers {
+            assert_eq!(
+                *pointer,
+                pointer.normalized(),
+                "'{}' JSON pointer is normalized",
+                pointer
+            );
         }
 
         Ok(())
     }
 
     #[test]
-    fn it_rejects_json_pointer_without_leading_backslash() {
-        let s = "path/without/leading/backslash";
-        let e = Pointer::new(s);
+    fn it_creates_json_pointer_in_const_context() -> Result<(), Error> {
+        const ROOT: Pointer<'static> = Pointer::from_static("");
+        const POINTER: Pointer<'static> = Pointer::from_static("/a~1b/c");
 
-        assert_eq!(e, Err(Error::MissingLeadingBackslash), "Invalid '{}' JSON pointer", s);
+        assert_eq!(ROOT, Pointer::root());
+        assert_eq!(POINTER, Pointer::new("/a~1b/c")?);
+
+        Ok(())
     }
 
     #[test]
-    fn it_detects_root_json_pointer() -> Result<(), Error> {
-        let tests = [Pointer::new("")?, Pointer::root()];
+    #[should_panic]
+    fn it_panics_on_static_json_pointer_without_leading_backslash() {
+        Pointer::from_static("a/b");
+    }
 
-        for pointer in tests {
-            assert!(pointer.is_root(), "'{}' is a root JSON pointer", pointer);
+    #[test]
+    #[should_panic]
+    fn it_panics_on_static_json_pointer_with_invalid_escape_sequence() {
+        Pointer::from_static("/a~2b");
+    }
+
+    #[test]
+    fn it_creates_json_pointer_from_checked_literal() -> Result<(), Error> {
+        assert_eq!(crate::pointer!(""), Pointer::root());
+        assert_eq!(crate::pointer!("/"), Pointer::new("/")?);
+        assert_eq!(crate::pointer!("/a/b"), Pointer::new("/a/b")?);
+        assert_eq!(crate::pointer!("/a~0b/~1c"), Pointer::new("/a~0b/~1c")?);
+        assert_eq!(crate::pointer!("/€~1"), Pointer::new("/€~1")?);
+
+        for s in ["", "/", "/a/b", "/a~0b/~1c", "/€~1"] {
+            assert!(has_valid_escape_sequences(s), "'{}' has valid escape sequences", s);
+        }
+
+        for s in ["~", "/a~", "/a~2b", "/~~0"] {
+            assert!(!has_valid_escape_sequences(s), "'{}' has invalid escape sequences", s);
         }
 
         Ok(())
     }
 
     #[test]
-    fn it_rejects_non_root_json_pointer() -> Result<(), Error> {
-        let tests = [
+    fn it_writes_json_pointers_into_single_buffer() -> Result<(), Box<dyn std::error::Error>> {
+        let pointers = [
+            Pointer::new("/a/b")?,
+            Pointer::root(),
+            Pointer::new("/~0c/d~1e")?,
             Pointer::new("/")?,
-            Pointer::new("/dummy_path/to/something")?,
-            Pointer::new("/0/1/2/3")?,
         ];
+        let mut s = String::new();
 
-        for pointer in tests {
-            assert!(!pointer.is_root(), "'{}' is not a root JSON pointer", pointer);
+        for pointer in &pointers {
+            pointer.write_to(&mut s)?;
+            s.push('\n');
         }
 
+        assert_eq!(s, "/a/b\n\n/~0c/d~1e\n/\n");
+
         Ok(())
     }
 
     #[test]
-    fn it_gets_parent_json_pointer() -> Result<(), Error> {
+    fn it_maps_json_pointers() -> Result<(), Error> {
+        let mapping = [
+            (Pointer::new("/a/b")?, Pointer::new("/x/y")?),
+            (Pointer::new("/a")?, Pointer::root()),
+            (Pointer::new("/c~1d")?, Pointer::new("/e")?),
+        ];
+
         let tests = [
+            (Pointer::new("/a/b/c")?, Some(Pointer::new("/x/y/c")?)),
+            (Pointer::new("/a/b")?, Some(Pointer::new("/x/y")?)),
+            (Pointer::new("/a/bc")?, Some(Pointer::new("/bc")?)),
+            (Pointer::new("/a/c/~0d")?, Some(Pointer::new("/c/~0d")?)),
+            (Pointer::new("/c~1d/0")?, Some(Pointer::new("/e/0")?)),
+            (Pointer::new("/b/c")?, None),
             (Pointer::root(), None),
-            (Pointer::new("/")?, Some(Pointer::root())),
-            (Pointer::new("/key")?, Some(Pointer::new("")?)),
-            (Pointer::new("/nested/key")?, Some(Pointer::new("/nested")?)),
-            (
-                Pointer::new("/deeper/nested/key")?,
-                Some(Pointer::new("/deeper/nested")?),
-            ),
         ];
 
-        for (pointer, expected_parent_pointer) in tests {
+        for (pointer, expected_pointer) in tests {
             assert_eq!(
-                pointer.parent(),
-                expected_parent_pointer,
-                "Parent of '{}' JSON pointer",
-                pointer,
+                pointer.map_pointer(&mapping),
+                expected_pointer,
+                "Mapping of '{}' JSON pointer",
+                pointer
             );
         }
 
@@ -318,62 +2961,95 @@ mod tests {
     }
 
     #[test]
-    fn it_gets_key_from_json_pointer() -> Result<(), Error> {
+    fn it_parses_environment_variable_name() -> Result<(), Error> {
         let tests = [
-            (Pointer::root(), None),
-            (Pointer::new("/")?, Some("")),
-            (Pointer::new("/key")?, Some("key")),
-            (Pointer::new("/nested/key")?, Some("key")),
-            (Pointer::new("/deeper/nested/key")?, Some("key")),
-            (Pointer::new("/with_encoded_char/~1key")?, Some("/key")),
-            (Pointer::new("/with_encoded_char/~0key")?, Some("~key")),
-            (Pointer::new("/with_encoded_char/~10key")?, Some("/0key")),
-            (Pointer::new("/with_encoded_char/~01key")?, Some("~1key")),
+            ("APP__server__port", "APP", "__", Some(Pointer::new("/server/port")?)),
+            (
+                "APP__max_connections",
+                "APP",
+                "__",
+                Some(Pointer::new("/max_connections")?),
+            ),
+            ("APP_DB_HOST", "APP", "_", Some(Pointer::new("/DB/HOST")?)),
+            ("APP__a/b__0", "APP", "__", Some(Pointer::new("/a~1b/0")?)),
+            ("APP", "APP", "__", Some(Pointer::root())),
+            ("APPLE__server", "APP", "__", None),
+            ("OTHER__server", "APP", "__", None),
+            ("APP_server", "APP", "__", None),
+            ("APP__server", "APP", "", None),
         ];
 
-        for (pointer, expected_key) in tests {
-            let expected_key = expected_key.map(ToString::to_string);
-            assert_eq!(pointer.key(), expected_key, "Key of '{}' JSON pointer", pointer);
+        for (name, prefix, separator, expected_pointer) in tests {
+            assert_eq!(
+                Pointer::from_env_name(name, prefix, separator),
+                expected_pointer,
+                "JSON pointer of '{}' environment variable",
+                name
+            );
         }
 
         Ok(())
     }
 
     #[test]
-    fn it_detects_parent_json_pointer() -> Result<(), Error> {
+    fn it_iterates_over_token_spans() -> Result<(), Error> {
         let tests = [
-            (Pointer::root(), Pointer::new("/")?),
-            (Pointer::new("/")?, Pointer::new("//a")?),
-            (Pointer::new("/foo/0")?, Pointer::new("/foo/0/zoo")?),
+            (Pointer::root(), vec![]),
+            (Pointer::new("/")?, vec![(1..1, "")]),
+            (Pointer::new("/a/~1b/c")?, vec![(1..2, "a"), (3..6, "~1b"), (7..8, "c")]),
+            (Pointer::new("//é/")?, vec![(1..1, ""), (2..4, "é"), (5..5, "")]),
         ];
 
-        for (pointer_a, pointer_b) in tests {
-            assert!(
-                pointer_a.is_parent_of(&pointer_b),
-                "'{}' is the parent of '{}' JSON pointer",
-                pointer_a,
-                pointer_b
-            );
+        for (pointer, expected_spans) in tests {
+            let spans = pointer.token_spans().collect::<Vec<_>>();
+
+            assert_eq!(spans, expected_spans, "Token spans of '{}' JSON pointer", pointer);
+
+            for (range, token) in spans {
+                assert_eq!(
+                    &pointer.as_str()[range],
+                    token,
+                    "Token spans of '{}' JSON pointer",
+                    pointer
+                );
+            }
         }
 
         Ok(())
     }
 
     #[test]
-    fn it_detects_non_parent_json_pointer() -> Result<(), Error> {
+    fn it_iterates_over_components() -> Result<(), Error> {
         let tests = [
-            (Pointer::root(), Pointer::root()),
-            (Pointer::new("/a/b")?, Pointer::new("/a")?),
-            (Pointer::new("/a/b")?, Pointer::new("/a/b")?),
-            (Pointer::new("/a/b")?, Pointer::new("/a/b/c/d")?),
+            (Pointer::root(), vec![Component::RootDir]),
+            (
+                Pointer::new("/")?,
+                vec![Component::RootDir, Component::Normal("".into())],
+            ),
+            (
+                Pointer::new("/a/b")?,
+                vec![
+                    Component::RootDir,
+                    Component::Normal("a".into()),
+                    Component::Normal("b".into()),
+                ],
+            ),
+            (
+                Pointer::new("/~0a/b~1c")?,
+                vec![
+                    Component::RootDir,
+                    Component::Normal("~a".into()),
+                    Component::Normal("b/c".into()),
+                ],
+            ),
         ];
 
-        for (pointer_a, pointer_b) in tests {
-            assert!(
-                !pointer_a.is_parent_of(&pointer_b),
-                "'{}' is not the parent of '{}' JSON pointer",
-                pointer_a,
-                pointer_b,
+        for (pointer, expected_components) in tests {
+            assert_eq!(
+                pointer.components().collect::<Vec<_>>(),
+                expected_components,
+                "Components of '{}' JSON pointer",
+                pointer
             );
         }
 
@@ -381,42 +3057,109 @@ mod tests {
     }
 
     #[test]
-    fn it_detects_ancestor_json_pointer() -> Result<(), Error> {
+    fn it_joins_raw_reference_tokens() -> Result<(), Error> {
         let tests = [
-            (Pointer::root(), Pointer::root()),
-            (Pointer::root(), Pointer::new("/")?),
-            (Pointer::new("/")?, Pointer::new("//a")?),
-            (Pointer::new("/a/b")?, Pointer::new("/a/b")?),
-            (Pointer::new("/a/b/c")?, Pointer::new("/a/b/c/d/e/f/g")?),
-            (Pointer::new("/foo/0")?, Pointer::new("/foo/0/bar/zoo")?),
+            ("", "foo", "/foo"),
+            ("", "", "/"),
+            ("/foo", "a/b", "/foo/a~1b"),
+            ("/foo", "~a~1", "/foo/~0a~01"),
+            ("/foo", "", "/foo/"),
+            ("/", "", "//"),
         ];
 
-        for (pointer_a, pointer_b) in tests {
-            assert!(
-                pointer_a.is_ancestor_of(&pointer_b),
-                "'{}' is an ancestor of '{}' JSON pointer",
-                pointer_a,
-                pointer_b
+        for (s, token, expected_s) in tests {
+            let pointer = Pointer::new(s)?;
+            let joined_pointer = pointer.join(token);
+
+            assert_eq!(
+                joined_pointer.as_str(),
+                expected_s,
+                "'{}' joined to '{}' JSON pointer",
+                token,
+                pointer
             );
+            assert_eq!(joined_pointer.key(), Some(token.to_string()));
+            assert_eq!(joined_pointer.parent(), Some(pointer.clone()));
+
+            let mut pushed_pointer = pointer;
+            pushed_pointer.push_token(token);
+
+            assert_eq!(pushed_pointer, joined_pointer);
         }
 
         Ok(())
     }
 
     #[test]
-    fn it_detects_non_ancestor_json_pointer() -> Result<(), Error> {
-        let tests = [
-            (Pointer::new("/a/b")?, Pointer::new("/a")?),
-            (Pointer::new("/0/foo/bar/zoo")?, Pointer::new("/1/foo/bar/zoo")?),
-            (Pointer::new("/tric")?, Pointer::new("/tricky/test")?),
-        ];
+    #[cfg(feature = "unicode")]
+    fn it_sorts_json_pointers_by_unicode_collation() -> Result<(), Error> {
+        let mut pointers = ["/zebra", "/éclair", "/Eb", "/apple", "/ed", "/Œuvre", "/eclair", "/a/b"]
+            .into_iter()
+            .map(Pointer::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        pointers.sort();
+
+        let expected_pointers = ["/apple", "/Eb", "/eclair", "/éclair", "/ed", "/Œuvre", "/zebra", "/a/b"]
+            .into_iter()
+            .map(Pointer::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        assert_eq!(pointers, expected_pointers);
+        assert_eq!(Pointer::new("/É")?.cmp(&Pointer::new("/é")?), Ordering::Less);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_round_trips_encoded_tokens() {
+        let chars = ['~', '0', '1', '/', 'a', 'é'];
+        let mut tokens = vec![String::new()];
+        let mut last_tokens = tokens.clone();
+
+        // every string of up to 4 chars among the ones above.
+        for _ in 0..4 {
+            last_tokens = last_tokens
+                .iter()
+                .flat_map(|token| chars.iter().map(move |c| format!("{}{}", token, c)))
+                .collect();
+
+            tokens.extend(last_tokens.iter().cloned());
+        }
+
+        for token in &tokens {
+            let encoded_token = encode_token(token);
 
-        for (pointer_a, pointer_b) in tests {
             assert!(
-                !pointer_a.is_ancestor_of(&pointer_b),
-                "'{}' is not an ancestor of '{}' JSON pointer",
-                pointer_a,
-                pointer_b,
+                !encoded_token.contains('/'),
+                "'{}' encoded token has no '/'",
+                encoded_token
+            );
+            assert!(check_escape_sequences(&encoded_token).is_ok());
+            assert_eq!(&decode_token(&encoded_token), token, "Round trip of '{}' token", token);
+        }
+    }
+
+    #[test]
+    fn it_creates_json_pointer_from_tokens() -> Result<(), Error> {
+        assert_eq!(Pointer::from_tokens(Vec::<String>::new()), Pointer::root());
+        assert_eq!(Pointer::from_tokens([""]).as_str(), "/");
+        assert_eq!(Pointer::from_tokens(["", ""]).as_str(), "//");
+        assert_eq!(
+            Pointer::from_tokens(vec!["a/b".to_string(), "~c".to_string()]).as_str(),
+            "/a~1b/~0c"
+        );
+
+        let tests = ["", "/", "/foo", "/foo/0/bar", "/a~1b/~0c", "/~01", "//a//"];
+
+        for s in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                Pointer::from_tokens(pointer.tokenize()),
+                pointer,
+                "Round trip of '{}' JSON pointer",
+                pointer
             );
         }
 
@@ -424,20 +3167,24 @@ mod tests {
     }
 
     #[test]
-    fn it_detects_sibling_json_pointer() -> Result<(), Error> {
+    fn it_collapses_empty_tokens() -> Result<(), Error> {
         let tests = [
-            (Pointer::new("/")?, Pointer::new("/a")?),
-            (Pointer::new("/a")?, Pointer::new("/")?),
-            (Pointer::new("/a/b/c")?, Pointer::new("/a/b/d")?),
-            (Pointer::new("/foo/bar/zoo/0")?, Pointer::new("/foo/bar/zoo/42")?),
+            ("/a//b/", "/a/b"),
+            ("/a/b", "/a/b"),
+            ("//a///b~1c//", "/a/b~1c"),
+            ("/", ""),
+            ("//", ""),
+            ("", ""),
         ];
 
-        for (pointer_a, pointer_b) in tests {
-            assert!(
-                pointer_a.is_sibling_of(&pointer_b),
-                "'{}' is a sibling of '{}' JSON pointer",
-                pointer_a,
-                pointer_b
+        for (s, expected_s) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                pointer.collapse_empty_tokens().as_str(),
+                expected_s,
+                "'{}' JSON pointer without empty tokens",
+                pointer
             );
         }
 
@@ -445,56 +3192,115 @@ mod tests {
     }
 
     #[test]
-    fn it_detects_non_sibling_json_pointer() -> Result<(), Error> {
-        let tests = [
-            (Pointer::root(), Pointer::root()),
-            (Pointer::new("/b/d")?, Pointer::new("/b/d")?),
-            (Pointer::new("/b/d")?, Pointer::new("/a")?),
-            (Pointer::new("/a")?, Pointer::new("/b/d")?),
-            (Pointer::new("/a/b/c")?, Pointer::new("/d/e/f")?),
-            (Pointer::new("/0/foo/bar/zoo")?, Pointer::new("/1/foo/bar/zoo")?),
-        ];
+    fn it_borrows_tokens_without_escape_sequences() -> Result<(), Error> {
+        let pointer = Pointer::new("/foo/0//bar baz/€")?;
+
+        assert!(pointer.tokens().all(|token| matches!(token, Cow::Borrowed(_))));
+        assert_eq!(
+            pointer.tokens().collect::<Vec<_>>(),
+            pointer.tokenize().collect::<Vec<_>>()
+        );
+
+        let pointer = Pointer::new("/foo/~1bar/~0/baz")?;
+        let tokens = pointer.tokens().collect::<Vec<_>>();
+
+        assert_eq!(tokens, vec!["foo", "/bar", "~", "baz"]);
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| matches!(token, Cow::Owned(_)))
+                .collect::<Vec<_>>(),
+            vec![false, true, true, false]
+        );
+        assert_eq!(Pointer::root().tokens().count(), 0);
 
-        for (pointer_a, pointer_b) in tests {
-            assert!(
-                !pointer_a.is_sibling_of(&pointer_b),
-                "'{}' is not a sibling of '{}' JSON pointer",
-                pointer_a,
-                pointer_b
-            );
+        Ok(())
+    }
+
+    #[test]
+    fn it_binary_searches_descendants_in_sorted_pointers() -> Result<(), Error> {
+        let mut pointers = [
+            "", "/a", "/a-b", "/b", "/a/x", "/a/y", "/a-b/x", "/b/x", "/a/x/z", "/a/y/z", "/ab/x/z", "/a//",
+        ]
+        .into_iter()
+        .map(Pointer::new)
+        .collect::<Result<Vec<_>, _>>()?;
+        pointers.sort();
+
+        let tests = ["", "/a", "/a/x", "/a-b", "/b/x", "/c", "/a/x/z", "/a/x/z/w", "/"];
+
+        for s in tests {
+            let prefix = Pointer::new(s)?;
+            let indexes = binary_search_prefix(&pointers, &prefix)
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+            let expected_indexes = (0..pointers.len())
+                .filter(|i| prefix.is_ancestor_of(&pointers[*i]))
+                .collect::<Vec<_>>();
+
+            assert_eq!(indexes, expected_indexes, "Descendants of '{}' JSON pointer", prefix);
         }
 
+        assert_eq!(binary_search_prefix(&[], &Pointer::root()), vec![]);
+
         Ok(())
     }
 
     #[test]
-    fn it_gets_ancestor_json_pointers() -> Result<(), Error> {
-        let tests = [
-            (Pointer::root(), vec![Pointer::root()]),
-            (Pointer::new("/")?, vec![Pointer::new("/")?, Pointer::root()]),
-            (
-                Pointer::new("/a/b")?,
-                vec![Pointer::new("/a/b")?, Pointer::new("/a")?, Pointer::root()],
-            ),
-            (
-                Pointer::new("/0/foo/bar/zoo")?,
-                vec![
-                    Pointer::new("/0/foo/bar/zoo")?,
-                    Pointer::new("/0/foo/bar")?,
-                    Pointer::new("/0/foo")?,
-                    Pointer::new("/0")?,
-                    Pointer::root(),
-                ],
-            ),
-        ];
+    fn it_sorts_lexical_json_pointers_in_document_order() -> Result<(), Error> {
+        let pointers = ["/b", "/a/b/c", "/a-b", "/a", "", "/a/b", "/ab", "/a/c", "/", "/a/"];
+
+        let sorted_pointers = pointers
+            .into_iter()
+            .map(Pointer::new)
+            .collect::<Result<BTreeSet<_>, _>>()?
+            .into_iter()
+            .map(|pointer| pointer.to_string())
+            .collect::<Vec<_>>();
+        let sorted_lexical_pointers = pointers
+            .into_iter()
+            .map(|s| Pointer::new(s).map(LexicalPointer))
+            .collect::<Result<BTreeSet<_>, _>>()?
+            .into_iter()
+            .map(|pointer| pointer.to_string())
+            .collect::<Vec<_>>();
+
+        #[cfg(not(feature = "unicode"))]
+        assert_eq!(
+            sorted_pointers,
+            ["", "/", "/a", "/a-b", "/ab", "/b", "/a/", "/a/b", "/a/c", "/a/b/c"]
+        );
+        assert_eq!(sorted_pointers.len(), sorted_lexical_pointers.len());
+        assert_eq!(
+            sorted_lexical_pointers,
+            ["", "/", "/a", "/a/", "/a/b", "/a/b/c", "/a/c", "/a-b", "/ab", "/b"]
+        );
 
-        for (pointer, expected_ancestor_pointers) in tests {
-            let ancestor_pointers = pointer.ancestors().collect::<Vec<_>>();
+        Ok(())
+    }
+
+    #[test]
+    fn it_round_trips_json_pointer_through_display() -> Result<(), Error> {
+        for s in [
+            "",
+            "/",
+            "//",
+            "/foo",
+            "/foo/0/bar",
+            "/a~1b/c~0d",
+            "/ é/%20",
+            "/-",
+            "/~01",
+        ] {
+            let pointer = Pointer::new(s)?;
+            let displayed_pointer = pointer.to_string();
 
             assert_eq!(
-                ancestor_pointers, expected_ancestor_pointers,
-                "Ancestors of '{}' JSON pointer",
-                pointer
+                Pointer::from_str(&displayed_pointer),
+                Ok(pointer),
+                "Round trip of '{}' JSON pointer",
+                s
             );
         }
 
@@ -502,38 +3308,90 @@ mod tests {
     }
 
     #[test]
-    fn it_gets_json_pointer_depth() -> Result<(), Error> {
-        let tests = [
-            (Pointer::root(), 0),
-            (Pointer::new("/")?, 1),
-            (Pointer::new("/a")?, 1),
-            (Pointer::new("/a/b/c")?, 3),
-            (Pointer::new("/foo/0/bar/1/zoo/2")?, 6),
-        ];
+    fn it_defaults_to_root_json_pointer() {
+        assert!(Pointer::default().is_root());
+        assert_eq!(Pointer::default(), Pointer::root());
+    }
 
-        for (pointer, expected_depth) in tests {
-            assert_eq!(pointer.depth(), expected_depth, "Depth of '{}' JSON pointer", pointer);
+    #[test]
+    fn it_derefs_json_pointer_to_str() -> Result<(), Error> {
+        let pointer = Pointer::new("/foo/bar")?;
+        let map = HashMap::from([("/foo/bar".to_string(), 42)]);
+
+        assert_eq!(map.get(&*pointer), Some(&42));
+        assert!(pointer.starts_with("/foo"));
+        assert_eq!(pointer.len(), 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_pops_reference_tokens() -> Result<(), Error> {
+        let s = "/foo/a~1b/0/c~0d/";
+        let expected_tokens = ["", "c~d", "0", "a/b", "foo"];
+
+        let mut borrowed_pointer = Pointer::new(s)?;
+        let mut owned_pointer = Pointer::new(s.to_string())?;
+        let capacity = match &owned_pointer.0 {
+            Cow::Owned(s) => s.capacity(),
+            Cow::Borrowed(_) => unreachable!(),
+        };
+
+        for expected_token in expected_tokens {
+            let expected_pointer = borrowed_pointer.parent().unwrap().into_owned();
+
+            assert_eq!(borrowed_pointer.pop().as_deref(), Some(expected_token));
+            assert_eq!(owned_pointer.pop().as_deref(), Some(expected_token));
+            assert_eq!(borrowed_pointer, expected_pointer);
+            assert_eq!(owned_pointer, expected_pointer);
         }
 
+        assert!(matches!(borrowed_pointer.0, Cow::Borrowed(_)));
+        assert!(matches!(&owned_pointer.0, Cow::Owned(s) if s.capacity() == capacity));
+
+        assert!(borrowed_pointer.is_root());
+        assert_eq!(borrowed_pointer.pop(), None);
+        assert_eq!(owned_pointer.pop(), None);
+        assert!(owned_pointer.is_root());
+
         Ok(())
     }
 
     #[test]
-    fn it_evaluates_json_pointer_into_tokens() -> Result<(), Error> {
+    fn it_gets_key_as_index_from_json_pointer() -> Result<(), Error> {
+        let pointer = Pointer::new("/foo/items")?;
+
+        for index in [0, 3, 42, usize::MAX] {
+            let index_pointer = pointer.with_index(index).with_index(index);
+
+            assert_eq!(
+                index_pointer.key_as_index(),
+                Some(index),
+                "Index of '{}' JSON pointer",
+                index_pointer
+            );
+            assert_eq!(index_pointer.parent().unwrap().key_as_index(), Some(index));
+        }
+
         let tests = [
-            (Pointer::root(), vec![]),
-            (Pointer::new("/")?, vec![""]),
-            (Pointer::new("/~1a")?, vec!["/a"]),
-            (Pointer::new("/~01a")?, vec!["~1a"]),
-            (Pointer::new("/~10a")?, vec!["/0a"]),
-            (Pointer::new("/~1a/~0b/c")?, vec!["/a", "~b", "c"]),
+            ("", None),
+            ("/0", Some(0)),
+            ("/foo/10", Some(10)),
+            ("/foo", None),
+            ("/foo/", None),
+            ("/foo/-", None),
+            ("/foo/01", None),
+            ("/foo/+1", None),
+            ("/foo/-1", None),
+            ("/foo/1a", None),
+            ("/foo/1/bar", None),
+            ("/foo/99999999999999999999999", None),
         ];
 
-        for (pointer, expected_tokens) in tests {
-            let tokens = pointer.tokenize().collect::<Vec<_>>();
-            let tokens = tokens.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        for (s, expected_index) in tests {
+            let pointer = Pointer::new(s)?;
 
-            assert_eq!(tokens, expected_tokens, "Tokens of '{}' JSON pointer", pointer);
+            assert_eq!(pointer.key_as_index(), expected_index, "Index of '{}' JSON pointer", s);
         }
 
         Ok(())
@@ -0,0 +1,66 @@
+use std::fmt;
+
+use crate::Pointer;
+
+/// Violations of a JSON pointer policy, see [`ValueExt::check_policy`](crate::ValueExt::check_policy) method.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct PolicyViolation {
+    /// Required JSON pointers which do not resolve, in the given order.
+    pub missing: Vec<Pointer<'static>>,
+    /// Forbidden JSON pointers which resolve, in the given order.
+    pub forbidden: Vec<Pointer<'static>>,
+}
+
+impl PolicyViolation {
+    /// Indicates if there is no violation at all.
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.forbidden.is_empty()
+    }
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let write_pointers = |f: &mut fmt::Formatter<'_>, label: &str, pointers: &[Pointer<'static>]| {
+            write!(f, "{} JSON pointers [", label)?;
+
+            for (i, pointer) in pointers.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+
+                write!(f, "'{}'", pointer)?;
+            }
+
+            f.write_str("]")
+        };
+
+        write_pointers(f, "missing required", &self.missing)?;
+        f.write_str(", ")?;
+        write_pointers(f, "present forbidden", &self.forbidden)
+    }
+}
+
+impl std::error::Error for PolicyViolation {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn it_displays_policy_violation() -> Result<(), Error> {
+        let violation = PolicyViolation {
+            missing: vec![Pointer::new("/a")?, Pointer::new("/b~1c")?],
+            forbidden: vec![Pointer::new("/d")?],
+        };
+
+        assert_eq!(
+            violation.to_string(),
+            "missing required JSON pointers ['/a', '/b~1c'], present forbidden JSON pointers ['/d']"
+        );
+        assert!(!violation.is_empty());
+        assert!(PolicyViolation::default().is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use alloc::string::String;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{Error, Pointer};
+
+/// Relative JSON pointer as described in the
+/// [draft specification](https://datatracker.ietf.org/doc/html/draft-bhutton-relative-json-pointer-00).
+///
+/// A relative JSON pointer is made of a non-negative integer, the number of levels to go up from a base JSON
+/// pointer, followed either by a `#` referencing the key or index of the reached JSON value, or by a JSON pointer
+/// applied on the reached JSON value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RelativePointer<'a> {
+    up: usize,
+    pointer: Option<Pointer<'a>>,
+}
+
+impl<'a> RelativePointer<'a> {
+    /// Creates a `RelativePointer` from a Unicode string.
+    ///
+    /// # Arguments
+    /// * `s`: A Unicode string representing a relative JSON pointer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use json_toolkit::{Pointer, RelativePointer};
+    ///
+    /// let relative_pointer = RelativePointer::new("1/foo").unwrap();
+    /// assert_eq!(relative_pointer.up(), 1);
+    /// assert_eq!(relative_pointer.pointer(), Some(&Pointer::new("/foo").unwrap()));
+    ///
+    /// let relative_pointer = RelativePointer::new("0#").unwrap();
+    /// assert!(relative_pointer.is_key_reference());
+    /// ```
+    ///
+    /// # Errors
+    /// This method fails with [`Error::InvalidRelativePointer`] if the Unicode string does not start with a
+    /// non-negative integer without leading zeros, or if the integer is followed by anything other than `#` or a JSON
+    /// pointer.
+    pub fn new(s: &'a str) -> Result<Self, Error> {
+        let digits_len = s.bytes().take_while(u8::is_ascii_digit).count();
+        let (digits, remainder) = s.split_at(digits_len);
+
+        if digits.is_empty() || (digits.len() > 1 && digits.starts_with('0')) {
+            return Err(Error::InvalidRelativePointer);
+        }
+
+        let up = digits.parse().map_err(|_| Error::InvalidRelativePointer)?;
+        let pointer = match remainder {
+            "#" => None,
+            remainder => Some(Pointer::new(remainder).map_err(|_| Error::InvalidRelativePointer)?),
+        };
+
+        Ok(Self { up, pointer })
+    }
+
+    /// Returns the number of levels to go up from the base JSON pointer.
+    pub fn up(&self) -> usize {
+        self.up
+    }
+
+    /// Returns the JSON pointer applied once gone up from the base JSON pointer, or `None` if the relative JSON
+    /// pointer references a key or an index.
+    pub fn pointer(&self) -> Option<&Pointer<'a>> {
+        self.pointer.as_ref()
+    }
+
+    /// Indicates if the relative JSON pointer ends with a `#`, referencing the key or index of the reached JSON
+    /// value rather than the JSON value itself.
+    pub fn is_key_reference(&self) -> bool {
+        self.pointer.is_none()
+    }
+
+    /// Converts the borrowed JSON pointer of the relative JSON pointer into an owned one.
+    pub fn into_owned(self) -> RelativePointer<'static> {
+        RelativePointer {
+            up: self.up,
+            pointer: self.pointer.map(Pointer::into_owned),
+        }
+    }
+}
+
+impl fmt::Display for RelativePointer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.pointer {
+            Some(pointer) => write!(f, "{}{}", self.up, pointer),
+            None => write!(f, "{}#", self.up),
+        }
+    }
+}
+
+impl FromStr for RelativePointer<'static> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RelativePointer::new(s).map(RelativePointer::into_owned)
+    }
+}
+
+/// Resolution of a relative JSON pointer, see [`ValueExt::resolve_relative`](crate::ValueExt::resolve_relative)
+/// method.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RelativeTarget<'v, V> {
+    /// The reached JSON value.
+    Value(&'v V),
+    /// The key of the reached JSON value within its parent JSON object.
+    Key(String),
+    /// The index of the reached JSON value within its parent JSON array.
+    Index(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_relative_json_pointer() -> Result<(), Error> {
+        let tests = [
+            ("0", 0, Some("")),
+            ("0#", 0, None),
+            ("1/foo", 1, Some("/foo")),
+            ("2/foo~1bar/0", 2, Some("/foo~1bar/0")),
+            ("10#", 10, None),
+        ];
+
+        for (s, expected_up, expected_pointer) in tests {
+            let relative_pointer = RelativePointer::new(s)?;
+
+            assert_eq!(
+                relative_pointer.up(),
+                expected_up,
+                "Levels of '{}' relative JSON pointer",
+                s
+            );
+            assert_eq!(
+                relative_pointer.pointer().map(Pointer::as_str),
+                expected_pointer,
+                "JSON pointer of '{}' relative JSON pointer",
+                s
+            );
+            assert_eq!(
+                relative_pointer.to_string(),
+                s,
+                "Display of '{}' relative JSON pointer",
+                s
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_invalid_relative_json_pointer() {
+        for s in [
+            "",
+            "#",
+            "/foo",
+            "01/foo",
+            "-1/foo",
+            "1foo",
+            "1#/foo",
+            "1##",
+            "99999999999999999999999#",
+        ] {
+            assert_eq!(
+                RelativePointer::new(s),
+                Err(Error::InvalidRelativePointer),
+                "'{}' relative JSON pointer",
+                s
+            );
+        }
+    }
+}
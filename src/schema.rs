@@ -0,0 +1,173 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::{Error, Pointer};
+
+/// A runtime allow-list of JSON pointers belonging to the schema `S`.
+///
+/// `S` is a marker type naming the schema, so that a [`SchemaPointer<S>`] can only be obtained from the allow-list of
+/// this very schema, see [`Schema::pointer`].
+///
+/// # Examples
+/// ```
+/// # use json_toolkit::{Error, Pointer, Schema};
+///
+/// struct Config;
+///
+/// let schema = Schema::<Config>::new([Pointer::new("/server/port").unwrap()]);
+///
+/// let port = schema.pointer(&Pointer::new("/server/port").unwrap()).unwrap();
+/// assert_eq!(port.as_pointer(), &Pointer::new("/server/port").unwrap());
+///
+/// let result = schema.pointer(&Pointer::new("/server/host").unwrap());
+/// assert_eq!(result.unwrap_err(), Error::NotInSchema("/server/host".to_string()));
+/// ```
+pub struct Schema<S> {
+    pointers: HashSet<String>,
+    marker: PhantomData<fn() -> S>,
+}
+
+impl<S> Schema<S> {
+    /// Creates a `Schema` allowing the given JSON pointers.
+    pub fn new<'a>(pointers: impl IntoIterator<Item = Pointer<'a>>) -> Self {
+        Self {
+            pointers: pointers
+                .into_iter()
+                .map(|pointer| pointer.normalized().as_str().to_string())
+                .collect(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Indicates if the given JSON pointer belongs to the schema.
+    pub fn contains(&self, pointer: &Pointer<'_>) -> bool {
+        self.pointers.contains(pointer.normalized().as_str())
+    }
+
+    /// Vets the given JSON pointer against the schema.
+    ///
+    /// # Errors
+    /// This method fails with [`Error::NotInSchema`] if the JSON pointer does not belong to the schema.
+    pub fn pointer(&self, pointer: &Pointer<'_>) -> Result<SchemaPointer<S>, Error> {
+        if self.contains(pointer) {
+            Ok(SchemaPointer {
+                pointer: pointer.normalized(),
+                marker: PhantomData,
+            })
+        } else {
+            Err(Error::NotInSchema(pointer.to_string()))
+        }
+    }
+}
+
+impl<S> fmt::Debug for Schema<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Schema").field("pointers", &self.pointers).finish()
+    }
+}
+
+impl<S> Clone for Schema<S> {
+    fn clone(&self) -> Self {
+        Self {
+            pointers: self.pointers.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// A JSON pointer vetted by a [`Schema<S>`].
+///
+/// A `SchemaPointer<S>` can only be created by [`Schema::pointer`], hence functions expecting a `SchemaPointer<S>` are
+/// ensured at compile time to receive a JSON pointer belonging to the schema `S`.
+pub struct SchemaPointer<S> {
+    pointer: Pointer<'static>,
+    marker: PhantomData<fn() -> S>,
+}
+
+impl<S> SchemaPointer<S> {
+    /// Returns the vetted JSON pointer.
+    pub fn as_pointer(&self) -> &Pointer<'static> {
+        &self.pointer
+    }
+
+    /// Converts the `SchemaPointer` into its vetted JSON pointer.
+    pub fn into_pointer(self) -> Pointer<'static> {
+        self.pointer
+    }
+}
+
+impl<S> AsRef<Pointer<'static>> for SchemaPointer<S> {
+    fn as_ref(&self) -> &Pointer<'static> {
+        &self.pointer
+    }
+}
+
+impl<S> fmt::Debug for SchemaPointer<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SchemaPointer").field(&self.pointer).finish()
+    }
+}
+
+impl<S> fmt::Display for SchemaPointer<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pointer.fmt(f)
+    }
+}
+
+impl<S> Clone for SchemaPointer<S> {
+    fn clone(&self) -> Self {
+        Self {
+            pointer: self.pointer.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<S> PartialEq for SchemaPointer<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.pointer == other.pointer
+    }
+}
+
+impl<S> Eq for SchemaPointer<S> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Config;
+
+    #[test]
+    fn it_vets_json_pointers_against_schema() -> Result<(), Error> {
+        let schema = Schema::<Config>::new([
+            Pointer::new("/server/port")?,
+            Pointer::new("/server/host")?,
+            Pointer::new("/a~01")?,
+        ]);
+
+        let tests = [
+            ("/server/port", Ok("/server/port")),
+            ("/server/host", Ok("/server/host")),
+            ("/a~01", Ok("/a~01")),
+            ("/server", Err(Error::NotInSchema("/server".to_string()))),
+            ("/server/user", Err(Error::NotInSchema("/server/user".to_string()))),
+            ("", Err(Error::NotInSchema("".to_string()))),
+        ];
+
+        for (s, expected_result) in tests {
+            let pointer = Pointer::new(s)?;
+            let result = schema.pointer(&pointer);
+
+            assert_eq!(schema.contains(&pointer), expected_result.is_ok());
+            assert_eq!(
+                result.map(SchemaPointer::into_pointer),
+                expected_result.map(|s| Pointer::new(s).unwrap()),
+                "Schema pointer of '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Pointer, ValueExt};
+
+/// Splits the JSON pointer into its first decoded reference token and the JSON pointer of the remaining ones.
+///
+/// The remaining JSON pointer borrows the underlying Unicode string as is, hence lax escapes are left untouched.
+fn split_first_token<'p>(pointer: &'p Pointer<'_>) -> Option<(String, Pointer<'p>)> {
+    let (span, _) = pointer.token_spans().next()?;
+    let key = pointer.first_token()?;
+    let remaining_pointer = Pointer::new(&pointer.as_str()[span.end..]).ok()?;
+
+    Some((key, remaining_pointer))
+}
+
+/// Manipulates a map of JSON values by a JSON pointer, as if the map were a root JSON object.
+///
+/// The first reference token of a JSON pointer selects the map entry, while the remaining ones are applied on the
+/// entry's JSON value, see [`ValueExt`]. As the map is not a JSON value itself, the root JSON pointer does not point
+/// to any JSON value.
+///
+/// This trait is implemented for [`Map<String, Value>`], [`HashMap<String, Value>`] and
+/// [`BTreeMap<String, Value>`].
+pub trait MapExt {
+    /// Looks up a JSON value by a JSON pointer, see [`ValueExt::pointer`].
+    ///
+    /// Returns `None` for the root JSON pointer.
+    fn pointer(&self, pointer: &Pointer<'_>) -> Option<&Value>;
+
+    /// Looks up a JSON value by a JSON pointer and returns a mutable reference to that value, see
+    /// [`ValueExt::pointer_mut`].
+    ///
+    /// Returns `None` for the root JSON pointer.
+    fn pointer_mut(&mut self, pointer: &Pointer<'_>) -> Option<&mut Value>;
+
+    /// Inserts any data at the given pointee JSON value, see [`ValueExt::insert_at`].
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use json_toolkit::serde::MapExt;
+    /// use json_toolkit::Pointer;
+    /// use serde_json::json;
+    ///
+    /// let mut config = HashMap::from([("db".to_string(), json!({ "host": "localhost" }))]);
+    ///
+    /// config.insert_at(&Pointer::new("/db/port").unwrap(), 5432).unwrap();
+    /// config.insert_at(&Pointer::new("/debug").unwrap(), true).unwrap();
+    ///
+    /// assert_eq!(config["db"], json!({ "host": "localhost", "port": 5432 }));
+    /// assert_eq!(MapExt::pointer(&config, &Pointer::new("/debug").unwrap()), Some(&json!(true)));
+    /// ```
+    ///
+    /// # Errors
+    /// This method fails with [`Error::UnexpectedRootPointer`] for the root JSON pointer, with
+    /// [`Error::KeyNotFound`] if the map entry does not exist while the JSON pointer has several reference tokens, and
+    /// otherwise behaves like [`ValueExt::insert_at`].
+    fn insert_at(&mut self, pointer: &Pointer<'_>, value: impl Into<Value>) -> Result<Option<Value>, Error>;
+}
+
+macro_rules! impl_map_ext {
+    ($($map:ty),+) => {
+        $(
+            impl MapExt for $map {
+                fn pointer(&self, pointer: &Pointer<'_>) -> Option<&Value> {
+                    let (key, remaining_pointer) = split_first_token(pointer)?;
+
+                    self.get(&key).and_then(|value| ValueExt::pointer(value, &remaining_pointer))
+                }
+
+                fn pointer_mut(&mut self, pointer: &Pointer<'_>) -> Option<&mut Value> {
+                    let (key, remaining_pointer) = split_first_token(pointer)?;
+
+                    self.get_mut(&key).and_then(|value| ValueExt::pointer_mut(value, &remaining_pointer))
+                }
+
+                fn insert_at(&mut self, pointer: &Pointer<'_>, value: impl Into<Value>) -> Result<Option<Value>, Error> {
+                    let (key, remaining_pointer) = split_first_token(pointer).ok_or(Error::UnexpectedRootPointer)?;
+
+                    if remaining_pointer.is_root() {
+                        return Ok(self.insert(key, value.into()));
+                    }
+
+                    match self.get_mut(&key) {
+                        Some(entry_value) => entry_value.insert_at(&remaining_pointer, value),
+                        None => Err(Error::KeyNotFound),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_map_ext!(Map<String, Value>, HashMap<String, Value>, BTreeMap<String, Value>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn map() -> Map<String, Value> {
+        match json!({ "a": { "b": [1, 2] }, "c~d": "e", "f": null }) {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_gets_pointee_json_value_of_map() -> Result<(), Error> {
+        let mut map = map();
+
+        let tests = [
+            ("", None),
+            ("/a", Some(json!({ "b": [1, 2] }))),
+            ("/a/b/1", Some(json!(2))),
+            ("/c~0d", Some(json!("e"))),
+            ("/f", Some(Value::Null)),
+            ("/a/z", None),
+            ("/z", None),
+        ];
+
+        for (s, expected_value) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                map.pointer(&pointer),
+                expected_value.as_ref(),
+                "Value at '{}' JSON pointer",
+                s
+            );
+            assert_eq!(
+                map.pointer_mut(&pointer).map(|value| &*value),
+                expected_value.as_ref(),
+                "Mutable value at '{}' JSON pointer",
+                s
+            );
+        }
+
+        *map.pointer_mut(&Pointer::new("/a/b/0")?).unwrap() = json!(3);
+        assert_eq!(map["a"], json!({ "b": [3, 2] }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_inserts_value_at_pointee_json_value_of_map() -> Result<(), Error> {
+        let mut map = map();
+
+        assert_eq!(map.insert_at(&Pointer::new("/a/g")?, 4)?, None);
+        assert_eq!(map.insert_at(&Pointer::new("/a/b/-")?, 5)?, None);
+        assert_eq!(map.insert_at(&Pointer::new("/c~0d")?, "h")?, Some(json!("e")));
+        assert_eq!(map.insert_at(&Pointer::new("/i")?, json!({}))?, None);
+
+        assert_eq!(
+            Value::Object(map.clone()),
+            json!({ "a": { "b": [1, 2, 5], "g": 4 }, "c~d": "h", "f": null, "i": {} })
+        );
+
+        let tests = [
+            ("", Error::UnexpectedRootPointer),
+            ("/z/g", Error::KeyNotFound),
+            ("/a/z/g", Error::KeyNotFound),
+            ("/f/g", Error::UnsupportedInsertion),
+            ("/a/b/9", Error::IndexOutOfBounds { index: 9, len: 3 }),
+        ];
+
+        for (s, expected_error) in tests {
+            assert_eq!(
+                map.insert_at(&Pointer::new(s)?, 6),
+                Err(expected_error),
+                "Insertion at '{}' JSON pointer",
+                s
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_manipulates_hash_map_and_btree_map() -> Result<(), Error> {
+        let mut hash_map = map().into_iter().collect::<HashMap<_, _>>();
+        let mut btree_map = map().into_iter().collect::<BTreeMap<_, _>>();
+        let pointer = Pointer::new("/a/b/1")?;
+
+        hash_map.insert_at(&pointer, 7)?;
+        btree_map.insert_at(&pointer, 7)?;
+
+        assert_eq!(hash_map.pointer(&pointer), Some(&json!(7)));
+        assert_eq!(btree_map.pointer(&pointer), Some(&json!(7)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_looks_up_json_pointer_with_lax_escape_in_map() -> Result<(), Error> {
+        let mut map = Map::new();
+        map.insert("a~2b".to_string(), json!({ "c~2d": 1, "e": { "f~": 2 } }));
+
+        let tests = [
+            ("/a~2b/c~2d", Some(json!(1))),
+            ("/a~2b/e/f~", Some(json!(2))),
+            ("/a~2b/z", None),
+        ];
+
+        for (s, expected_value) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                map.pointer(&pointer),
+                ValueExt::pointer(&Value::Object(map.clone()), &pointer),
+                "Value at '{}' JSON pointer",
+                s
+            );
+            assert_eq!(
+                map.pointer(&pointer),
+                expected_value.as_ref(),
+                "Value at '{}' JSON pointer",
+                s
+            );
+        }
+
+        assert_eq!(map.insert_at(&Pointer::new("/a~2b/g~")?, 3)?, None);
+        assert_eq!(map["a~2b"]["g~"], json!(3));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,304 @@
+use super::parse_index;
+use crate::Pointer;
+
+/// Minimal JSON scanner walking a source text without building any JSON value.
+struct Scanner<'s> {
+    source: &'s str,
+    pos: usize,
+}
+
+impl<'s> Scanner<'s> {
+    fn peek(&self) -> Option<u8> {
+        self.source.as_bytes().get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        self.skip_whitespaces();
+
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    /// Consumes the given byte if it is the next non-whitespace byte.
+    fn accept(&mut self, byte: u8) -> bool {
+        self.expect(byte).is_some()
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_string(&mut self) -> Option<()> {
+        self.expect(b'"')?;
+
+        loop {
+            match self.peek()? {
+                b'"' => break,
+                b'\\' => self.pos += 2,
+                _ => self.pos += 1,
+            }
+        }
+
+        self.pos += 1;
+
+        Some(())
+    }
+
+    /// Consumes a JSON object key and returns it decoded.
+    fn key(&mut self) -> Option<String> {
+        self.skip_whitespaces();
+
+        let start = self.pos;
+
+        self.skip_string()?;
+
+        serde_json::from_str(&self.source[start..self.pos]).ok()
+    }
+
+    /// Consumes the comma separating two JSON object members or JSON array elements, returning `false` if the closing
+    /// delimiter is reached instead.
+    fn next_item(&mut self, closing: u8) -> Option<bool> {
+        if self.accept(b',') {
+            Some(true)
+        } else {
+            self.expect(closing).map(|_| false)
+        }
+    }
+
+    fn skip_value(&mut self) -> Option<()> {
+        self.skip_whitespaces();
+
+        match self.peek()? {
+            b'"' => self.skip_string(),
+            b'{' => {
+                self.pos += 1;
+
+                if self.accept(b'}') {
+                    return Some(());
+                }
+
+                loop {
+                    self.skip_string()?;
+                    self.expect(b':')?;
+                    self.skip_value()?;
+
+                    if !self.next_item(b'}')? {
+                        return Some(());
+                    }
+                }
+            }
+            b'[' => {
+                self.pos += 1;
+
+                if self.accept(b']') {
+                    return Some(());
+                }
+
+                loop {
+                    self.skip_value()?;
+
+                    if !self.next_item(b']')? {
+                        return Some(());
+                    }
+                }
+            }
+            b't' | b'f' | b'n' => {
+                let literal = ["true", "false", "null"]
+                    .into_iter()
+                    .find(|literal| self.source[self.pos..].starts_with(literal))?;
+
+                self.pos += literal.len();
+
+                Some(())
+            }
+            b'-' | b'0'..=b'9' => {
+                while matches!(self.peek(), Some(b'+' | b'-' | b'.' | b'e' | b'E' | b'0'..=b'9')) {
+                    self.pos += 1;
+                }
+
+                Some(())
+            }
+            _ => None,
+        }
+    }
+
+    /// Moves to the start of the child JSON value identified by the given reference token.
+    fn enter(&mut self, token: &str) -> Option<()> {
+        self.skip_whitespaces();
+
+        match self.peek()? {
+            b'{' => {
+                self.pos += 1;
+
+                if self.accept(b'}') {
+                    return None;
+                }
+
+                // `serde_json` keeps the last duplicate key, hence every member of the JSON object is scanned.
+                let mut value_pos = None;
+
+                loop {
+                    let key = self.key()?;
+
+                    self.expect(b':')?;
+
+                    if key == token {
+                        self.skip_whitespaces();
+
+                        value_pos = Some(self.pos);
+                    }
+
+                    self.skip_value()?;
+
+                    if !self.next_item(b'}')? {
+                        break;
+                    }
+                }
+
+                self.pos = value_pos?;
+
+                Some(())
+            }
+            b'[' => {
+                let index = parse_index(token)?;
+
+                self.pos += 1;
+
+                if self.accept(b']') {
+                    return None;
+                }
+
+                for _ in 0..index {
+                    self.skip_value()?;
+
+                    if !self.next_item(b']')? {
+                        return None;
+                    }
+                }
+
+                Some(())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Locates the pointee JSON value in a JSON source text.
+///
+/// Returns the `(start, end)` byte offsets of the pointee JSON value in the source text, `end` being exclusive, or
+/// `None` if the pointee JSON value does not exist. The source text is scanned lazily up to the pointee JSON value and
+/// the end of its parent JSON objects, hence a malformed source text may only be detected when the pointee JSON value is
+/// located after the malformed part or within the same JSON objects.
+///
+/// As with [`serde_json::Value`], the last occurrence of a duplicate JSON object key is located.
+///
+/// # Arguments
+/// * `source`: A JSON source text.
+/// * `pointer`: A JSON pointer.
+///
+/// # Examples
+/// ```
+/// # use json_toolkit::{serde::locate_span, Pointer};
+///
+/// let source = r#"{ "foo": { "bar": [1, 2] } }"#;
+/// let (start, end) = locate_span(source, &Pointer::new("/foo/bar").unwrap()).unwrap();
+///
+/// assert_eq!(&source[start..end], "[1, 2]");
+/// ```
+pub fn locate_span(source: &str, pointer: &Pointer<'_>) -> Option<(usize, usize)> {
+    let mut scanner = Scanner { source, pos: 0 };
+
+    for token in pointer.tokenize() {
+        scanner.enter(&token)?;
+    }
+
+    scanner.skip_whitespaces();
+
+    let start = scanner.pos;
+
+    scanner.skip_value()?;
+
+    Some((start, scanner.pos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ValueExt;
+
+    #[test]
+    fn it_locates_pointee_json_value_span() -> Result<(), Box<dyn std::error::Error>> {
+        let source = r#" {
+            "foo": "bar",
+            "a/b": {"escaped \"key\"": true},
+            "zoo": {"id": [1, {"nested": null}, -2.5e3], "empty": {}}
+        } "#;
+
+        let tests = [
+            ("", source.trim()),
+            ("/foo", r#""bar""#),
+            ("/a~1b/escaped \"key\"", "true"),
+            ("/zoo/id", r#"[1, {"nested": null}, -2.5e3]"#),
+            ("/zoo/id/1/nested", "null"),
+            ("/zoo/id/2", "-2.5e3"),
+            ("/zoo/empty", "{}"),
+        ];
+
+        for (s, expected_text) in tests {
+            let (start, end) = locate_span(source, &Pointer::new(s)?).ok_or("missing span")?;
+
+            assert_eq!(&source[start..end], expected_text, "Span of '{}' JSON pointer", s);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_fails_to_locate_non_existing_pointee_json_value_span() -> Result<(), Box<dyn std::error::Error>> {
+        let source = r#"{"foo": {"bar": [1, 2]}, "zoo": "scalar"}"#;
+        let tests = ["/not_existing", "/foo/bar/2", "/foo/bar/01", "/zoo/id", "/foo/bar/0/id"];
+
+        for s in tests {
+            assert_eq!(
+                locate_span(source, &Pointer::new(s)?),
+                None,
+                "Span of '{}' JSON pointer",
+                s
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_locates_last_occurrence_of_duplicate_key_span() -> Result<(), Box<dyn std::error::Error>> {
+        let source = r#"{"a": 1, "b": {"c": 2}, "a": [3, 4], "b": {"d": 5}}"#;
+        let value = serde_json::from_str::<serde_json::Value>(source)?;
+
+        let tests = [("/a", "[3, 4]"), ("/a/1", "4"), ("/b", r#"{"d": 5}"#), ("/b/d", "5")];
+
+        for (s, expected_text) in tests {
+            let pointer = Pointer::new(s)?;
+            let (start, end) = locate_span(source, &pointer).ok_or("missing span")?;
+
+            assert_eq!(&source[start..end], expected_text, "Span of '{}' JSON pointer", s);
+            assert_eq!(
+                serde_json::from_str::<serde_json::Value>(&source[start..end])?,
+                ValueExt::pointer(&value, &pointer).cloned().ok_or("missing value")?,
+                "Value at '{}' JSON pointer",
+                s
+            );
+        }
+
+        assert_eq!(locate_span(source, &Pointer::new("/b/c")?), None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,3069 @@
+/// Represents any valid JSON value.
+pub use simd_json::OwnedValue as Value;
+
+use simd_json::{prelude::ValueAsScalar, StaticNode};
+
+use super::{Error, Pointer, ValueExt};
+use crate::pointer::parse_index;
+
+impl ValueExt for Value {
+    fn new_object() -> Self {
+        Value::Object(Box::default())
+    }
+
+    fn new_array() -> Self {
+        Value::Array(Box::default())
+    }
+
+    fn new_null() -> Self {
+        Value::Static(StaticNode::Null)
+    }
+
+    fn is_object(&self) -> bool {
+        matches!(self, Value::Object(_))
+    }
+
+    fn is_null(&self) -> bool {
+        matches!(self, Value::Static(StaticNode::Null))
+    }
+
+    fn as_array(&self) -> Option<&Vec<Self>> {
+        match self {
+            Value::Array(array) => Some(array.as_ref()),
+            _ => None,
+        }
+    }
+
+    fn as_array_mut(&mut self) -> Option<&mut Vec<Self>> {
+        match self {
+            Value::Array(array) => Some(array.as_mut()),
+            _ => None,
+        }
+    }
+
+    fn object_len(&self) -> Option<usize> {
+        match self {
+            Value::Object(object) => Some(object.len()),
+            _ => None,
+        }
+    }
+
+    fn children(&self) -> Vec<(String, &Self)> {
+        match self {
+            Value::Object(object) => object.iter().map(|(key, value)| (key.clone(), value)).collect(),
+            Value::Array(array) => array
+                .iter()
+                .enumerate()
+                .map(|(i, value)| (i.to_string(), value))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        ValueAsScalar::as_str(self)
+    }
+
+    fn as_i64(&self) -> Option<i64> {
+        ValueAsScalar::as_i64(self)
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        ValueAsScalar::cast_f64(self)
+    }
+
+    fn as_bool(&self) -> Option<bool> {
+        ValueAsScalar::as_bool(self)
+    }
+
+    fn lookup_token(&self, token: &str) -> Option<&Self> {
+        match self {
+            Value::Object(object) => object.get(token),
+            Value::Array(array) => parse_index(token).and_then(move |i| array.get(i)),
+            _ => None,
+        }
+    }
+
+    fn lookup_token_mut(&mut self, token: &str) -> Option<&mut Self> {
+        match self {
+            Value::Object(object) => object.get_mut(token),
+            Value::Array(array) => parse_index(token).and_then(move |i| array.get_mut(i)),
+            _ => None,
+        }
+    }
+
+    fn remove_token(&mut self, token: &str) -> Option<Self> {
+        match self {
+            Value::Object(object) => object.remove(token),
+            Value::Array(array) => parse_index(token)
+                .filter(|i| *i < array.len())
+                .map(move |i| array.remove(i)),
+            _ => None,
+        }
+    }
+
+    fn pointer(&self, pointer: &Pointer<'_>) -> Option<&Self> {
+        pointer.tokenize().try_fold(self, |value, key| value.lookup_token(&key))
+    }
+
+    fn pointer_mut(&mut self, pointer: &Pointer<'_>) -> Option<&mut Self> {
+        pointer
+            .tokenize()
+            .try_fold(self, |value, key| value.lookup_token_mut(&key))
+    }
+
+    fn insert(&mut self, key: String, value: impl Into<Self>) -> Result<Option<Self>, Error> {
+        match self {
+            Value::Object(object) => Ok(object.insert(key, value.into())),
+            Value::Array(array) => match parse_index(&key) {
+                Some(i) if i < array.len() => Ok(Some(std::mem::replace(&mut array[i], value.into()))),
+                Some(i) if i == array.len() => {
+                    array.push(value.into());
+
+                    Ok(None)
+                }
+                _ if key == "-" => {
+                    array.push(value.into());
+
+                    Ok(None)
+                }
+                Some(index) => Err(Error::IndexOutOfBounds {
+                    index,
+                    len: array.len(),
+                }),
+                None => Err(Error::InvalidIndex(key)),
+            },
+            _ => Err(Error::UnsupportedInsertion),
+        }
+    }
+
+    fn insert_strict(&mut self, key: String, value: impl Into<Self>) -> Result<(), Error> {
+        match self {
+            Value::Object(object) if object.contains_key(key.as_str()) => Err(Error::KeyAlreadyExists),
+            Value::Object(object) => {
+                object.insert(key, value.into());
+
+                Ok(())
+            }
+            Value::Array(array) => match parse_index(&key) {
+                Some(i) if i < array.len() => Err(Error::KeyAlreadyExists),
+                Some(i) if i == array.len() => {
+                    array.push(value.into());
+
+                    Ok(())
+                }
+                _ if key == "-" => {
+                    array.push(value.into());
+
+                    Ok(())
+                }
+                Some(index) => Err(Error::IndexOutOfBounds {
+                    index,
+                    len: array.len(),
+                }),
+                None => Err(Error::InvalidIndex(key)),
+            },
+            _ => Err(Error::UnsupportedInsertion),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        CompiledPointer, InsertOptions, JsonType, Patch, PatchOperation, PointerEntry, PointerPattern, PolicyViolation,
+        RelativePointer, RelativeTarget,
+    };
+
+    use simd_json::json;
+
+    #[test]
+    fn it_looks_up_values_by_json_pointer() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"foo": "bar", "zoo": {"id": [1, 2, 3]}});
+        let tests = [("", value.clone()), ("/foo", "bar".into()), ("/zoo/id/0", 1.into())];
+
+        for (s, mut expected_value) in tests {
+            let pointer = Pointer::new(s)?;
+
+            let pointee_value = value.pointer(&pointer);
+            assert_eq!(pointee_value, Some(&expected_value));
+
+            let pointee_value = value.pointer_mut(&pointer);
+            assert_eq!(pointee_value, Some(&mut expected_value));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_inserts_value_at_pointee_json_value() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"foo": {"bar": "zoo"}});
+
+        let tests = [
+            (json!({"foo": {"bar": "zoo", "test": 42}}), "/foo/test", 42),
+            (json!({"foo": {"bar": "zoo"}, "test": 21}), "/test", 21),
+        ];
+
+        for (expected_value, s, new_value) in tests {
+            let mut value = value.clone();
+            let old_value = value.insert_at(&Pointer::new(s)?, new_value)?;
+
+            assert_eq!(old_value, None);
+            assert_eq!(value, expected_value);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_inserts_value_at_root_json_value() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"foo": {"bar": "zoo"}});
+        let new_value = "test2";
+
+        let expected_old_value = value.clone();
+        let old_value = value.insert_at(&Pointer::root(), new_value)?;
+
+        assert_eq!(old_value, Some(expected_old_value));
+        assert_eq!(value, new_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_fails_to_insert_value_at_non_existing_pointee_json_value() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"foo": {"bar": "zoo"}});
+        let result = value.insert_at(&Pointer::new("/foo/not_existing/zoo")?, 42);
+
+        assert_eq!(result, Err(Error::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_fails_to_insert_value_at_json_scalar_value() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"foo": {"bar": "zoo", "array": [1, 2, 3]}});
+
+        let tests = ["/foo/bar/zoo", "/foo/bar/0"];
+
+        for s in tests {
+            let result = value.insert_at(&Pointer::new(s)?, 42);
+
+            assert_eq!(result, Err(Error::UnsupportedInsertion));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_strictly_inserts_new_key() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"foo": {"bar": "zoo"}});
+
+        value.insert_strict("test".to_string(), 21)?;
+        value.insert_at_strict(&Pointer::new("/foo/test")?, 42)?;
+
+        assert_eq!(value, json!({"foo": {"bar": "zoo", "test": 42}, "test": 21}));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_fails_to_strictly_insert_existing_key() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"foo": {"bar": "zoo"}});
+        let expected_value = value.clone();
+
+        let tests = [
+            value.clone().insert_strict("foo".to_string(), 42),
+            value.insert_at_strict(&Pointer::new("/foo/bar")?, 42),
+            value.insert_at_strict(&Pointer::root(), 42),
+        ];
+
+        for result in tests {
+            assert_eq!(result, Err(Error::KeyAlreadyExists));
+        }
+
+        assert_eq!(value, expected_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_inserts_value_with_options() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"foo": {"array": [1, 2]}});
+
+        let tests = [
+            (
+                InsertOptions::new().create_intermediate(true),
+                "/foo/bar/zoo",
+                json!({"foo": {"array": [1, 2], "bar": {"zoo": 42}}}),
+            ),
+            (
+                InsertOptions::new().create_intermediate(true),
+                "/a/b/c",
+                json!({"foo": {"array": [1, 2]}, "a": {"b": {"c": 42}}}),
+            ),
+            (
+                InsertOptions::new().append_on_overflow(true),
+                "/foo/array/2",
+                json!({"foo": {"array": [1, 2, 42]}}),
+            ),
+            (
+                InsertOptions::new().append_on_overflow(true),
+                "/foo/array/99",
+                json!({"foo": {"array": [1, 2, 42]}}),
+            ),
+            (
+                InsertOptions::new().create_intermediate(true).append_on_overflow(true),
+                "/foo/array/5",
+                json!({"foo": {"array": [1, 2, 42]}}),
+            ),
+            (
+                InsertOptions::new(),
+                "/foo/test",
+                json!({"foo": {"array": [1, 2], "test": 42}}),
+            ),
+        ];
+
+        for (options, s, expected_value) in tests {
+            let mut value = value.clone();
+            let old_value = value.insert_at_with(&Pointer::new(s)?, 42, &options)?;
+
+            assert_eq!(old_value, None);
+            assert_eq!(value, expected_value, "Insertion at '{}' with {:?}", s, options);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_fails_to_insert_value_with_options() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"foo": {"bar": "zoo", "array": [1, 2]}});
+
+        let tests = [
+            (InsertOptions::new(), "/foo/not_existing/zoo", Error::KeyNotFound),
+            (
+                InsertOptions::new().append_on_overflow(true),
+                "/foo/not_existing/zoo",
+                Error::KeyNotFound,
+            ),
+            (
+                InsertOptions::new(),
+                "/foo/array/5",
+                Error::IndexOutOfBounds { index: 5, len: 2 },
+            ),
+            (
+                InsertOptions::new().create_intermediate(true),
+                "/foo/array/5",
+                Error::IndexOutOfBounds { index: 5, len: 2 },
+            ),
+            (
+                InsertOptions::new().create_intermediate(true),
+                "/foo/bar/zoo/test",
+                Error::UnsupportedInsertion,
+            ),
+            (
+                InsertOptions::new().append_on_overflow(true),
+                "/foo/array/05",
+                Error::InvalidIndex("05".to_string()),
+            ),
+            (
+                InsertOptions::new().append_on_overflow(true),
+                "/foo/array/+5",
+                Error::InvalidIndex("+5".to_string()),
+            ),
+        ];
+
+        for (options, s, expected_error) in tests {
+            let mut value = value.clone();
+            let result = value.insert_at_with(&Pointer::new(s)?, 42, &options);
+
+            assert_eq!(result, Err(expected_error), "Insertion at '{}' with {:?}", s, options);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_inserts_value_in_json_array() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"arr": [1, 2, 3]});
+
+        let tests = [
+            ("/arr/1", Some(2.into())),
+            ("/arr/-", None),
+            ("/arr/4", None),
+            ("/arr/0", Some(1.into())),
+        ];
+
+        for (s, expected_old_value) in tests {
+            let old_value = value.insert_at(&Pointer::new(s)?, 42)?;
+
+            assert_eq!(old_value, expected_old_value, "Old value at '{}' JSON pointer", s);
+        }
+
+        assert_eq!(value, json!({"arr": [42, 42, 3, 42, 42]}));
+
+        let tests = [
+            ("/arr/6", Error::IndexOutOfBounds { index: 6, len: 5 }),
+            ("/arr/foo", Error::InvalidIndex("foo".to_string())),
+        ];
+
+        for (s, expected_error) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                value.insert_at(&pointer, 42),
+                Err(expected_error.clone()),
+                "Insertion at '{}' JSON pointer",
+                s
+            );
+            assert_eq!(
+                value.insert_at_strict(&pointer, 42),
+                Err(expected_error),
+                "Strict insertion at '{}' JSON pointer",
+                s
+            );
+        }
+
+        assert_eq!(
+            value.insert_at_strict(&Pointer::new("/arr/0")?, 0),
+            Err(Error::KeyAlreadyExists)
+        );
+
+        value.insert_at_strict(&Pointer::new("/arr/-")?, 0)?;
+
+        assert_eq!(value, json!({"arr": [42, 42, 3, 42, 42, 0]}));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_dispatches_insertion_on_parent_container_type() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({ "arr": [], "obj": {} });
+
+        for (s, expected_old_value) in [
+            ("/arr/0", None),
+            ("/arr/1", None),
+            ("/arr/1", Some(Value::from(3))),
+            ("/obj/1", None),
+        ] {
+            let old_value = value.insert_at(&Pointer::new(s)?, 3)?;
+
+            assert_eq!(old_value, expected_old_value, "Old value at '{}' JSON pointer", s);
+        }
+
+        let expected_value = json!({ "arr": [3, 3], "obj": { "1": 3 } });
+        assert_eq!(value, expected_value);
+
+        for s in ["/arr/1a", "/arr/-1", "/arr/ 1", "/arr/"] {
+            let token = Pointer::new(s)?.key().unwrap();
+
+            assert_eq!(
+                value.insert_at(&Pointer::new(s)?, 4),
+                Err(Error::InvalidIndex(token)),
+                "Insertion at '{}' JSON pointer",
+                s
+            );
+        }
+
+        assert_eq!(
+            value.insert_at(&Pointer::new("/arr/3")?, 4),
+            Err(Error::IndexOutOfBounds { index: 3, len: 2 })
+        );
+        assert_eq!(value, expected_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_non_canonical_json_array_indexes() -> Result<(), Error> {
+        let mut value = json!({ "arr": [1, 2] });
+        let expected_value = value.clone();
+
+        for token in ["01", "+1", "00", " 1", "1 "] {
+            let pointer = Pointer::new(format!("/arr/{}", token))?;
+            let array = value.pointer_mut(&Pointer::new("/arr")?).unwrap();
+
+            assert_eq!(array.lookup_token(token), None, "Lookup of '{}' token", token);
+            assert_eq!(
+                array.lookup_token_mut(token),
+                None,
+                "Mutable lookup of '{}' token",
+                token
+            );
+            assert_eq!(array.remove_token(token), None, "Removal of '{}' token", token);
+            assert_eq!(
+                ValueExt::insert(array, token.to_string(), 3),
+                Err(Error::InvalidIndex(token.to_string())),
+                "Insertion of '{}' token",
+                token
+            );
+            assert_eq!(
+                array.insert_strict(token.to_string(), 3),
+                Err(Error::InvalidIndex(token.to_string())),
+                "Strict insertion of '{}' token",
+                token
+            );
+            assert_eq!(
+                ValueExt::pointer(&value, &pointer),
+                None,
+                "Value at '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        assert_eq!(value, expected_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_locates_first_value_matching_predicate() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"foo": {"bar": [1, {"id": 42}]}, "a/b": {"id": 21}});
+
+        let tests = [
+            (Pointer::root(), value.clone()),
+            (Pointer::new("/foo/bar/1/id")?, 42.into()),
+            (Pointer::new("/a~1b/id")?, 21.into()),
+        ];
+
+        for (expected_pointer, expected_value) in tests {
+            assert_eq!(value.locate(|v| *v == expected_value), Some(expected_pointer));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_fails_to_locate_value_not_matching_predicate() {
+        let value = json!({"foo": "bar"});
+
+        assert_eq!(value.locate(|v| *v == 42), None);
+    }
+
+    #[test]
+    fn it_flattens_value_to_depth() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"foo": {"bar": {"zoo": 42}}, "array": [1, [2]], "empty": {}});
+
+        let tests = [
+            (0, vec![("", value.clone())]),
+            (
+                1,
+                vec![
+                    ("/foo", json!({"bar": {"zoo": 42}})),
+                    ("/array", json!([1, [2]])),
+                    ("/empty", json!({})),
+                ],
+            ),
+            (
+                2,
+                vec![
+                    ("/foo/bar", json!({"zoo": 42})),
+                    ("/array/0", 1.into()),
+                    ("/array/1", json!([2])),
+                    ("/empty", json!({})),
+                ],
+            ),
+            (
+                usize::MAX,
+                vec![
+                    ("/foo/bar/zoo", 42.into()),
+                    ("/array/0", 1.into()),
+                    ("/array/1/0", 2.into()),
+                    ("/empty", json!({})),
+                ],
+            ),
+        ];
+
+        for (depth, expected_values) in tests {
+            let values = value.flatten_to_depth(depth);
+            let expected_values = expected_values
+                .iter()
+                .map(|(s, value)| Ok((Pointer::new(*s)?, value)))
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            assert_eq!(values, expected_values, "Flatten to {} depth", depth);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_splits_json_pointer_target() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"foo": {"bar": "zoo"}, "array": [1, 2]});
+
+        let tests = [
+            ("/foo/bar", json!({"bar": "zoo"}), "bar"),
+            ("/foo/a~1b", json!({"bar": "zoo"}), "a/b"),
+            ("/array/0", json!([1, 2]), "0"),
+            ("/test", value.clone(), "test"),
+        ];
+
+        for (s, mut expected_parent_value, expected_key) in tests {
+            let (parent_value, key) = value.split_target_mut(&Pointer::new(s)?)?;
+
+            assert_eq!(
+                parent_value, &mut expected_parent_value,
+                "Parent of '{}' JSON pointer",
+                s
+            );
+            assert_eq!(key, expected_key, "Key of '{}' JSON pointer", s);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_fails_to_split_json_pointer_target() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"foo": {"bar": "zoo"}});
+
+        let tests = [
+            (Pointer::root(), Error::UnexpectedRootPointer),
+            (Pointer::new("/foo/not_existing/zoo")?, Error::KeyNotFound),
+            (Pointer::new("/foo/bar/zoo")?, Error::NotAContainer),
+        ];
+
+        for (pointer, expected_error) in tests {
+            let result = value.split_target_mut(&pointer);
+
+            assert_eq!(result, Err(expected_error), "Target of '{}' JSON pointer", pointer);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_looks_up_values_by_compiled_json_pointer() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"foo": "bar", "zoo": {"id": [1, 2, 3]}, "a/b": 42});
+        let tests = [
+            ("", Some(value.clone())),
+            ("/foo", Some("bar".into())),
+            ("/zoo/id/0", Some(1.into())),
+            ("/a~1b", Some(42.into())),
+            ("/zoo/id/3", None),
+            ("/foo/bar", None),
+        ];
+
+        for (s, mut expected_value) in tests {
+            let pointer = CompiledPointer::new(&Pointer::new(s)?);
+
+            let pointee_value = value.pointer_compiled(&pointer);
+            assert_eq!(pointee_value, expected_value.as_ref(), "Lookup of '{}' JSON pointer", s);
+
+            let pointee_value = value.pointer_compiled_mut(&pointer);
+            assert_eq!(pointee_value, expected_value.as_mut(), "Lookup of '{}' JSON pointer", s);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_looks_up_typed_values_by_json_pointer() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"str": "foo", "int": -42, "float": 1.5, "bool": true, "null": null});
+
+        let tests = [
+            ("/str", Some("foo"), None, None, None),
+            ("/int", None, Some(-42), Some(-42.0), None),
+            ("/float", None, None, Some(1.5), None),
+            ("/bool", None, None, None, Some(true)),
+            ("/null", None, None, None, None),
+            ("/not_existing", None, None, None, None),
+        ];
+
+        for (s, expected_str, expected_i64, expected_f64, expected_bool) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(value.get_str(&pointer), expected_str, "String at '{}'", s);
+            assert_eq!(value.get_i64(&pointer), expected_i64, "`i64` at '{}'", s);
+            assert_eq!(value.get_f64(&pointer), expected_f64, "`f64` at '{}'", s);
+            assert_eq!(value.get_bool(&pointer), expected_bool, "`bool` at '{}'", s);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_renders_placeholders() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({
+            "server": {"host": "localhost", "port": 8080, "secure": false},
+            "url": "http://${/server/host}:${/server/port}/",
+            "nested": [{"message": "secure=${/server/secure}, unclosed=${"}],
+            "template": "${/url}"
+        });
+
+        value.render_placeholders()?;
+
+        assert_eq!(
+            value,
+            json!({
+                "server": {"host": "localhost", "port": 8080, "secure": false},
+                "url": "http://localhost:8080/",
+                "nested": [{"message": "secure=false, unclosed=${"}],
+                "template": "http://${/server/host}:${/server/port}/"
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_fails_to_render_unresolved_placeholders() {
+        let tests = [
+            (
+                json!({"url": "http://${/host}/"}),
+                Error::UnresolvedPlaceholder("/host".to_string()),
+            ),
+            (
+                json!({"url": "http://${/host}/", "host": null}),
+                Error::UnresolvedPlaceholder("/host".to_string()),
+            ),
+            (
+                json!({"url": "http://${host}/", "host": "localhost"}),
+                Error::MissingLeadingBackslash,
+            ),
+        ];
+
+        for (mut value, expected_error) in tests {
+            let expected_value = value.clone();
+
+            assert_eq!(value.render_placeholders(), Err(expected_error));
+            assert_eq!(value, expected_value);
+        }
+    }
+
+    #[test]
+    fn it_looks_up_values_by_json_pointer_with_negative_index() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"array": [1, 2, {"id": 3}], "-1": "foo"});
+        let tests = [
+            ("/array/-1", Some(json!({"id": 3}))),
+            ("/array/-1/id", Some(3.into())),
+            ("/array/-3", Some(1.into())),
+            ("/array/0", Some(1.into())),
+            ("/-1", Some("foo".into())),
+            ("/array/-4", None),
+            ("/array/-0", None),
+            ("/array/-", None),
+            ("/array/-+1", None),
+            ("/array/-01", None),
+            ("/array/- 1", None),
+        ];
+
+        for (s, expected_value) in tests {
+            let pointee_value = value.pointer_ext(&Pointer::new(s)?);
+
+            assert_eq!(pointee_value, expected_value.as_ref(), "Lookup of '{}' JSON pointer", s);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_children_json_pointers() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"foo": {"bar": 1, "a/b": 2}, "array": [1, 2, 3], "empty": [], "zoo": "scalar"});
+        let tests = [
+            ("/foo", Some(vec!["/foo/bar", "/foo/a~1b"])),
+            ("/array", Some(vec!["/array/0", "/array/1", "/array/2"])),
+            ("/empty", Some(vec![])),
+            ("/zoo", None),
+            ("/not_existing", None),
+        ];
+
+        for (s, expected_children) in tests {
+            let expected_children = expected_children
+                .map(|pointers| pointers.into_iter().map(Pointer::new).collect::<Result<Vec<_>, _>>())
+                .transpose()?;
+
+            assert_eq!(
+                value.children_at(&Pointer::new(s)?),
+                expected_children,
+                "Children of '{}' JSON pointer",
+                s
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_looks_up_values_by_bounded_json_pointer() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"foo": {"bar": {"zoo": 42}}});
+        let tests = [
+            ("", 0, Ok(Some(value.clone()))),
+            ("/foo/bar/zoo", 3, Ok(Some(42.into()))),
+            ("/foo/not_existing", 3, Ok(None)),
+            ("/foo/bar/zoo", 2, Err(Error::StepLimitExceeded)),
+            ("/a/b/c/d/e/f/g/h", 4, Err(Error::StepLimitExceeded)),
+        ];
+
+        for (s, max_steps, expected_result) in tests {
+            let result = value.pointer_bounded(&Pointer::new(s)?, max_steps);
+
+            assert_eq!(
+                result,
+                expected_result.as_ref().map(Option::as_ref).map_err(Clone::clone),
+                "Lookup of '{}' JSON pointer within {} steps",
+                s,
+                max_steps
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_computes_content_hash_excluding_json_pointers() -> Result<(), Box<dyn std::error::Error>> {
+        let exclude = [Pointer::new("/meta/timestamp")?, Pointer::new("/items/1")?];
+        let value = json!({"meta": {"timestamp": 1, "id": "a"}, "items": [1, 2, 3]});
+        let hash = value.content_hash_excluding(&exclude);
+
+        let same_hash_values = [
+            json!({"meta": {"timestamp": 2, "id": "a"}, "items": [1, 2, 3]}),
+            json!({"meta": {"id": "a"}, "items": [1, 2, 3]}),
+            json!({"items": [1, 42, 3], "meta": {"id": "a", "timestamp": 3}}),
+        ];
+
+        for value in same_hash_values {
+            assert_eq!(
+                value.content_hash_excluding(&exclude),
+                hash,
+                "Hash of '{}' is unchanged",
+                value
+            );
+        }
+
+        let distinct_hash_values = [
+            json!({"meta": {"timestamp": 1, "id": "b"}, "items": [1, 2, 3]}),
+            json!({"meta": {"timestamp": 1, "id": "a"}, "items": [1, 2, 4]}),
+            json!({"meta": {"timestamp": 1, "id": "a"}, "items": [1, 3]}),
+            json!({"meta": {"timestamp": 1, "id": "a"}, "items": [1, 2, 3], "new": null}),
+            json!({"meta": {"timestamp": 1, "id": "a"}, "items": [1, 2, "3"]}),
+        ];
+
+        for value in distinct_hash_values {
+            assert_ne!(
+                value.content_hash_excluding(&exclude),
+                hash,
+                "Hash of '{}' is changed",
+                value
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_records_successful_json_pointer_accesses() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"foo": "bar", "zoo": {"id": 42}});
+        let mut audit = Vec::new();
+
+        let tests = [
+            ("/foo", Some("bar".into())),
+            ("/not_existing", None),
+            ("/zoo/id", Some(42.into())),
+            ("/zoo/id/0", None),
+            ("", Some(value.clone())),
+        ];
+
+        for (s, expected_value) in tests {
+            let pointee_value = value.pointer_audited(&Pointer::new(s)?, &mut audit);
+
+            assert_eq!(pointee_value, expected_value.as_ref(), "Lookup of '{}' JSON pointer", s);
+        }
+
+        assert_eq!(
+            audit,
+            vec![Pointer::new("/foo")?, Pointer::new("/zoo/id")?, Pointer::root()]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_sets_values_matching_pattern() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"items": [{"active": false}, {"id": 1}, {"active": null}], "active": false});
+
+        let tests = [
+            (
+                "/items/*/active",
+                2,
+                json!({"items": [{"active": true}, {"id": 1}, {"active": true}], "active": false}),
+            ),
+            (
+                "/**/active",
+                3,
+                json!({"items": [{"active": true}, {"id": 1}, {"active": true}], "active": true}),
+            ),
+            ("/items/*", 3, json!({"items": [true, true, true], "active": false})),
+            ("/not_existing/*", 0, value.clone()),
+        ];
+
+        for (s, expected_count, expected_value) in tests {
+            let mut value = value.clone();
+            let count = value.set_where_matches(&PointerPattern::new(s)?, true.into());
+
+            assert_eq!(count, expected_count, "Values matching '{}'", s);
+            assert_eq!(value, expected_value, "Values matching '{}'", s);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_clones_pointee_value() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"a": {"b": [1, 2]}});
+
+        let mut cloned_value = value.clone_at(&Pointer::new("/a")?).ok_or("missing '/a' JSON value")?;
+        cloned_value.insert_at(&Pointer::new("/c")?, true)?;
+
+        assert_eq!(cloned_value, json!({"b": [1, 2], "c": true}));
+        assert_eq!(value, json!({"a": {"b": [1, 2]}}));
+        assert_eq!(value.clone_at(&Pointer::new("/a/c")?), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_manipulates_occupied_entry() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"a": {"b": 1, "c": [1, 2, 3]}});
+
+        match value.entry_at(&Pointer::new("/a/b")?)? {
+            PointerEntry::Occupied(mut entry) => *entry.get_mut() = 2.into(),
+            PointerEntry::Vacant(_) => panic!("'/a/b' entry is occupied"),
+        }
+
+        assert_eq!(value, json!({"a": {"b": 2, "c": [1, 2, 3]}}));
+
+        match value.entry_at(&Pointer::new("/a/c/1")?)? {
+            PointerEntry::Occupied(entry) => assert_eq!(entry.remove(), 2),
+            PointerEntry::Vacant(_) => panic!("'/a/c/1' entry is occupied"),
+        }
+
+        assert_eq!(value, json!({"a": {"b": 2, "c": [1, 3]}}));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_manipulates_vacant_entry() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"a": {"b": 1}});
+
+        match value.entry_at(&Pointer::new("/a/c")?)? {
+            PointerEntry::Vacant(entry) => *entry.insert(true)? = false.into(),
+            PointerEntry::Occupied(_) => panic!("'/a/c' entry is vacant"),
+        }
+
+        assert_eq!(value, json!({"a": {"b": 1, "c": false}}));
+        assert_eq!(
+            value.entry_at(&Pointer::root()).err(),
+            Some(Error::UnexpectedRootPointer)
+        );
+        assert_eq!(value.entry_at(&Pointer::new("/b/c")?).err(), Some(Error::KeyNotFound));
+        assert_eq!(
+            value.entry_at(&Pointer::new("/a/b/c")?).err(),
+            Some(Error::NotAContainer)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_inserts_value_within_node_cap() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"a": {"b": 1}});
+
+        assert_eq!(
+            value.insert_at_capped(&Pointer::new("/a/c")?, json!([1, 2]), 6),
+            Ok(None)
+        );
+        assert_eq!(
+            value.insert_at_capped(&Pointer::new("/a/d")?, json!([1, 2]), 8),
+            Err(Error::DocumentTooLarge)
+        );
+        assert_eq!(value, json!({"a": {"b": 1, "c": [1, 2]}}));
+
+        assert_eq!(
+            value.insert_at_capped(&Pointer::new("/a/c")?, json!(["x", "y"]), 6),
+            Ok(Some(json!([1, 2])))
+        );
+        assert_eq!(
+            value.insert_at_capped(&Pointer::new("/a/b")?, json!([1]), 6),
+            Err(Error::DocumentTooLarge)
+        );
+        assert_eq!(value, json!({"a": {"b": 1, "c": ["x", "y"]}}));
+        assert_eq!(
+            value.insert_at_capped(&Pointer::root(), 42, 1),
+            Ok(Some(json!({"a": {"b": 1, "c": ["x", "y"]}})))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_applies_patch_with_base() -> Result<(), Box<dyn std::error::Error>> {
+        let base = json!({"a": 1, "b": {"c": 2}, "d": [1, 2]});
+        let patch = Patch::new(vec![
+            PatchOperation::Replace {
+                path: Pointer::new("/a")?,
+                value: 10.into(),
+            },
+            PatchOperation::Add {
+                path: Pointer::new("/b/e")?,
+                value: true.into(),
+            },
+            PatchOperation::Remove {
+                path: Pointer::new("/b/c")?,
+            },
+        ]);
+
+        let mut value = base.clone();
+
+        assert_eq!(value.apply_with_base(&patch, &base)?, Vec::<Pointer>::new());
+        assert_eq!(value, json!({"a": 10, "b": {"e": true}, "d": [1, 2]}));
+
+        let mut value = json!({"a": 1, "b": {"c": 3}, "d": [1, 2]});
+
+        assert_eq!(value.apply_with_base(&patch, &base)?, vec![Pointer::new("/b/c")?]);
+        assert_eq!(value, json!({"a": 10, "b": {"e": true}, "d": [1, 2]}));
+
+        let mut value = json!({"a": 1});
+
+        assert_eq!(value.apply_with_base(&patch, &base), Err(Error::KeyNotFound));
+        assert_eq!(value, json!({"a": 1}));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_resolves_value_through_layers() -> Result<(), Box<dyn std::error::Error>> {
+        let layers = [
+            json!({"db": {"host": "localhost", "port": 5432}, "debug": false}),
+            json!({"db": {"host": "db.internal"}}),
+            json!({"debug": true}),
+        ];
+
+        let tests = [
+            ("/db/host", Some("db.internal".into())),
+            ("/db/port", Some(5432.into())),
+            ("/debug", Some(true.into())),
+            ("/missing", None),
+        ];
+
+        for (s, expected_value) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                Value::resolve_layered(&layers, &pointer),
+                expected_value.as_ref(),
+                "Layered value of '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_dumps_values_sorted_by_json_pointers() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({
+            "z": {"b": "a \"quoted\"\nstring", "a": 1.5},
+            "a": [true, null, {}, []],
+            "y": -3
+        });
+
+        let expected_dump = "/y\t-3\n\
+                             /a/0\ttrue\n\
+                             /a/1\tnull\n\
+                             /a/2\t{}\n\
+                             /a/3\t[]\n\
+                             /z/a\t1.5\n\
+                             /z/b\t\"a \\\"quoted\\\"\\nstring\"\n";
+
+        assert_eq!(value.to_pointer_dump(), expected_dump);
+        assert_eq!(value.clone().to_pointer_dump(), value.to_pointer_dump());
+        assert_eq!(json!({}).to_pointer_dump(), "\t{}\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_array_length() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"empty": [], "one": [1], "many": [1, 2, 3, [4, 5]], "object": {"a": 1}, "scalar": 42});
+
+        let tests = [
+            ("/empty", Some(0)),
+            ("/one", Some(1)),
+            ("/many", Some(4)),
+            ("/many/3", Some(2)),
+            ("/object", None),
+            ("/scalar", None),
+            ("/missing", None),
+            ("", None),
+        ];
+
+        for (s, expected_len) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                value.array_len_at(&pointer),
+                expected_len,
+                "Array length at '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_coerced_values() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({
+            "bool": true,
+            "true": "true",
+            "false": "false",
+            "one": "1",
+            "zero": "0",
+            "int": -42,
+            "int_str": "-42",
+            "float": 1.5,
+            "float_str": "-2e3",
+            "garbage": "yes",
+            "padded": " 1",
+            "inf": "inf",
+            "null": null
+        });
+
+        let tests = [
+            ("/bool", Some(true), None, None),
+            ("/true", Some(true), None, None),
+            ("/false", Some(false), None, None),
+            ("/one", Some(true), Some(1), Some(1.0)),
+            ("/zero", Some(false), Some(0), Some(0.0)),
+            ("/int", None, Some(-42), Some(-42.0)),
+            ("/int_str", None, Some(-42), Some(-42.0)),
+            ("/float", None, None, Some(1.5)),
+            ("/float_str", None, None, Some(-2000.0)),
+            ("/garbage", None, None, None),
+            ("/padded", None, None, None),
+            ("/inf", None, None, None),
+            ("/null", None, None, None),
+            ("/missing", None, None, None),
+        ];
+
+        for (s, expected_bool, expected_i64, expected_f64) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                value.get_coerced_bool(&pointer),
+                expected_bool,
+                "Coerced bool at '{}' JSON pointer",
+                pointer
+            );
+            assert_eq!(
+                value.get_coerced_i64(&pointer),
+                expected_i64,
+                "Coerced i64 at '{}' JSON pointer",
+                pointer
+            );
+            assert_eq!(
+                value.get_coerced_f64(&pointer),
+                expected_f64,
+                "Coerced f64 at '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_shards_top_level_values() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"a": {"b": 1}, "c": [2]});
+        let shards = value.shard_top_level();
+
+        assert_eq!(
+            shards,
+            vec![
+                (Pointer::new("/a")?, &json!({"b": 1})),
+                (Pointer::new("/c")?, &json!([2])),
+            ]
+        );
+
+        let value = json!([true, {"a": 1}]);
+        let shards = value.shard_top_level();
+
+        assert_eq!(
+            shards,
+            vec![
+                (Pointer::new("/0")?, &Value::from(true)),
+                (Pointer::new("/1")?, &json!({"a": 1})),
+            ]
+        );
+        assert!(Value::from(42).shard_top_level().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_touches_json_pointers() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"a": {"b": 1}, "c": [1]});
+
+        value.touch_at(&Pointer::new("/a/b")?)?;
+        value.touch_at(&Pointer::new("/c/0")?)?;
+        value.touch_at(&Pointer::root())?;
+
+        assert_eq!(value, json!({"a": {"b": 1}, "c": [1]}));
+
+        value.touch_at(&Pointer::new("/a/d")?)?;
+        value.touch_at(&Pointer::new("/e/f/g")?)?;
+
+        assert_eq!(
+            value,
+            json!({"a": {"b": 1, "d": null}, "c": [1], "e": {"f": {"g": null}}})
+        );
+        assert_eq!(
+            value.touch_at(&Pointer::new("/a/b/c")?),
+            Err(Error::UnsupportedInsertion)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_computes_changed_json_pointers() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({
+            "same": {"a": 1, "b": [1, 2]},
+            "modified": {"a": 1, "b": "foo"},
+            "removed": {"a": [1, 2], "b": true},
+            "replaced": 42,
+            "array": [1, 2, 3],
+            "emptied": {"a": 1}
+        });
+        let other = json!({
+            "same": {"a": 1, "b": [1, 2]},
+            "modified": {"a": 1, "b": "bar"},
+            "removed": {"b": true},
+            "replaced": {"a": null},
+            "array": [1, 4],
+            "emptied": {},
+            "added": {"a": {"b": 1}}
+        });
+
+        let expected_pointers = [
+            "/emptied",
+            "/replaced",
+            "/array/1",
+            "/array/2",
+            "/emptied/a",
+            "/modified/b",
+            "/replaced/a",
+            "/added/a/b",
+            "/removed/a/0",
+            "/removed/a/1",
+        ]
+        .into_iter()
+        .map(Pointer::new)
+        .collect::<Result<Vec<_>, _>>()?;
+
+        assert_eq!(value.changed_pointers(&other), expected_pointers);
+        assert_eq!(other.changed_pointers(&value), expected_pointers);
+        assert!(value.changed_pointers(&value).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_masks_values_matching_patterns() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({
+            "password": "root",
+            "users": [
+                {"name": "foo", "password": "bar", "token": {"value": "baz"}},
+                {"name": "zoo", "password": {"hash": "qux"}}
+            ]
+        });
+        let patterns = [
+            PointerPattern::new("/**/password")?,
+            PointerPattern::new("/users/*/token/*")?,
+        ];
+
+        let masked_value = value.masked(&patterns, &"***".into());
+
+        assert_eq!(
+            masked_value,
+            json!({
+                "password": "***",
+                "users": [
+                    {"name": "foo", "password": "***", "token": {"value": "***"}},
+                    {"name": "zoo", "password": "***"}
+                ]
+            })
+        );
+        assert_eq!(value.get_str(&Pointer::new("/password")?), Some("root"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_requires_json_pointers() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"name": "foo", "address": {"city": null}, "tags": ["a"]});
+
+        let required = [
+            Pointer::root(),
+            Pointer::new("/name")?,
+            Pointer::new("/address/city")?,
+            Pointer::new("/tags/0")?,
+        ];
+
+        assert_eq!(value.require_all(&required), Ok(()));
+        assert_eq!(value.require_all(&[]), Ok(()));
+
+        let required = [
+            Pointer::new("/name")?,
+            Pointer::new("/age")?,
+            Pointer::new("/address/city")?,
+            Pointer::new("/address/zip")?,
+            Pointer::new("/tags/1")?,
+        ];
+
+        assert_eq!(
+            value.require_all(&required),
+            Err(vec![
+                Pointer::new("/age")?,
+                Pointer::new("/address/zip")?,
+                Pointer::new("/tags/1")?,
+            ])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_compacts_json_arrays() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({
+            "items": [null, 1, null, null, {"a": null, "b": [null, 2]}, null],
+            "nested": {"items": [[null], null, "foo"]},
+            "null": null
+        });
+
+        let mut compacted_value = value.clone();
+        compacted_value.compact_arrays(false);
+
+        assert_eq!(compacted_value, value);
+
+        compacted_value.compact_arrays(true);
+
+        assert_eq!(
+            compacted_value,
+            json!({
+                "items": [1, {"a": null, "b": [2]}],
+                "nested": {"items": [[], "foo"]},
+                "null": null
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_sorts_json_array_by_relative_json_pointer() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({
+            "records": [
+                {"id": 3, "name": "c"},
+                {"name": "missing"},
+                {"id": 1, "name": "a"},
+                {"id": "2", "name": "string"},
+                {"id": 2.5, "name": "float"},
+                {"id": null, "name": "null"},
+                {"id": 1, "name": "b"}
+            ]
+        });
+
+        value.sort_array_at(&Pointer::new("/records")?, &Pointer::new("/id")?)?;
+
+        assert_eq!(
+            value,
+            json!({
+                "records": [
+                    {"id": null, "name": "null"},
+                    {"id": 1, "name": "a"},
+                    {"id": 1, "name": "b"},
+                    {"id": 2.5, "name": "float"},
+                    {"id": 3, "name": "c"},
+                    {"id": "2", "name": "string"},
+                    {"name": "missing"}
+                ]
+            })
+        );
+
+        let mut value = json!({"scores": [3, 1, 2], "object": {}});
+
+        value.sort_array_at(&Pointer::new("/scores")?, &Pointer::root())?;
+
+        assert_eq!(value, json!({"scores": [1, 2, 3], "object": {}}));
+        assert_eq!(
+            value.sort_array_at(&Pointer::new("/object")?, &Pointer::root()),
+            Err(Error::NotAnArray)
+        );
+        assert_eq!(
+            value.sort_array_at(&Pointer::new("/missing")?, &Pointer::root()),
+            Err(Error::KeyNotFound)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_updates_each_pointee_value() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"a": 1, "b": {"c": [1, 2]}});
+
+        let results = value.update_each(vec![
+            (
+                Pointer::new("/a")?,
+                Box::new(|value: &mut Value| *value = "updated".into()),
+            ),
+            (
+                Pointer::new("/missing/c")?,
+                Box::new(|value: &mut Value| *value = "missing".into()),
+            ),
+            (
+                Pointer::new("/b/c")?,
+                Box::new(|value: &mut Value| {
+                    value.insert_at(&Pointer::new("/-").unwrap(), 3).unwrap();
+                }),
+            ),
+        ]);
+
+        assert_eq!(results, vec![Ok(()), Err(Error::KeyNotFound), Ok(())]);
+        assert_eq!(value, json!({"a": "updated", "b": {"c": [1, 2, 3]}}));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_str_strictly() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"str": "foo", "num": 42, "arr": [1], "obj": {"a": null}, "null": null});
+
+        let tests = [
+            ("/str", Ok("foo")),
+            (
+                "/num",
+                Err(Error::TypeMismatch {
+                    expected: "string",
+                    found: "number",
+                }),
+            ),
+            (
+                "/arr",
+                Err(Error::TypeMismatch {
+                    expected: "string",
+                    found: "array",
+                }),
+            ),
+            (
+                "/obj",
+                Err(Error::TypeMismatch {
+                    expected: "string",
+                    found: "object",
+                }),
+            ),
+            (
+                "/null",
+                Err(Error::TypeMismatch {
+                    expected: "string",
+                    found: "null",
+                }),
+            ),
+            ("/missing", Err(Error::KeyNotFound)),
+        ];
+
+        for (s, expected_result) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                value.get_str_strict(&pointer),
+                expected_result,
+                "Strict string at '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_inserts_many_values() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"a": 1});
+
+        let count = value.insert_many(vec![
+            (Pointer::new("/b/c")?, Value::from(2)),
+            (Pointer::new("/a")?, Value::from(3)),
+            (Pointer::new("/d")?, json!([])),
+            (Pointer::new("/d/0")?, Value::from(4)),
+        ])?;
+
+        assert_eq!(count, 4);
+        assert_eq!(value, json!({"a": 3, "b": {"c": 2}, "d": [4]}));
+
+        let result = value.insert_many(vec![
+            (Pointer::new("/e")?, Value::from(5)),
+            (Pointer::new("/a/f")?, Value::from(6)),
+            (Pointer::new("/g")?, Value::from(7)),
+        ]);
+
+        assert_eq!(
+            result,
+            Err(Error::InvalidListEntry {
+                position: 1,
+                source: Box::new(Error::UnsupportedInsertion),
+            })
+        );
+        assert_eq!(value, json!({"a": 3, "b": {"c": 2}, "d": [4], "e": 5}));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_appends_values_with_dash_token() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"arr": []});
+        let pointer = Pointer::new("/arr/-")?;
+
+        for i in 0..3 {
+            assert_eq!(value.insert_at(&pointer, i)?, None);
+        }
+
+        assert_eq!(value, json!({"arr": [0, 1, 2]}));
+        assert_eq!(ValueExt::pointer(&value, &pointer), None);
+        assert_eq!(ValueExt::pointer_mut(&mut value, &pointer), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_diffs_json_values_under_prefix() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"a": 1, "b": {"c": 2, "d": [1, 2, 3], "e": {"f": true}, "g": "foo"}});
+        let other = json!({"a": 2, "b": {"c": 2, "d": [1, 4], "e": [], "h": null}});
+
+        assert_eq!(value.diff_under(&other, &Pointer::new("/b/c")?), vec![]);
+        assert_eq!(
+            value.diff_under(&other, &Pointer::new("/b/d")?),
+            vec![
+                PatchOperation::Replace {
+                    path: Pointer::new("/b/d/1")?,
+                    value: Value::from(4),
+                },
+                PatchOperation::Remove {
+                    path: Pointer::new("/b/d/2")?,
+                },
+            ]
+        );
+        assert_eq!(
+            value.diff_under(&other, &Pointer::new("/b/g")?),
+            vec![PatchOperation::Remove {
+                path: Pointer::new("/b/g")?,
+            }]
+        );
+        assert_eq!(
+            value.diff_under(&other, &Pointer::new("/b/h")?),
+            vec![PatchOperation::Add {
+                path: Pointer::new("/b/h")?,
+                value: Value::Static(StaticNode::Null),
+            }]
+        );
+        assert_eq!(value.diff_under(&other, &Pointer::new("/b/missing")?), vec![]);
+
+        let mut patched_value = value.clone();
+
+        for operation in value.diff_under(&other, &Pointer::new("/b")?) {
+            assert!(Pointer::new("/b")?.is_ancestor_of(operation.path()));
+
+            operation.apply(&mut patched_value)?;
+        }
+
+        assert_eq!(
+            ValueExt::pointer(&patched_value, &Pointer::new("/b")?),
+            ValueExt::pointer(&other, &Pointer::new("/b")?)
+        );
+        assert_eq!(
+            ValueExt::pointer(&patched_value, &Pointer::new("/a")?),
+            Some(&Value::from(1))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_looks_up_values_of_json_type() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"a": "foo", "b": [1, "bar", {"c": "zoo", "d": null}], "e": true, "f": 1.5});
+
+        let tests = [
+            (JsonType::String, vec!["/a", "/b/1", "/b/2/c"]),
+            (JsonType::Number, vec!["/b/0", "/f"]),
+            (JsonType::Null, vec!["/b/2/d"]),
+            (JsonType::Boolean, vec!["/e"]),
+            (JsonType::Array, vec!["/b"]),
+            (JsonType::Object, vec!["", "/b/2"]),
+        ];
+
+        for (ty, expected_pointers) in tests {
+            let mut pointers = value
+                .values_of_type(ty)
+                .into_iter()
+                .map(|(pointer, found_value)| {
+                    assert_eq!(found_value.json_type(), ty);
+                    assert_eq!(value.type_at(&pointer), Some(ty));
+
+                    pointer
+                })
+                .collect::<Vec<_>>();
+            pointers.sort();
+
+            let mut expected_pointers = expected_pointers
+                .into_iter()
+                .map(Pointer::new)
+                .collect::<Result<Vec<_>, _>>()?;
+            expected_pointers.sort();
+
+            assert_eq!(pointers, expected_pointers, "Pointers of {:?} JSON values", ty);
+        }
+
+        assert_eq!(value.type_at(&Pointer::new("/missing")?), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_applies_patch() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"foo": ["all", "grass", "cows", "eat"], "bar": {"baz": 1}});
+        let patch = Patch::new(vec![
+            PatchOperation::Add {
+                path: Pointer::new("/foo/0")?,
+                value: "we".into(),
+            },
+            PatchOperation::Move {
+                from: Pointer::new("/foo/2")?,
+                path: Pointer::new("/foo/-")?,
+            },
+            PatchOperation::Copy {
+                from: Pointer::new("/bar")?,
+                path: Pointer::new("/qux")?,
+            },
+            PatchOperation::Replace {
+                path: Pointer::new("/bar/baz")?,
+                value: 2.into(),
+            },
+            PatchOperation::Test {
+                path: Pointer::new("/qux/baz")?,
+                value: 1.into(),
+            },
+        ]);
+
+        value.apply_patch(&patch)?;
+
+        assert_eq!(
+            value,
+            json!({"foo": ["we", "all", "cows", "eat", "grass"], "bar": {"baz": 2}, "qux": {"baz": 1}})
+        );
+
+        let failing_patch = Patch::new(vec![
+            PatchOperation::Remove {
+                path: Pointer::new("/qux")?,
+            },
+            PatchOperation::Add {
+                path: Pointer::new("/foo/6")?,
+                value: "late".into(),
+            },
+        ]);
+        let expected_value = value.clone();
+
+        assert_eq!(
+            value.apply_patch(&failing_patch),
+            Err(Error::IndexOutOfBounds { index: 6, len: 5 })
+        );
+        assert_eq!(value, expected_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_applies_merge_patches() {
+        let tests = [
+            (json!({"a": "b"}), json!({"a": "c"}), json!({"a": "c"})),
+            (json!({"a": "b", "b": "c"}), json!({"a": null}), json!({"b": "c"})),
+            (
+                json!({"a": {"b": "c"}}),
+                json!({"a": {"b": "d", "c": null}}),
+                json!({"a": {"b": "d"}}),
+            ),
+            (json!({"a": "b"}), json!(["c"]), json!(["c"])),
+            (json!([1, 2]), json!({"a": "b", "c": null}), json!({"a": "b"})),
+            (json!({}), json!({"a": {"bb": {"ccc": null}}}), json!({"a": {"bb": {}}})),
+        ];
+
+        for (value, patch, expected_value) in tests {
+            let mut merged_value = value.clone();
+
+            merged_value.merge_patch(&patch);
+
+            assert_eq!(merged_value, expected_value, "Merge of {} patch into {}", patch, value);
+        }
+    }
+
+    #[test]
+    fn it_moves_value_if_present() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"old": {"name": "foo"}, "arr": [1, 2, 3], "new": {}});
+
+        assert!(value.move_if_present(&Pointer::new("/old/name")?, &Pointer::new("/new/name")?)?);
+        assert!(value.move_if_present(&Pointer::new("/arr/0")?, &Pointer::new("/arr/-")?)?);
+        assert!(!value.move_if_present(&Pointer::new("/old/name")?, &Pointer::new("/new/other")?)?);
+        assert!(!value.move_if_present(&Pointer::new("/missing/a")?, &Pointer::new("/b")?)?);
+        assert_eq!(value, json!({"old": {}, "arr": [2, 3, 1], "new": {"name": "foo"}}));
+
+        let tests = [
+            ("/new", "/new/name/child", Error::MoveIntoDescendant),
+            ("/missing", "/missing/child", Error::MoveIntoDescendant),
+            ("/arr/0", "/missing/child", Error::KeyNotFound),
+            ("/new/name", "/arr/9", Error::IndexOutOfBounds { index: 9, len: 3 }),
+        ];
+
+        for (from, to, expected_error) in tests {
+            let mut moved_value = value.clone();
+
+            assert_eq!(
+                moved_value.move_if_present(&Pointer::new(from)?, &Pointer::new(to)?),
+                Err(expected_error),
+                "Move from '{}' to '{}' JSON pointer",
+                from,
+                to
+            );
+            assert_eq!(moved_value, value);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_expands_shape_templates() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({
+            "name": "root",
+            "items": [
+                {"name": "foo", "tags": [{"name": "a"}]},
+                {"id": 1},
+                {"name": "bar"}
+            ]
+        });
+
+        let tests = [
+            ("/items/*/name", vec!["/items/0/name", "/items/2/name"]),
+            (
+                "/**/name",
+                vec!["/name", "/items/0/name", "/items/0/tags/0/name", "/items/2/name"],
+            ),
+            ("/items/*/tags/*/name", vec!["/items/0/tags/0/name"]),
+            ("/items/1", vec!["/items/1"]),
+            ("/items/*/missing", vec![]),
+            ("items/*", vec![]),
+        ];
+
+        for (shape, expected_pointers) in tests {
+            let mut pointers = value.expand_shape(shape);
+            pointers.sort();
+
+            let mut expected_pointers = expected_pointers
+                .into_iter()
+                .map(Pointer::new)
+                .collect::<Result<Vec<_>, _>>()?;
+            expected_pointers.sort();
+
+            assert_eq!(pointers, expected_pointers, "Expansion of '{}' shape template", shape);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_inserts_value_recursively() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({});
+
+        assert_eq!(value.insert_at_recursive(&Pointer::new("/a/b/c/d")?, 1)?, None);
+        assert_eq!(value.insert_at_recursive(&Pointer::new("/a/b/e")?, 2)?, None);
+        assert_eq!(
+            value.insert_at_recursive(&Pointer::new("/a/b/e")?, 3)?,
+            Some(Value::from(2))
+        );
+        assert_eq!(value, json!({"a": {"b": {"c": {"d": 1}, "e": 3}}}));
+
+        let tests = [
+            ("/a/b/e/f", Error::UnsupportedInsertion),
+            ("/a/b/c/d/e/f", Error::UnsupportedInsertion),
+        ];
+
+        for (s, expected_error) in tests {
+            let mut inserted_value = value.clone();
+
+            assert_eq!(
+                inserted_value.insert_at_recursive(&Pointer::new(s)?, 4),
+                Err(expected_error),
+                "Recursive insertion at '{}' JSON pointer",
+                s
+            );
+            assert_eq!(inserted_value, value);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_computes_subtree_hashes() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"a": {"b": [1, {"c": 2}]}, "d": {"e": "foo"}, "f/g": [], "h": 3});
+        let other = json!({"a": {"b": [1, {"c": 3}]}, "d": {"e": "foo"}, "f/g": [], "h": 3});
+
+        let hashes = value.subtree_hashes();
+        let other_hashes = other.subtree_hashes();
+
+        assert_eq!(
+            hashes.keys().collect::<Vec<_>>(),
+            vec!["", "/a", "/a/b", "/a/b/1", "/d", "/f~1g"]
+        );
+        assert_eq!(
+            hashes.keys().collect::<Vec<_>>(),
+            other_hashes.keys().collect::<Vec<_>>()
+        );
+
+        for (pointer, hash) in &hashes {
+            let is_changed = ["", "/a", "/a/b", "/a/b/1"].contains(&pointer.as_str());
+
+            assert_eq!(
+                *hash != other_hashes[pointer],
+                is_changed,
+                "Hash of '{}' JSON pointer subtree",
+                pointer
+            );
+            assert_eq!(
+                *hash,
+                ValueExt::pointer(&value, &Pointer::new(pointer.as_str())?)
+                    .unwrap()
+                    .subtree_hashes()[""]
+            );
+        }
+
+        let reordered = json!({"d": {"e": "foo"}, "h": 3, "f/g": [], "a": {"b": [1, {"c": 2}]}});
+        assert_eq!(reordered.subtree_hashes(), hashes);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_checks_json_pointer_existence() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"a": {"b": [1, null]}});
+
+        let tests = [
+            ("", Some(value.clone())),
+            ("/a/b", Some(json!([1, null]))),
+            ("/a/b/1", Some(Value::Static(StaticNode::Null))),
+            ("/a/b/2", None),
+            ("/a/c", None),
+            ("/a/b/1/c", None),
+        ];
+
+        for (s, expected_value) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                value.exists(&pointer),
+                expected_value.is_some(),
+                "Existence of '{}' JSON pointer",
+                pointer
+            );
+            assert_eq!(
+                value.get_at(&pointer),
+                expected_value.as_ref().ok_or(Error::KeyNotFound),
+                "Value at '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_resolves_json_pointer_with_detailed_error() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"arr": [1, {"a": "b"}], "obj": {"x": null}, "s": "foo"});
+
+        assert_eq!(value.resolve(&Pointer::root())?, &value);
+        assert_eq!(value.resolve(&Pointer::new("/arr/1/a")?)?, &Value::from("b"));
+        assert_eq!(
+            value.resolve(&Pointer::new("/obj/x")?)?,
+            &Value::Static(StaticNode::Null)
+        );
+
+        let tests = [
+            ("/arr/99", "/arr/99", Error::IndexOutOfBounds { index: 99, len: 2 }),
+            ("/arr/-", "/arr/-", Error::IndexOutOfBounds { index: 2, len: 2 }),
+            ("/arr/foo/a", "/arr/foo", Error::InvalidIndex("foo".to_string())),
+            ("/arr/01", "/arr/01", Error::InvalidIndex("01".to_string())),
+            ("/arr/+5/a", "/arr/+5", Error::InvalidIndex("+5".to_string())),
+            ("/obj/y/z", "/obj/y", Error::KeyNotFound),
+            ("/arr/1/b", "/arr/1/b", Error::KeyNotFound),
+            ("/s/0", "/s/0", Error::NotAContainer),
+            ("/obj/x/y", "/obj/x/y", Error::NotAContainer),
+        ];
+
+        for (s, expected_pointer, expected_source) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                value.resolve(&pointer),
+                Err(Error::UnresolvedToken {
+                    pointer: expected_pointer.to_string(),
+                    source: Box::new(expected_source),
+                }),
+                "Resolution of '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_chains_insertions() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"a": 1})
+            .with_inserted(&Pointer::new("/b/c")?, 2)?
+            .with_inserted(&Pointer::new("/a")?, 3)?;
+
+        assert_eq!(value, json!({"a": 3, "b": {"c": 2}}));
+        assert_eq!(
+            value.with_inserted(&Pointer::new("/a/d")?, 4),
+            Err(Error::UnsupportedInsertion)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_collects_all_keys() {
+        let value = json!({
+            "a": {"b": 1, "a/~": [{"c": null, "b": {}}, [{"d": true}]]},
+            "e": [],
+            "": "empty"
+        });
+
+        assert_eq!(
+            value.all_keys().into_iter().collect::<Vec<_>>(),
+            vec!["", "a", "a/~", "b", "c", "d", "e"]
+        );
+        assert!(json!([1, [2]]).all_keys().is_empty());
+    }
+
+    #[test]
+    fn it_walks_json_value_entries() -> Result<(), Box<dyn std::error::Error>> {
+        let value = json!({"a/b": {"c~d": [1, {"": null}]}, "e": []});
+
+        let expected_entries = [
+            ("", value.clone()),
+            ("/a~1b", json!({"c~d": [1, {"": null}]})),
+            ("/a~1b/c~0d", json!([1, {"": null}])),
+            ("/a~1b/c~0d/0", Value::from(1)),
+            ("/a~1b/c~0d/1", json!({"": null})),
+            ("/a~1b/c~0d/1/", Value::Static(StaticNode::Null)),
+            ("/e", json!([])),
+        ];
+
+        let entries = ValueExt::entries(&value)
+            .map(|(pointer, value)| (pointer.to_string(), value.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            entries,
+            expected_entries
+                .into_iter()
+                .map(|(s, value)| (s.to_string(), value))
+                .collect::<Vec<_>>()
+        );
+
+        for (pointer, entry_value) in ValueExt::entries(&value) {
+            assert_eq!(ValueExt::pointer(&value, &pointer), Some(entry_value));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_round_trips_json_value_through_flat_map() -> Result<(), Box<dyn std::error::Error>> {
+        let tests = [
+            json!({"a": {"b": [1, "two", {"c": null}], "d/~": true}, "e": {}, "f": [], "": 1.5}),
+            json!([[0], {"x": [[]]}, "s", false, null, 1, 2, 3, 4, 5, 6, 7]),
+            Value::from("scalar"),
+            json!({}),
+        ];
+
+        for value in tests {
+            let map = value.clone().into_flat_map();
+
+            assert_eq!(map.len(), value.flatten_to_depth(usize::MAX).len());
+
+            for (pointer, leaf_value) in &map {
+                assert_eq!(ValueExt::pointer(&value, pointer), Some(leaf_value));
+            }
+
+            assert_eq!(Value::from_flat_map(map)?, value);
+        }
+
+        let map = [
+            (Pointer::new("/a")?, Value::from(1)),
+            (Pointer::new("/a/b")?, Value::from(2)),
+        ];
+
+        assert_eq!(
+            Value::from_flat_map(map.into_iter().collect()),
+            Err(Error::KeyAlreadyExists)
+        );
+        assert_eq!(Value::from_flat_map(HashMap::new()), Err(Error::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_diffs_json_values_into_json_patch() -> Result<(), Error> {
+        let tests = [
+            (json!({ "a": 1 }), json!({ "a": 1 })),
+            (
+                json!({ "a": 1, "b": { "c": [1, 2, 3] } }),
+                json!({ "a": "1", "b": { "c": [1, 4] }, "d": null }),
+            ),
+            (
+                json!({ "a/b": { "c~d": 1, "~1": [] } }),
+                json!({ "a/b": { "c~d": 2, "~1": [true, false] }, "": 3 }),
+            ),
+            (json!([1, { "a": [2] }, 3, 4]), json!([{ "a": [2, 5] }, 1])),
+            (json!({ "a": [1, 2] }), json!(["a"])),
+            (json!({ "a": { "b": 1 } }), json!({ "b": { "a": 1 } })),
+        ];
+
+        for (value, other) in tests {
+            let patch = value.diff(&other);
+            let mut patched_value = value.clone();
+
+            patched_value.apply_patch(&patch)?;
+
+            assert_eq!(patched_value, other, "Patched JSON value of {:?} diff", patch);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_checks_json_pointer_policy() -> Result<(), Error> {
+        let value = json!({ "name": "foo", "roles": ["admin"], "secret": null });
+        let required = [Pointer::new("/name")?, Pointer::new("/roles/0")?];
+        let forbidden = [Pointer::new("/password")?, Pointer::new("/roles/1")?];
+
+        assert_eq!(value.check_policy(&required, &forbidden), Ok(()));
+        assert_eq!(value.check_policy(&[], &[]), Ok(()));
+
+        let required = [Pointer::new("/name")?, Pointer::new("/age")?, Pointer::new("/roles/1")?];
+
+        assert_eq!(
+            value.check_policy(&required, &forbidden),
+            Err(PolicyViolation {
+                missing: vec![Pointer::new("/age")?, Pointer::new("/roles/1")?],
+                forbidden: vec![],
+            })
+        );
+
+        let forbidden = [
+            Pointer::new("/secret")?,
+            Pointer::new("/password")?,
+            Pointer::new("/roles")?,
+        ];
+
+        assert_eq!(
+            value.check_policy(&[Pointer::new("/name")?], &forbidden),
+            Err(PolicyViolation {
+                missing: vec![],
+                forbidden: vec![Pointer::new("/secret")?, Pointer::new("/roles")?],
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_sibling_json_values() -> Result<(), Error> {
+        let value = json!({ "a": { "b": 1, "c/d": [2, 3, 4] }, "e": null });
+
+        let siblings = value.siblings_at(&Pointer::new("/a/b")?);
+        assert_eq!(siblings, Some(vec![(Pointer::new("/a/c~1d")?, &json!([2, 3, 4]))]));
+
+        let siblings = value.siblings_at(&Pointer::new("/a/c~1d/1")?);
+        assert_eq!(
+            siblings,
+            Some(vec![
+                (Pointer::new("/a/c~1d/0")?, &2.into()),
+                (Pointer::new("/a/c~1d/2")?, &4.into())
+            ])
+        );
+
+        let siblings = value.siblings_at(&Pointer::new("/e")?);
+        assert_eq!(siblings.map(|siblings| siblings.len()), Some(1));
+
+        let siblings = value.siblings_at(&Pointer::new("/a/c~1d/0/0")?);
+        assert_eq!(siblings, None);
+
+        let siblings = value.siblings_at(&Pointer::new("/a/missing")?);
+        assert_eq!(siblings, None);
+
+        let siblings = value.siblings_at(&Pointer::root());
+        assert_eq!(siblings, None);
+
+        let value = json!({ "a": [1] });
+        let siblings = value.siblings_at(&Pointer::new("/a/0")?);
+        assert_eq!(siblings, Some(vec![]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_replaces_json_value_returning_old_and_new_ones() -> Result<(), Error> {
+        let mut value = json!({ "a": { "b": [1, 2] }, "c": null });
+
+        let (old_value, new_value) = value.replace_returning(&Pointer::new("/a/b/1")?, "x")?;
+        assert_eq!(old_value, Value::from(2));
+        assert_eq!(*new_value, Value::from("x"));
+
+        *new_value = true.into();
+        assert_eq!(value, json!({ "a": { "b": [1, true] }, "c": null }));
+
+        let (old_value, new_value) = value.replace_returning(&Pointer::new("/a")?, json!({}))?;
+        assert_eq!(old_value, json!({ "b": [1, true] }));
+        ValueExt::insert(new_value, "d".to_string(), 4)?;
+        assert_eq!(value, json!({ "a": { "d": 4 }, "c": null }));
+
+        let (old_value, _) = value.replace_returning(&Pointer::root(), json!([]))?;
+        assert_eq!(old_value, json!({ "a": { "d": 4 }, "c": null }));
+        assert_eq!(value, json!([]));
+
+        let result = value
+            .replace_returning(&Pointer::new("/0")?, 1)
+            .map(|(old_value, _)| old_value);
+        assert_eq!(result, Err(Error::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_checks_existence_of_many_json_pointers() -> Result<(), Error> {
+        let value = json!({
+            "a": { "b": 1, "c": [2, { "d": null }], "e/f": { "~": true } },
+            "": { "": 0 },
+        });
+        let pointers = [
+            "",
+            "/a",
+            "/a/b",
+            "/a/b/c",
+            "/a/c/1/d",
+            "/a/c/0",
+            "/a/c/2",
+            "/a/c/01",
+            "/a/c/-",
+            "/a/e~1f/~0",
+            "/a/e~1f/~1",
+            "/a/e/f",
+            "/a/c/1",
+            "/b",
+            "/",
+            "//",
+            "///",
+            "/a/c/1/d/e",
+            "/a/b",
+        ]
+        .into_iter()
+        .map(Pointer::new)
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let mask = value.contains_all_mask(&pointers);
+        let expected_mask = pointers.iter().map(|pointer| value.exists(pointer)).collect::<Vec<_>>();
+
+        assert_eq!(mask, expected_mask);
+        assert!(value.contains_all_mask(&[]).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_swaps_json_values() -> Result<(), Error> {
+        let mut value = json!({ "a": { "b": 1, "c": [2, 3] }, "d": { "e": { "f": "g" } } });
+
+        value.swap_at(&Pointer::new("/a/b")?, &Pointer::new("/a/c")?)?;
+        assert_eq!(
+            value,
+            json!({ "a": { "b": [2, 3], "c": 1 }, "d": { "e": { "f": "g" } } })
+        );
+
+        value.swap_at(&Pointer::new("/a/b/0")?, &Pointer::new("/a/b/1")?)?;
+        assert_eq!(
+            value,
+            json!({ "a": { "b": [3, 2], "c": 1 }, "d": { "e": { "f": "g" } } })
+        );
+
+        value.swap_at(&Pointer::new("/a/b/1")?, &Pointer::new("/d/e")?)?;
+        assert_eq!(
+            value,
+            json!({ "a": { "b": [3, { "f": "g" }], "c": 1 }, "d": { "e": 2 } })
+        );
+
+        value.swap_at(&Pointer::new("/a/c")?, &Pointer::new("/a/c")?)?;
+        assert_eq!(
+            value,
+            json!({ "a": { "b": [3, { "f": "g" }], "c": 1 }, "d": { "e": 2 } })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_swapping_overlapping_or_missing_json_values() -> Result<(), Error> {
+        let mut value = json!({ "a": { "b": { "c": 1 } }, "d": 2 });
+        let original_value = value.clone();
+
+        let tests = [
+            ("/a", "/a/b/c", Error::OverlappingPointers),
+            ("/a/b/c", "/a", Error::OverlappingPointers),
+            ("", "/d", Error::OverlappingPointers),
+            ("/a/b", "/missing", Error::KeyNotFound),
+            ("/missing", "/d", Error::KeyNotFound),
+            ("/a", "/a/missing", Error::KeyNotFound),
+        ];
+
+        for (a, b, expected_error) in tests {
+            assert_eq!(
+                value.swap_at(&Pointer::new(a)?, &Pointer::new(b)?),
+                Err(expected_error),
+                "Swap of '{}' and '{}' JSON pointers",
+                a,
+                b
+            );
+            assert_eq!(value, original_value);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_many_json_values() -> Result<(), Error> {
+        let value = json!({ "a": { "b": [1, 2] }, "c": null });
+
+        let [a, b, c, d] = value.get_many([
+            &Pointer::new("/a/b/1")?,
+            &Pointer::new("/a/missing")?,
+            &Pointer::new("/c")?,
+            &Pointer::root(),
+        ]);
+        assert_eq!(a, Some(&2.into()));
+        assert_eq!(b, None);
+        assert_eq!(c, Some(&Value::Static(StaticNode::Null)));
+        assert_eq!(d, Some(&value));
+
+        assert_eq!(value.get_many([]), []);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_replaces_substrings_in_json_strings() {
+        let mut value = json!({
+            "{{name}}": "hello {{name}}",
+            "a": ["{{name}}{{name}}", "{{other}}", 1, null, { "b": "{{name}}", "c": true }],
+            "d": "{{name2}}",
+        });
+
+        assert_eq!(value.replace_in_strings("{{name}}", "foo"), 3);
+        assert_eq!(
+            value,
+            json!({
+                "{{name}}": "hello foo",
+                "a": ["foofoo", "{{other}}", 1, null, { "b": "foo", "c": true }],
+                "d": "{{name2}}",
+            })
+        );
+
+        assert_eq!(value.replace_in_strings("{{name}}", "foo"), 0);
+        assert_eq!(value.replace_in_strings("", "foo"), 0);
+        assert_eq!(value.replace_in_strings("foo", "foo"), 0);
+
+        let mut value = Value::from("abc");
+        assert_eq!(value.replace_in_strings("b", ""), 1);
+        assert_eq!(value, Value::from("ac"));
+    }
+
+    #[test]
+    fn it_replaces_existing_json_values_only() -> Result<(), Error> {
+        let mut value = json!({ "a": { "b": 1 }, "c": [2, 3], "": null });
+
+        let tests = [
+            ("/a/b", Ok(Value::from(1))),
+            ("/c/1", Ok(Value::from(3))),
+            ("/", Ok(Value::Static(StaticNode::Null))),
+            ("/a/missing", Err(Error::KeyNotFound)),
+            ("/c/2", Err(Error::KeyNotFound)),
+            ("/c/-", Err(Error::KeyNotFound)),
+            ("/a/b/c", Err(Error::KeyNotFound)),
+        ];
+
+        for (s, expected_result) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                value.replace_at(&pointer, "x"),
+                expected_result,
+                "Replacement of '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        assert_eq!(value, json!({ "a": { "b": "x" }, "c": [2, "x"], "": "x" }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_queries_json_values_matching_pattern() -> Result<(), Error> {
+        let value = json!({
+            "items": [{ "name": "a", "tags": ["x"] }, { "id": 1 }, { "name": "b", "tags": ["y", "z"] }],
+            "users": { "foo": { "age": 1 }, "bar": { "age": 2 }, "*": { "age": 3 } },
+        });
+
+        let tests = [
+            (
+                "/items/*/name",
+                vec![("/items/0/name", Value::from("a")), ("/items/2/name", Value::from("b"))],
+            ),
+            (
+                "/users/*/age",
+                vec![
+                    ("/users/*/age", Value::from(3)),
+                    ("/users/bar/age", Value::from(2)),
+                    ("/users/foo/age", Value::from(1)),
+                ],
+            ),
+            ("/users/~2/age", vec![("/users/*/age", Value::from(3))]),
+            (
+                "/items/*/tags/*",
+                vec![
+                    ("/items/0/tags/0", Value::from("x")),
+                    ("/items/2/tags/0", Value::from("y")),
+                    ("/items/2/tags/1", Value::from("z")),
+                ],
+            ),
+            ("/items/*/missing", vec![]),
+        ];
+
+        for (s, expected_values) in tests {
+            let pattern = PointerPattern::new(s)?;
+            // JSON object members order depends on the JSON value representation.
+            let mut values = value.query(&pattern);
+            values.sort_by(|(pointer, _), (other, _)| pointer.cmp(other));
+
+            let expected_values = expected_values
+                .iter()
+                .map(|(s, value)| Ok((Pointer::new(*s)?, value)))
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            assert_eq!(values, expected_values, "Query of '{}' pattern", pattern);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_or_inserts_json_value() -> Result<(), Error> {
+        let mut value = json!({ "a": { "b": 1 }, "c": [2], "d": "e" });
+
+        let pointee_value = value.pointer_or_insert_with(&Pointer::new("/a/b")?, || unreachable!())?;
+        assert_eq!(*pointee_value, Value::from(1));
+        *pointee_value = Value::from(3);
+
+        let pointee_value = value.pointer_or_insert_with(&Pointer::new("/a/f")?, || Value::from(4))?;
+        assert_eq!(*pointee_value, Value::from(4));
+
+        let pointee_value = value.pointer_or_insert_with(&Pointer::new("/g/h/i")?, ValueExt::new_object)?;
+        ValueExt::insert(pointee_value, "j".to_string(), 5)?;
+
+        let pointee_value = value.pointer_or_insert_with(&Pointer::new("/c/-")?, || Value::from(6))?;
+        *pointee_value = Value::from(7);
+
+        let pointee_value = value.pointer_or_insert_with(&Pointer::new("/c/2")?, || Value::from(8))?;
+        assert_eq!(*pointee_value, Value::from(8));
+
+        assert_eq!(
+            value,
+            json!({ "a": { "b": 3, "f": 4 }, "c": [2, 7, 8], "d": "e", "g": { "h": { "i": { "j": 5 } } } })
+        );
+
+        let result = value
+            .pointer_or_insert_with(&Pointer::new("/d/e")?, || Value::from(9))
+            .map(|_| ());
+        assert!(result.is_err());
+
+        let result = value
+            .pointer_or_insert_with(&Pointer::new("/c/5")?, || Value::from(9))
+            .map(|_| ());
+        assert!(result.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_length_of_pointee_container() -> Result<(), Error> {
+        let value = json!({ "a": { "b": 1, "c": 2 }, "d": [3, 4, 5], "e": {}, "f": [], "g": "h" });
+
+        for (pointer, expected_len) in [
+            ("", Some(5)),
+            ("/a", Some(2)),
+            ("/d", Some(3)),
+            ("/e", Some(0)),
+            ("/f", Some(0)),
+        ] {
+            let pointer = Pointer::new(pointer)?;
+
+            assert_eq!(
+                value.len_at(&pointer).ok(),
+                expected_len,
+                "length of '{}' JSON pointer",
+                pointer
+            );
+            assert_eq!(
+                value.is_empty_at(&pointer).ok(),
+                expected_len.map(|len| len == 0),
+                "emptiness of '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        assert_eq!(value.object_len(), Some(5));
+        assert_eq!(
+            ValueExt::pointer(&value, &Pointer::new("/d")?).unwrap().object_len(),
+            None
+        );
+        assert!(matches!(value.len_at(&Pointer::new("/g")?), Err(Error::NotAContainer)));
+        assert!(matches!(
+            value.len_at(&Pointer::new("/a/b")?),
+            Err(Error::NotAContainer)
+        ));
+        assert!(matches!(value.len_at(&Pointer::new("/z")?), Err(Error::KeyNotFound)));
+        assert!(matches!(
+            value.is_empty_at(&Pointer::new("/d/9")?),
+            Err(Error::KeyNotFound)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_inserts_all_json_values_or_none() -> Result<(), Error> {
+        let mut value = json!({ "a": { "b": 1 }, "c": [2] });
+
+        value.insert_all([
+            (Pointer::new("/d/e/f")?, Value::from(3)),
+            (Pointer::new("/c/1")?, Value::from(4)),
+            (Pointer::new("/d/e")?, json!({})),
+            (Pointer::new("/d")?, json!({})),
+            (Pointer::new("/a/b")?, Value::from(5)),
+            (Pointer::new("/a/b")?, Value::from(6)),
+        ])?;
+        assert_eq!(value, json!({ "a": { "b": 6 }, "c": [2, 4], "d": { "e": { "f": 3 } } }));
+
+        let original_value = value.clone();
+
+        for entries in [
+            vec![
+                (Pointer::new("/g")?, Value::from(7)),
+                (Pointer::new("/h/i")?, Value::from(8)),
+            ],
+            vec![
+                (Pointer::new("/a/j")?, Value::from(7)),
+                (Pointer::new("/c/9")?, Value::from(8)),
+            ],
+            vec![(Pointer::new("/a/b/k")?, Value::from(7))],
+        ] {
+            assert!(value.insert_all(entries).is_err());
+            assert_eq!(value, original_value);
+        }
+
+        value.insert_all(Vec::new())?;
+        assert_eq!(value, original_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_resolves_relative_json_pointer() -> Result<(), Error> {
+        let value = json!({ "foo": ["bar", "baz"], "highly": { "nested": { "objects": true } } });
+        let base = Pointer::new("/foo/1")?;
+
+        let tests = [
+            ("0", Some(RelativeTarget::Value(&value["foo"][1]))),
+            ("1/0", Some(RelativeTarget::Value(&value["foo"][0]))),
+            (
+                "2/highly/nested/objects",
+                Some(RelativeTarget::Value(&value["highly"]["nested"]["objects"])),
+            ),
+            ("2", Some(RelativeTarget::Value(&value))),
+            ("0#", Some(RelativeTarget::Index(1))),
+            ("1#", Some(RelativeTarget::Key("foo".to_string()))),
+            ("2#", None),
+            ("3", None),
+            ("3#", None),
+            ("1/9", None),
+        ];
+
+        for (s, expected_target) in tests {
+            let relative_pointer = RelativePointer::new(s)?;
+
+            assert_eq!(
+                value.resolve_relative(&base, &relative_pointer),
+                expected_target,
+                "Resolution of '{}' relative JSON pointer",
+                s
+            );
+        }
+
+        let relative_pointer = RelativePointer::new("0#")?;
+        assert_eq!(
+            value.resolve_relative(&Pointer::new("/highly/nested")?, &relative_pointer),
+            Some(RelativeTarget::Key("nested".to_string()))
+        );
+        assert_eq!(
+            value.resolve_relative(&Pointer::new("/foo/9")?, &relative_pointer),
+            None
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_reports_failing_location_of_insertion() -> Result<(), Error> {
+        let mut value = json!({ "a": { "b": {} }, "c": [1], "d": "e" });
+
+        assert_eq!(value.insert_at_verbose(&Pointer::new("/a/b/f")?, 2)?, None);
+        assert_eq!(
+            value.insert_at_verbose(&Pointer::new("/c/0")?, 3)?,
+            Some(Value::from(1))
+        );
+
+        let expected_value = json!({ "a": { "b": { "f": 2 } }, "c": [3], "d": "e" });
+        assert_eq!(value, expected_value);
+
+        let tests = [
+            ("/g/h/i", "/g", Error::KeyNotFound),
+            ("/a/g/h/i", "/a/g", Error::KeyNotFound),
+            ("/a/b/f/g/h", "/a/b/f/g", Error::NotAContainer),
+            ("/c/5/g", "/c/5", Error::IndexOutOfBounds { index: 5, len: 1 }),
+            ("/c/5", "/c/5", Error::IndexOutOfBounds { index: 5, len: 1 }),
+            ("/c/g", "/c/g", Error::InvalidIndex("g".to_string())),
+            ("/c/01/g", "/c/01", Error::InvalidIndex("01".to_string())),
+            ("/d/g", "/d/g", Error::UnsupportedInsertion),
+        ];
+
+        for (s, expected_pointer, expected_source) in tests {
+            let pointer = Pointer::new(s)?;
+
+            assert_eq!(
+                value.insert_at_verbose(&pointer, 4),
+                Err(Error::UnresolvedToken {
+                    pointer: expected_pointer.to_string(),
+                    source: Box::new(expected_source),
+                }),
+                "Insertion at '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        assert_eq!(value, expected_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_ensures_pointee_json_container() -> Result<(), Error> {
+        let mut value = json!({ "a": { "b": 1 }, "c": [{}] });
+
+        let object = value.ensure_object_mut(&Pointer::new("/d/e/f")?)?;
+        ValueExt::insert(object, "g".to_string(), 2)?;
+        ValueExt::insert(object, "h".to_string(), 3)?;
+
+        let object = value.ensure_object_mut(&Pointer::new("/d/e/f")?)?;
+        ValueExt::insert(object, "i".to_string(), 4)?;
+
+        value.ensure_object_mut(&Pointer::new("/c/0")?)?;
+        value.ensure_array_mut(&Pointer::new("/c/0/j")?)?.push(Value::from(5));
+        value.ensure_array_mut(&Pointer::new("/c/0/j")?)?.push(Value::from(6));
+        value.ensure_array_mut(&Pointer::new("/c/-")?)?.push(Value::from(7));
+
+        let expected_value = json!({
+            "a": { "b": 1 },
+            "c": [{ "j": [5, 6] }, [7]],
+            "d": { "e": { "f": { "g": 2, "h": 3, "i": 4 } } }
+        });
+        assert_eq!(value, expected_value);
+
+        let tests = [
+            (
+                "/a/b",
+                Error::TypeMismatch {
+                    expected: "object",
+                    found: "number",
+                },
+            ),
+            (
+                "/c",
+                Error::TypeMismatch {
+                    expected: "object",
+                    found: "array",
+                },
+            ),
+            ("/a/b/k", Error::UnsupportedInsertion),
+            ("/c/5", Error::IndexOutOfBounds { index: 5, len: 2 }),
+        ];
+
+        for (s, expected_error) in tests {
+            let result = value.ensure_object_mut(&Pointer::new(s)?).map(|_| ());
+
+            assert_eq!(result, Err(expected_error), "Object at '{}' JSON pointer", s);
+        }
+
+        let result = value.ensure_array_mut(&Pointer::new("/d")?).map(|_| ());
+        assert_eq!(
+            result,
+            Err(Error::TypeMismatch {
+                expected: "array",
+                found: "object"
+            })
+        );
+
+        assert_eq!(value, expected_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_flattens_leaf_json_values() -> Result<(), Error> {
+        let value = json!({
+            "a": { "b": [1, [2, 3], { "c/d": "e" }] },
+            "f~g": null,
+            "h": {},
+            "i": [],
+            "j": true
+        });
+
+        let flattened = value.flatten();
+        let expected_entries = [
+            ("/f~0g", Value::Static(StaticNode::Null)),
+            ("/h", json!({})),
+            ("/i", json!([])),
+            ("/j", Value::from(true)),
+            ("/a/b/0", Value::from(1)),
+            ("/a/b/1/0", Value::from(2)),
+            ("/a/b/1/1", Value::from(3)),
+            ("/a/b/2/c~1d", Value::from("e")),
+        ];
+
+        assert_eq!(
+            flattened.keys().map(Pointer::as_str).collect::<Vec<_>>(),
+            expected_entries.iter().map(|(s, _)| *s).collect::<Vec<_>>()
+        );
+
+        for (s, expected_value) in &expected_entries {
+            assert_eq!(
+                flattened.get(&Pointer::new(*s)?),
+                Some(&expected_value),
+                "Leaf value of '{}' JSON pointer",
+                s
+            );
+        }
+
+        assert_eq!(
+            Value::from(42).flatten().into_iter().collect::<Vec<_>>(),
+            vec![(Pointer::root(), &Value::from(42))]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_splices_values_into_json_array() -> Result<(), Error> {
+        let mut value = json!({ "a": [1, 5], "b": { "c": 2 }, "d": "e" });
+
+        value.splice_at(&Pointer::new("/a/1")?, [Value::from(2), Value::from(3), Value::from(4)])?;
+        assert_eq!(value["a"], json!([1, 2, 3, 4, 5]));
+
+        value.splice_at(&Pointer::new("/a/-")?, [Value::from(6), Value::from(7)])?;
+        value.splice_at(&Pointer::new("/a/7")?, [Value::from(8)])?;
+        value.splice_at(&Pointer::new("/a/0")?, [Value::from(0)])?;
+        value.splice_at(&Pointer::new("/a/4")?, Vec::new())?;
+        assert_eq!(value["a"], json!([0, 1, 2, 3, 4, 5, 6, 7, 8]));
+
+        let expected_value = value.clone();
+
+        let tests = [
+            ("", Error::UnexpectedRootPointer),
+            ("/a/10", Error::IndexOutOfBounds { index: 10, len: 9 }),
+            ("/a/f", Error::InvalidIndex("f".to_string())),
+            ("/a/01", Error::InvalidIndex("01".to_string())),
+            ("/a/+1", Error::InvalidIndex("+1".to_string())),
+            ("/b/0", Error::NotAnArray),
+            ("/d/0", Error::NotAnArray),
+            ("/z/0", Error::KeyNotFound),
+        ];
+
+        for (s, expected_error) in tests {
+            assert_eq!(
+                value.splice_at(&Pointer::new(s)?, [Value::from(9)]),
+                Err(expected_error),
+                "Splicing at '{}' JSON pointer",
+                s
+            );
+        }
+
+        assert_eq!(value, expected_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_patch_with_non_canonical_json_array_index() -> Result<(), Error> {
+        let mut value = json!({ "arr": [1, 2] });
+        let expected_value = value.clone();
+
+        for token in ["01", "+1"] {
+            let path = Pointer::new(format!("/arr/{}", token))?;
+            let operations = [
+                PatchOperation::Add {
+                    path: path.clone(),
+                    value: Value::from(3),
+                },
+                PatchOperation::Remove { path },
+            ];
+
+            for operation in operations {
+                assert!(
+                    value.apply_patch(&Patch::new(vec![operation])).is_err(),
+                    "Patch at '{}' token",
+                    token
+                );
+            }
+
+            assert_eq!(
+                value.apply_patch(&Patch::new(vec![PatchOperation::Add {
+                    path: Pointer::new(format!("/arr/{}", token))?,
+                    value: Value::from(3),
+                }])),
+                Err(Error::InvalidIndex(token.to_string()))
+            );
+        }
+
+        assert_eq!(value, expected_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_appends_value_through_vacant_entry() -> Result<(), Box<dyn std::error::Error>> {
+        let mut value = json!({"a": [1]});
+
+        for (s, inserted_value) in [("/a/-", 2), ("/a/2", 3)] {
+            match value.entry_at(&Pointer::new(s)?)? {
+                PointerEntry::Vacant(entry) => {
+                    let pointee_value = entry.insert(inserted_value)?;
+
+                    assert_eq!(*pointee_value, Value::from(inserted_value), "Value at '{}' entry", s);
+                    *pointee_value = Value::from(inserted_value * 10);
+                }
+                PointerEntry::Occupied(_) => panic!("'{}' entry is vacant", s),
+            }
+        }
+
+        assert_eq!(value, json!({"a": [1, 20, 30]}));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_masks_json_containers_matching_patterns() -> Result<(), Error> {
+        let value = json!({
+            "name": "foo",
+            "secrets": { "api": { "token": "bar" }, "keys": ["baz", "qux"] },
+            "items": [{ "id": 1, "private": [2] }, { "id": 3 }]
+        });
+        let patterns = [
+            PointerPattern::new("/secrets")?,
+            PointerPattern::new("/secrets/api/token")?,
+            PointerPattern::new("/items/*/private")?,
+        ];
+
+        assert_eq!(
+            value.masked(&patterns, &Value::Static(StaticNode::Null)),
+            json!({ "name": "foo", "secrets": null, "items": [{ "id": 1, "private": null }, { "id": 3 }] })
+        );
+        assert_eq!(
+            value.masked(&[PointerPattern::new("/**")?], &Value::Static(StaticNode::Null)),
+            Value::Static(StaticNode::Null)
+        );
+        assert_eq!(value.masked(&[], &Value::Static(StaticNode::Null)), value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_swaps_json_values_through_aliased_json_pointers() -> Result<(), Error> {
+        let mut value = json!({ "~": { "a": 1 }, "b": 2 });
+        let original_value = value.clone();
+
+        value.swap_at(&Pointer::new("/~")?, &Pointer::new("/~0")?)?;
+        assert_eq!(value, original_value);
+
+        assert_eq!(
+            value.swap_at(&Pointer::new("/~")?, &Pointer::new("/~0/a")?),
+            Err(Error::OverlappingPointers)
+        );
+        assert_eq!(value, original_value);
+
+        value.swap_at(&Pointer::new("/~/a")?, &Pointer::new("/b")?)?;
+        assert_eq!(value, json!({ "~": { "a": 2 }, "b": 1 }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_accesses_simd_json_values() {
+        let value = json!({ "a": "b", "c": 1, "d": 2.5, "e": true, "f": null, "g": [1, 2] });
+        let tests = [
+            ("/a", Some("b"), None, None, None, false),
+            ("/c", None, Some(1), Some(1.0), None, false),
+            ("/d", None, None, Some(2.5), None, false),
+            ("/e", None, None, None, Some(true), false),
+            ("/f", None, None, None, None, true),
+            ("/g", None, None, None, None, false),
+        ];
+
+        for (s, expected_str, expected_i64, expected_f64, expected_bool, expected_null) in tests {
+            let pointee_value = ValueExt::pointer(&value, &Pointer::new(s).unwrap()).unwrap();
+
+            assert_eq!(
+                ValueExt::as_str(pointee_value),
+                expected_str,
+                "String at '{}' JSON pointer",
+                s
+            );
+            assert_eq!(
+                ValueExt::as_i64(pointee_value),
+                expected_i64,
+                "Integer at '{}' JSON pointer",
+                s
+            );
+            assert_eq!(
+                ValueExt::as_f64(pointee_value),
+                expected_f64,
+                "Float at '{}' JSON pointer",
+                s
+            );
+            assert_eq!(
+                ValueExt::as_bool(pointee_value),
+                expected_bool,
+                "Boolean at '{}' JSON pointer",
+                s
+            );
+            assert_eq!(
+                ValueExt::is_null(pointee_value),
+                expected_null,
+                "Null at '{}' JSON pointer",
+                s
+            );
+            assert!(!ValueExt::is_object(pointee_value), "Object at '{}' JSON pointer", s);
+        }
+
+        assert!(ValueExt::is_object(&value));
+        assert_eq!(<Value as ValueExt>::new_object(), json!({}));
+        assert_eq!(<Value as ValueExt>::new_array(), json!([]));
+        assert_eq!(<Value as ValueExt>::new_null(), json!(null));
+    }
+
+    #[test]
+    fn it_gets_and_removes_children_of_simd_json_values() {
+        let mut value = json!({ "a": [1, 2, 3], "b": "c" });
+
+        let mut children = value.children();
+        children.sort_by(|(key, _), (other_key, _)| key.cmp(other_key));
+        assert_eq!(
+            children,
+            vec![("a".to_string(), &json!([1, 2, 3])), ("b".to_string(), &json!("c"))]
+        );
+
+        let array = value.lookup_token_mut("a").unwrap();
+        assert_eq!(
+            array.children(),
+            vec![
+                ("0".to_string(), &json!(1)),
+                ("1".to_string(), &json!(2)),
+                ("2".to_string(), &json!(3))
+            ]
+        );
+        assert_eq!(array.remove_token("1"), Some(json!(2)));
+        assert_eq!(array.remove_token("2"), None);
+        assert_eq!(array.remove_token("-"), None);
+
+        assert_eq!(value.remove_token("b"), Some(json!("c")));
+        assert_eq!(value.remove_token("b"), None);
+        assert_eq!(value.lookup_token_mut("a").unwrap().remove_token("b"), None);
+        assert_eq!(value, json!({ "a": [1, 3] }));
+        assert!(json!("a").children().is_empty());
+    }
+
+    #[test]
+    fn it_tries_to_insert_json_values() -> Result<(), Error> {
+        let mut value = json!({ "a": { "b": 1 }, "c": [2] });
+
+        value.try_insert_at(&Pointer::new("/a/d")?, 3)?;
+        value.try_insert_at(&Pointer::new("/c/-")?, 4)?;
+        assert_eq!(value, json!({ "a": { "b": 1, "d": 3 }, "c": [2, 4] }));
+
+        let expected_value = value.clone();
+
+        for s in ["", "/a", "/a/b", "/c/0"] {
+            assert_eq!(
+                value.try_insert_at(&Pointer::new(s)?, 5),
+                Err(Error::UnresolvedToken {
+                    pointer: s.to_string(),
+                    source: Box::new(Error::KeyAlreadyExists),
+                }),
+                "Insertion at '{}' JSON pointer",
+                s
+            );
+        }
+
+        assert_eq!(value.try_insert_at(&Pointer::new("/z/a")?, 5), Err(Error::KeyNotFound));
+        assert_eq!(value, expected_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_inserts_value_in_json_array_by_shifting_elements() -> Result<(), Error> {
+        let mut value = json!({ "arr": [1, 2, 3], "obj": { "a": 1 } });
+
+        let tests = [
+            ("/arr/1", Some(Value::from(2))),
+            ("/arr/0", Some(Value::from(1))),
+            ("/arr/5", None),
+            ("/arr/-", None),
+            ("/obj/a", Some(Value::from(1))),
+            ("/obj/b", None),
+        ];
+
+        for (s, expected_old_value) in tests {
+            let old_value = value.insert_at_shifting(&Pointer::new(s)?, 42)?;
+
+            assert_eq!(old_value, expected_old_value, "Old value at '{}' JSON pointer", s);
+        }
+
+        assert_eq!(
+            value,
+            json!({ "arr": [42, 1, 42, 2, 3, 42, 42], "obj": { "a": 42, "b": 42 } })
+        );
+
+        let expected_value = value.clone();
+        let tests = [
+            ("/arr/8", Error::IndexOutOfBounds { index: 8, len: 7 }),
+            ("/arr/foo", Error::InvalidIndex("foo".to_string())),
+            ("/arr/01", Error::InvalidIndex("01".to_string())),
+            ("/missing/0", Error::KeyNotFound),
+            ("/arr/0/a", Error::UnsupportedInsertion),
+        ];
+
+        for (s, expected_error) in tests {
+            assert_eq!(
+                value.insert_at_shifting(&Pointer::new(s)?, 0),
+                Err(expected_error),
+                "Insertion at '{}' JSON pointer",
+                s
+            );
+        }
+
+        assert_eq!(value, expected_value);
+        assert_eq!(value.insert_at_shifting(&Pointer::root(), 0)?, Some(expected_value));
+        assert_eq!(value, Value::from(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_flattens_index_keyed_json_object_into_leaf_json_values_only() -> Result<(), Error> {
+        let map = json!({ "0": 1, "1": { "a": {} } }).into_flat_map();
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&Pointer::new("/0")?), Some(&Value::from(1)));
+        assert_eq!(map.get(&Pointer::new("/1/a")?), Some(&json!({})));
+        assert_eq!(Value::from_flat_map(map)?, json!([1, { "a": {} }]));
+
+        let map = [
+            (Pointer::new("/a")?, json!([])),
+            (Pointer::new("/a/0")?, Value::from(2)),
+        ];
+        assert_eq!(
+            Value::from_flat_map(map.into_iter().collect()),
+            Err(Error::KeyAlreadyExists)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_sorts_json_array_mixing_integers_and_floats_exactly() -> Result<(), Error> {
+        let mut value = json!({ "numbers": [
+            9_223_372_036_854_775_807_i64,
+            9.223_372_036_854_776e18,
+            9_223_372_036_854_775_806_i64,
+            -9.223_372_036_854_776e18,
+            -9_223_372_036_854_775_807_i64,
+            1.5,
+            1,
+            2,
+            -1.5,
+            -1
+        ] });
+
+        value.sort_array_at(&Pointer::new("/numbers")?, &Pointer::root())?;
+
+        assert_eq!(
+            value,
+            json!({ "numbers": [
+                -9.223_372_036_854_776e18,
+                -9_223_372_036_854_775_807_i64,
+                -1.5,
+                -1,
+                1,
+                1.5,
+                2,
+                9_223_372_036_854_775_806_i64,
+                9_223_372_036_854_775_807_i64,
+                9.223_372_036_854_776e18
+            ] })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_sets_values_matching_pattern_once_per_subtree() -> Result<(), Error> {
+        let mut value = json!({ "a": { "a": { "b": 1 } }, "c": [{ "a": 2 }] });
+        let count = value.set_where_matches(&PointerPattern::new("/**/a")?, json!({ "a": { "d": 3 } }));
+
+        assert_eq!(count, 2);
+        assert_eq!(
+            value,
+            json!({ "a": { "a": { "d": 3 } }, "c": [{ "a": { "a": { "d": 3 } } }] })
+        );
+
+        Ok(())
+    }
+}
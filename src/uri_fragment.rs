@@ -0,0 +1,98 @@
+use serde::{de, Deserialize, Deserializer, Serializer};
+
+use crate::Pointer;
+
+/// Serializes a JSON pointer into its URI fragment representation, see [`Pointer::to_uri_fragment`].
+///
+/// # Examples
+/// ```
+/// use json_toolkit::Pointer;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Reference {
+///     #[serde(rename = "$ref", with = "json_toolkit::uri_fragment")]
+///     target: Pointer<'static>,
+/// }
+///
+/// let reference = Reference {
+///     target: Pointer::new("/definitions/a b").unwrap(),
+/// };
+///
+/// assert_eq!(serde_json::to_string(&reference).unwrap(), r##"{"$ref":"#/definitions/a%20b"}"##);
+/// ```
+pub fn serialize<S>(pointer: &Pointer<'_>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&pointer.to_uri_fragment())
+}
+
+/// Deserializes a JSON pointer from its URI fragment representation, see [`Pointer::from_uri_fragment`].
+///
+/// # Errors
+/// This function fails if the deserialized string is not a valid URI fragment representation of a JSON pointer.
+pub fn deserialize<'de, 'a, D>(deserializer: D) -> Result<Pointer<'a>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let fragment = String::deserialize(deserializer)?;
+
+    Pointer::from_uri_fragment(&fragment).map_err(de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::Serialize;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Reference {
+        #[serde(with = "crate::uri_fragment")]
+        target: Pointer<'static>,
+    }
+
+    #[test]
+    fn it_round_trips_json_pointer_as_uri_fragment() -> Result<(), serde_json::Error> {
+        for (pointer, fragment) in [
+            ("", "#"),
+            ("/foo/0", "#/foo/0"),
+            ("/a b/c%d", "#/a%20b/c%25d"),
+            ("/m~0n/o~1p", "#/m~0n/o~1p"),
+            ("/é", "#/%C3%A9"),
+        ] {
+            let reference = Reference {
+                target: Pointer::new(pointer).unwrap(),
+            };
+
+            let serialized = serde_json::to_value(&reference)?;
+            assert_eq!(
+                serialized,
+                json!({ "target": fragment }),
+                "serialization of '{}' JSON pointer",
+                pointer
+            );
+            assert_eq!(
+                serde_json::from_value::<Reference>(serialized)?,
+                reference,
+                "deserialization of '{}' JSON pointer",
+                pointer
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_invalid_uri_fragment() {
+        for fragment in ["/foo", "#foo", "#/%ZZ", "#/%C0%AF"] {
+            assert!(
+                serde_json::from_value::<Reference>(json!({ "target": fragment })).is_err(),
+                "'{}' URI fragment",
+                fragment
+            );
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{Pointer, ValueExt};
+
+/// Depth-first iterator over every JSON value of a document alongside its JSON pointer.
+///
+/// This type is returned by [`ValueExt::entries`].
+#[derive(Debug, Clone)]
+pub struct Entries<'v, V> {
+    stack: Vec<(Pointer<'static>, &'v V)>,
+}
+
+impl<'v, V> Entries<'v, V> {
+    /// Creates an `Entries` iterator starting at the given root JSON value.
+    pub(crate) fn new(value: &'v V) -> Self {
+        Self {
+            stack: vec![(Pointer::root(), value)],
+        }
+    }
+}
+
+impl<'v, V: ValueExt> Iterator for Entries<'v, V> {
+    type Item = (Pointer<'static>, &'v V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (pointer, value) = self.stack.pop()?;
+
+        // children are pushed in reverse order so that the first one is popped first.
+        self.stack.extend(
+            value
+                .children()
+                .into_iter()
+                .rev()
+                .map(|(token, child)| (pointer.join(&token), child)),
+        );
+
+        Some((pointer, value))
+    }
+}